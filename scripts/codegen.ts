@@ -2913,7 +2913,7 @@ impl Pattern {
 pub(crate) static PATTERN: Lazy<Vec<(bool, &'static Regex, Pattern)>> = Lazy::new(|| {
     let mut vec = vec![
 ${patternLines.join("")}    ];
-    vec.sort_unstable_by(|(_, _, pat1), (_, _, pat2)| pat2.priority.unwrap_or(0).cmp(&pat1.priority.unwrap_or(0)));
+    vec.sort_unstable_by_key(|(_, _, pat)| std::cmp::Reverse(pat.priority.unwrap_or(0)));
     vec
 });
 `;
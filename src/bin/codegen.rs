@@ -8,15 +8,16 @@ fn translate_pcre2_to_fancy_regex(pattern: &str) -> String {
     pattern.replace(r#"\g<"#, r#"\k<"#)
 }
 
-fn validate_fancy_regex(pattern: &str) -> Result<(), fancy_regex::Error> {
+fn validate_fancy_regex(pattern: &str) -> Result<(), Box<fancy_regex::Error>> {
     // Runtime matching uses multiline mode; validate patterns with the same semantics
     // to ensure codegen fails fast when Linguist introduces syntax we can't run.
     RegexBuilder::new(&format!("(?m){pattern}"))
         .build()
         .map(|_| ())
+        .map_err(Box::new)
 }
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs::{self, File},
     io::{BufWriter, Write},
     iter,
@@ -151,18 +152,23 @@ impl PatternDTO {
                 if let Some(pattern) = named_patterns.get(pattern_name) {
                     // Assume that all named patterns are positive
                     let pattern = PatternDTO::Positive(pattern.clone());
-                    return pattern.to_domain_object_code(named_patterns);
+                    pattern.to_domain_object_code(named_patterns)
                 } else {
                     panic!(
                         "Named pattern: {} not found in named pattern map",
                         pattern_name
                     );
-                };
+                }
             }
         }
     }
 }
 
+#[derive(Deserialize)]
+struct LanguageDTO {
+    color: Option<String>,
+}
+
 #[derive(Clone, Deserialize)]
 #[serde(untagged)]
 enum MaybeMany<T> {
@@ -173,7 +179,13 @@ enum MaybeMany<T> {
 const DISAMBIGUATION_HEURISTICS_FILE: &str = "src/codegen/disambiguation-heuristics-map.rs";
 const TOKEN_LOG_PROBABILITY_FILE: &str = "src/codegen/token-log-probabilities.rs";
 
+const PATH_CLASSIFICATION_FILE: &str = "src/codegen/path-classification-patterns.rs";
+const LINGUIST_LANGUAGES_FILE: &str = "src/codegen/linguist-languages.rs";
+
 const HEURISTICS_SOURCE_FILE: &str = "heuristics.yml";
+const VENDOR_SOURCE_FILE: &str = "vendor.yml";
+const DOCUMENTATION_SOURCE_FILE: &str = "documentation.yml";
+const LANGUAGES_SOURCE_FILE: &str = "languages.yml";
 
 const MAX_TOKEN_BYTES: usize = 32;
 
@@ -186,6 +198,10 @@ fn main() {
 
     create_disambiguation_heuristics_map(heuristics);
 
+    create_path_classification_patterns();
+
+    create_linguist_languages();
+
     // Only train classifier if samples directory exists
     if Path::new("samples").exists() {
         train_classifier();
@@ -306,6 +322,75 @@ fn create_disambiguation_heuristics_map(heuristics: Heuristics) {
     .unwrap();
 }
 
+/// Write Linguist's vendored and documentation path regexes as static slices.
+///
+/// The lists are validated with the `regex` crate because that's what the runtime
+/// compiles them with (as a `RegexSet`).
+fn create_path_classification_patterns() {
+    let mut file = BufWriter::new(File::create(PATH_CLASSIFICATION_FILE).unwrap());
+
+    for (name, source) in [
+        ("VENDOR_PATTERNS", VENDOR_SOURCE_FILE),
+        ("DOCUMENTATION_PATTERNS", DOCUMENTATION_SOURCE_FILE),
+    ] {
+        let patterns: Vec<String> =
+            serde_norway::from_str(&fs::read_to_string(source).unwrap()[..]).unwrap();
+
+        writeln!(&mut file, "static {name}: &[&str] = &[").unwrap();
+        for pattern in patterns.iter() {
+            if let Err(e) = regex::Regex::new(pattern) {
+                panic!("Invalid path pattern in {source}: {pattern}\n{e}");
+            }
+            writeln!(&mut file, "    {pattern:?},").unwrap();
+        }
+        writeln!(&mut file, "];\n").unwrap();
+    }
+}
+
+/// Write the `FileType` -> Linguist language lookup.
+///
+/// Several Linguist languages can resolve to the same `FileType`; the language whose
+/// lowercased name equals the canonical filetype wins, otherwise the first one in
+/// alphabetical order.
+fn create_linguist_languages() {
+    let languages: BTreeMap<String, LanguageDTO> =
+        serde_norway::from_str(&fs::read_to_string(LANGUAGES_SOURCE_FILE).unwrap()[..]).unwrap();
+
+    let mut names: BTreeMap<String, String> = BTreeMap::new();
+    for name in languages.keys() {
+        let Some(ft) = filetype_for_language(name) else {
+            continue;
+        };
+        let variant = format!("{ft:?}");
+        let exact = name.to_lowercase() == ft.canonical();
+        if exact || !names.contains_key(&variant) {
+            names.insert(variant, name.clone());
+        }
+    }
+
+    let mut file = BufWriter::new(File::create(LINGUIST_LANGUAGES_FILE).unwrap());
+    writeln!(&mut file, "const fn linguist_name(file_type: FileType) -> Option<&'static str> {{").unwrap();
+    writeln!(&mut file, "    match file_type {{").unwrap();
+    for (variant, name) in names.iter() {
+        writeln!(&mut file, "        FileType::{variant} => Some({name:?}),").unwrap();
+    }
+    writeln!(&mut file, "        _ => None,").unwrap();
+    writeln!(&mut file, "    }}").unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file).unwrap();
+    writeln!(&mut file, "const fn linguist_color(file_type: FileType) -> Option<&'static str> {{").unwrap();
+    writeln!(&mut file, "    match file_type {{").unwrap();
+    for (variant, name) in names.iter() {
+        if let Some(color) = &languages[name].color {
+            writeln!(&mut file, "        FileType::{variant} => Some({color:?}),").unwrap();
+        }
+    }
+    writeln!(&mut file, "        _ => None,").unwrap();
+    writeln!(&mut file, "    }}").unwrap();
+    writeln!(&mut file, "}}").unwrap();
+}
+
 fn train_classifier() {
     let mut temp_token_count: HashMap<String, HashMap<String, i32>> = HashMap::new();
     let mut temp_total_tokens_count = HashMap::new();
@@ -314,7 +399,7 @@ fn train_classifier() {
         .unwrap()
         .map(|entry| entry.unwrap())
         .filter(|entry| entry.path().is_dir())
-        .map(|language_dir| {
+        .flat_map(|language_dir| {
             let path = language_dir.path();
             let language = path.file_name().unwrap();
             let language = language.to_string_lossy().into_owned();
@@ -331,7 +416,6 @@ fn train_classifier() {
             let language_iter = iter::repeat(language);
             file_paths.zip(language_iter)
         })
-        .flatten()
         .for_each(|(entry, language)| {
             let content = fs::read(entry).unwrap();
 
@@ -349,7 +433,7 @@ fn train_classifier() {
 
                     let tokens_count = temp_token_count
                         .entry(language.clone())
-                        .or_insert(HashMap::new());
+                        .or_default();
 
                     let count = tokens_count.entry(String::from(token)).or_insert(0);
                     *count += 1;
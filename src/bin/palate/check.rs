@@ -0,0 +1,437 @@
+//! `palate check`: a policy of allowed and forbidden file types per path, from the
+//! `[check]` table of `.palate.toml`, evaluated against a scan.
+//!
+//! ```toml
+//! [check]
+//! unknown = "deny"   # files no stage recognized: "allow" (default), "warn" or "deny"
+//!
+//! [[check.rules]]
+//! name = "no new Perl"
+//! forbid = ["perl"]
+//!
+//! [[check.rules]]
+//! paths = [".github/workflows/"]
+//! allow = ["yaml"]
+//!
+//! [[check.rules]]
+//! name = "generated code lives in gen/"
+//! except = ["/gen/"]
+//! generated = false
+//! level = "warn"
+//! ```
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use palate::{DetectionStage, FileType, scan::ScannedFile};
+use toml::Value;
+
+use crate::{
+    config::{strings, table},
+    parse_file_type,
+};
+
+/// How bad a violation is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Level {
+    /// Reported, but `palate check` still succeeds.
+    Warn,
+    /// Fails `palate check`.
+    Deny,
+}
+
+impl Level {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Warn => "warning",
+            Self::Deny => "error",
+        }
+    }
+}
+
+/// The `[check]` table of a config file.
+pub(crate) struct Policy {
+    /// How files that no detection stage recognized are treated; `None` allows them.
+    unknown: Option<Level>,
+    rules: Vec<Rule>,
+}
+
+/// One `[[check.rules]]` entry; every file it applies to must satisfy all of its
+/// constraints.
+struct Rule {
+    name: String,
+    /// Directory the globs are relative to.
+    root: PathBuf,
+    /// `None` applies the rule everywhere.
+    paths: Option<Gitignore>,
+    except: Option<Gitignore>,
+    allow: Option<HashSet<FileType>>,
+    forbid: HashSet<FileType>,
+    /// Whether files must (or must not) be generated.
+    generated: Option<bool>,
+    level: Level,
+}
+
+/// A file that breaks the policy.
+pub(crate) struct Violation {
+    pub(crate) path: PathBuf,
+    pub(crate) file_type: FileType,
+    pub(crate) stage: DetectionStage,
+    /// The rule's name, `unknown` for unrecognized files.
+    pub(crate) rule: String,
+    pub(crate) level: Level,
+    pub(crate) message: String,
+}
+
+impl Policy {
+    /// Parse the `[check]` table; globs are relative to `root`.
+    pub(crate) fn from_value(root: &Path, value: &Value) -> Result<Self, String> {
+        let mut policy = Self {
+            unknown: None,
+            rules: Vec::new(),
+        };
+        for (key, value) in table("check", value)? {
+            match key.as_str() {
+                "unknown" => policy.unknown = level("check.unknown", value)?,
+                "rules" => {
+                    let rules = value
+                        .as_array()
+                        .ok_or("check.rules must be an array of tables")?;
+                    for (index, rule) in rules.iter().enumerate() {
+                        let key = format!("check.rules[{index}]");
+                        policy.rules.push(Rule::from_value(root, &key, rule)?);
+                    }
+                }
+                other => return Err(format!("unknown key `check.{other}`")),
+            }
+        }
+        Ok(policy)
+    }
+
+    /// Every violation in `files`, in file order.
+    pub(crate) fn check(&self, files: &[ScannedFile]) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for file in files {
+            let mut violation = |rule: &str, level, message: String| {
+                violations.push(Violation {
+                    path: file.path.clone(),
+                    file_type: file.file_type,
+                    stage: file.stage,
+                    rule: rule.to_string(),
+                    level,
+                    message,
+                })
+            };
+            if let Some(level) = self
+                .unknown
+                .filter(|_| file.stage == DetectionStage::Fallback)
+            {
+                let message = "no detection stage recognized the file".to_string();
+                violation("unknown", level, message);
+            }
+            for rule in self.rules.iter().filter(|rule| rule.applies_to(&file.path)) {
+                if let Some(message) = rule.broken_by(file) {
+                    violation(&rule.name, rule.level, message);
+                }
+            }
+        }
+        violations
+    }
+}
+
+impl Rule {
+    fn from_value(root: &Path, key: &str, value: &Value) -> Result<Self, String> {
+        let mut rule = Self {
+            name: key.to_string(),
+            root: std::path::absolute(root).unwrap_or_else(|_| root.to_path_buf()),
+            paths: None,
+            except: None,
+            allow: None,
+            forbid: HashSet::new(),
+            generated: None,
+            level: Level::Deny,
+        };
+        for (field, value) in table(key, value)? {
+            let key = format!("{key}.{field}");
+            match field.as_str() {
+                "name" => {
+                    rule.name = value
+                        .as_str()
+                        .ok_or_else(|| format!("{key} must be a string"))?
+                        .to_string();
+                }
+                "paths" => rule.paths = Some(globs(&key, value)?),
+                "except" => rule.except = Some(globs(&key, value)?),
+                "allow" => rule.allow = Some(file_types(&key, value)?),
+                "forbid" => rule.forbid = file_types(&key, value)?,
+                "generated" => {
+                    let generated = value
+                        .as_bool()
+                        .ok_or_else(|| format!("{key} must be true or false"))?;
+                    rule.generated = Some(generated);
+                }
+                "level" => {
+                    rule.level = level(&key, value)?
+                        .ok_or_else(|| format!("{key} must be \"warn\" or \"deny\""))?;
+                }
+                _ => return Err(format!("unknown key `{key}`")),
+            }
+        }
+        Ok(rule)
+    }
+
+    fn applies_to(&self, path: &Path) -> bool {
+        let matches = |globs: &Gitignore| {
+            let Some(relative) = std::path::absolute(path)
+                .ok()
+                .and_then(|path| Some(path.strip_prefix(&self.root).ok()?.to_path_buf()))
+            else {
+                return false;
+            };
+            globs
+                .matched_path_or_any_parents(&relative, false)
+                .is_ignore()
+        };
+        self.paths.as_ref().is_none_or(matches) && !self.except.as_ref().is_some_and(matches)
+    }
+
+    /// Why `file` breaks the rule, if it does.
+    fn broken_by(&self, file: &ScannedFile) -> Option<String> {
+        let canonical = file.file_type.canonical();
+        if self.forbid.contains(&file.file_type) {
+            return Some(format!("{canonical} is forbidden"));
+        }
+        if let Some(allow) = self
+            .allow
+            .as_ref()
+            .filter(|allow| !allow.contains(&file.file_type))
+        {
+            let mut allowed: Vec<&str> = allow.iter().map(|ft| ft.canonical()).collect();
+            allowed.sort_unstable();
+            return Some(format!(
+                "{canonical} is not allowed here (allowed: {})",
+                allowed.join(", ")
+            ));
+        }
+        match self.generated {
+            Some(false) if file.generated => Some("generated files are not allowed here".into()),
+            Some(true) if !file.generated => Some("only generated files are allowed here".into()),
+            _ => None,
+        }
+    }
+}
+
+/// `allow` (`None`), `warn` or `deny`.
+fn level(key: &str, value: &Value) -> Result<Option<Level>, String> {
+    match value.as_str() {
+        Some("allow") => Ok(None),
+        Some("warn") => Ok(Some(Level::Warn)),
+        Some("deny") => Ok(Some(Level::Deny)),
+        _ => Err(format!("{key} must be \"allow\", \"warn\" or \"deny\"")),
+    }
+}
+
+fn globs(key: &str, value: &Value) -> Result<Gitignore, String> {
+    let mut builder = GitignoreBuilder::new("");
+    for glob in strings(key, value)? {
+        builder
+            .add_line(None, glob)
+            .map_err(|err| format!("{key}: {err}"))?;
+    }
+    builder.build().map_err(|err| format!("{key}: {err}"))
+}
+
+fn file_types(key: &str, value: &Value) -> Result<HashSet<FileType>, String> {
+    strings(key, value)?
+        .into_iter()
+        .map(parse_file_type)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use palate::scan::ScanBuilder;
+
+    use super::*;
+
+    /// The violations of the `[check]` table of `config`, with globs relative to `root`,
+    /// for `files` written to a temp dir and scanned the way `palate check` scans; paths
+    /// are relative to the temp dir.
+    fn check(config: &str, root: &str, files: &[(&str, &str)]) -> Vec<Violation> {
+        let dir = tempfile::Builder::new()
+            .prefix("palate-")
+            .tempdir()
+            .unwrap();
+        for (path, content) in files {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let policy = policy(&dir.path().join(root), config).unwrap();
+        let files = ScanBuilder::new(dir.path())
+            .exclude_hidden(false)
+            .exclude_vendored(false)
+            .exclude_documentation(false)
+            .exclude_generated(false)
+            .keep_undetected(true)
+            .count_lines(false)
+            .files();
+        let mut violations = policy.check(&files);
+        for violation in &mut violations {
+            violation.path = violation.path.strip_prefix(dir.path()).unwrap().into();
+        }
+        violations
+    }
+
+    fn policy(root: &Path, config: &str) -> Result<Policy, String> {
+        let config: toml::Table = config.parse().unwrap();
+        Policy::from_value(root, &config["check"])
+    }
+
+    /// `path: message` for every violation.
+    fn messages(violations: &[Violation]) -> Vec<String> {
+        violations
+            .iter()
+            .map(|violation| format!("{}: {}", violation.path.display(), violation.message))
+            .collect()
+    }
+
+    const GO: &[(&str, &str)] = &[
+        ("main.go", "package main\n"),
+        ("src/app.go", "package app\n"),
+        ("src/gen/api.go", "package gen\n"),
+        ("tools/src/lint.go", "package lint\n"),
+    ];
+
+    #[test]
+    fn test_paths_relative_to_root() {
+        let config = r#"
+[[check.rules]]
+name = "no go in src"
+paths = ["/src/"]
+except = ["gen/"]
+forbid = ["go"]
+"#;
+        let violations = check(config, "", GO);
+        assert_eq!(vec!["src/app.go: go is forbidden"], messages(&violations));
+        assert_eq!("no go in src", violations[0].rule);
+        assert_eq!(Level::Deny, violations[0].level);
+        // With the config in tools/, `/src/` is tools/src/.
+        assert_eq!(
+            vec!["tools/src/lint.go: go is forbidden"],
+            messages(&check(config, "tools", GO))
+        );
+    }
+
+    #[test]
+    fn test_allow_and_forbid() {
+        let config = r#"
+[[check.rules]]
+allow = ["go", "python"]
+forbid = ["go"]
+level = "warn"
+"#;
+        let files = [
+            ("a.go", "package a\n"),
+            ("b.py", "print(1)\n"),
+            ("c.rs", "fn main() {}\n"),
+        ];
+        let violations = check(config, "", &files);
+        // `forbid` wins over `allow`.
+        assert_eq!(
+            vec![
+                "a.go: go is forbidden",
+                "c.rs: rust is not allowed here (allowed: go, python)",
+            ],
+            messages(&violations)
+        );
+        assert!(violations.iter().all(|violation| {
+            violation.rule == "check.rules[0]" && violation.level == Level::Warn
+        }));
+    }
+
+    #[test]
+    fn test_generated() {
+        let config = r#"
+[[check.rules]]
+except = ["/gen/"]
+generated = false
+
+[[check.rules]]
+paths = ["/gen/"]
+generated = true
+"#;
+        let banner = "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage gen\n";
+        let files = [
+            ("api.pb.go", banner),
+            ("main.go", "package main\n"),
+            ("gen/api.pb.go", banner),
+            ("gen/handwritten.go", "package gen\n"),
+        ];
+        assert_eq!(
+            vec![
+                "api.pb.go: generated files are not allowed here",
+                "gen/handwritten.go: only generated files are allowed here",
+            ],
+            messages(&check(config, "", &files))
+        );
+    }
+
+    #[test]
+    fn test_unknown() {
+        let files = [
+            ("NOTES", "remember the milk\n"),
+            ("main.go", "package main\n"),
+        ];
+        let unknown = |level: &str| {
+            check(&format!("[check]\nunknown = \"{level}\""), "", &files)
+                .into_iter()
+                .map(|violation| (violation.path, violation.rule, violation.level))
+                .collect::<Vec<_>>()
+        };
+        let notes = |level| vec![(PathBuf::from("NOTES"), "unknown".to_string(), level)];
+        assert_eq!(notes(Level::Warn), unknown("warn"));
+        assert_eq!(notes(Level::Deny), unknown("deny"));
+        assert!(unknown("allow").is_empty());
+    }
+
+    #[test]
+    fn test_invalid_policies() {
+        for (config, error) in [
+            ("[check]\nfail = true", "unknown key `check.fail`"),
+            (
+                "[check]\nunknown = \"error\"",
+                "check.unknown must be \"allow\", \"warn\" or \"deny\"",
+            ),
+            (
+                "[check]\nrules = {}",
+                "check.rules must be an array of tables",
+            ),
+            (
+                "[[check.rules]]\nforbid = [\"klingon\"]",
+                "unknown file type: klingon",
+            ),
+            (
+                "[[check.rules]]\nlevel = \"allow\"",
+                "check.rules[0].level must be \"warn\" or \"deny\"",
+            ),
+            (
+                "[[check.rules]]\ngenerated = \"yes\"",
+                "check.rules[0].generated must be true or false",
+            ),
+            (
+                "[[check.rules]]\npath = [\"src/\"]",
+                "unknown key `check.rules[0].path`",
+            ),
+        ] {
+            assert_eq!(
+                Some(error.to_string()),
+                policy(Path::new("."), config).err(),
+                "{config}"
+            );
+        }
+    }
+}
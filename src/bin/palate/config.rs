@@ -0,0 +1,346 @@
+//! `.palate.toml`: a project's own file type mappings, exclusions, aliases and output
+//! defaults, found in the scanned directory or one of its parents.
+//!
+//! ```toml
+//! exclude = ["fixtures/", "*.snap"]
+//!
+//! [extensions]
+//! inc = "php"
+//!
+//! [filenames]
+//! Tiltfile = "starlark"
+//!
+//! [globs]
+//! "/scripts/*" = "sh"
+//!
+//! [aliases]
+//! typescriptreact = "typescript"
+//!
+//! [check]
+//! unknown = "deny"
+//!
+//! [output]
+//! format = "markdown"
+//! by = "code"
+//! sort = "name"
+//! top = 10
+//! color = "never"
+//! ```
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use palate::{FileType, detect, scan::Overrides};
+use toml::{Table, Value};
+
+use crate::{
+    check::Policy,
+    output::{Metric, OutputFormat},
+    parse_file_type,
+};
+
+pub(crate) const FILE_NAME: &str = ".palate.toml";
+
+/// A loaded `.palate.toml`.
+pub(crate) struct Config {
+    pub(crate) path: PathBuf,
+    /// Mappings and exclusions, relative to the directory of the file.
+    pub(crate) overrides: Overrides,
+    /// File types that are reported as another file type.
+    aliases: HashMap<FileType, FileType>,
+    pub(crate) output: OutputDefaults,
+    /// `[check]`, the policy for `palate check`.
+    pub(crate) policy: Option<Policy>,
+}
+
+/// `[output]`: defaults for options that weren't given on the command line.
+#[derive(Default)]
+pub(crate) struct OutputDefaults {
+    pub(crate) format: Option<OutputFormat>,
+    pub(crate) by: Option<Metric>,
+    pub(crate) sort: Option<String>,
+    pub(crate) top: Option<usize>,
+    pub(crate) color: Option<String>,
+}
+
+impl Config {
+    /// Load the first `.palate.toml` in `start` (a file or directory) or its parents.
+    pub(crate) fn discover(start: &Path) -> Result<Option<Self>, String> {
+        let Ok(start) = std::path::absolute(start) else {
+            return Ok(None);
+        };
+        for dir in start.ancestors() {
+            let path = dir.join(FILE_NAME);
+            if path.is_file() {
+                return Self::load(&path).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Load the config file at `path`; errors start with the path.
+    pub(crate) fn load(path: &Path) -> Result<Self, String> {
+        let fail = |err: String| format!("{}: {err}", path.display());
+        let text = std::fs::read_to_string(path).map_err(|err| fail(err.to_string()))?;
+        let table: Table = text
+            .parse()
+            .map_err(|err: toml::de::Error| fail(err.to_string()))?;
+        let root = path.parent().unwrap_or(Path::new("."));
+        Self::from_table(path, root, &table).map_err(fail)
+    }
+
+    fn from_table(path: &Path, root: &Path, table: &Table) -> Result<Self, String> {
+        let mut config = Self {
+            path: path.to_path_buf(),
+            overrides: Overrides::new(root),
+            aliases: HashMap::new(),
+            output: OutputDefaults::default(),
+            policy: None,
+        };
+        for (key, value) in table {
+            match key.as_str() {
+                "exclude" => {
+                    for glob in strings(key, value)? {
+                        config
+                            .overrides
+                            .exclude(glob)
+                            .map_err(|err| format!("exclude: {err}"))?;
+                    }
+                }
+                "extensions" => {
+                    for (extension, file_type) in file_types(key, value)? {
+                        config.overrides.extension(extension, file_type);
+                    }
+                }
+                "filenames" => {
+                    for (filename, file_type) in file_types(key, value)? {
+                        config.overrides.filename(filename, file_type);
+                    }
+                }
+                "globs" => {
+                    for (glob, file_type) in file_types(key, value)? {
+                        config
+                            .overrides
+                            .glob(glob, file_type)
+                            .map_err(|err| format!("globs: {err}"))?;
+                    }
+                }
+                "aliases" => {
+                    for (alias, file_type) in file_types(key, value)? {
+                        config.aliases.insert(parse_file_type(alias)?, file_type);
+                    }
+                }
+                "output" => config.output = OutputDefaults::from_value(value)?,
+                "check" => config.policy = Some(Policy::from_value(root, value)?),
+                other => return Err(format!("unknown key `{other}`")),
+            }
+        }
+        Ok(config)
+    }
+
+    /// The file type of `path`: the project's mapping, or else the detected one.
+    pub(crate) fn detect(&self, path: &Path, content: &str) -> FileType {
+        self.overrides
+            .file_type(path)
+            .unwrap_or_else(|| detect(path, content))
+    }
+
+    /// The file type `file_type` is reported as.
+    pub(crate) fn alias(&self, file_type: FileType) -> FileType {
+        self.aliases.get(&file_type).copied().unwrap_or(file_type)
+    }
+}
+
+impl OutputDefaults {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        let mut defaults = Self::default();
+        for (key, value) in table("output", value)? {
+            let string = || {
+                value
+                    .as_str()
+                    .ok_or_else(|| format!("output.{key} must be a string"))
+            };
+            match key.as_str() {
+                "format" => defaults.format = Some(string()?.parse()?),
+                "by" => defaults.by = Some(string()?.parse()?),
+                "sort" => {
+                    let sort = string()?;
+                    if sort != "name" {
+                        sort.parse::<Metric>()?;
+                    }
+                    defaults.sort = Some(sort.to_string());
+                }
+                "top" => {
+                    let top = value
+                        .as_integer()
+                        .filter(|&top| top >= 1)
+                        .ok_or("output.top must be a positive integer")?;
+                    defaults.top = Some(top as usize);
+                }
+                "color" => {
+                    let color = string()?;
+                    if !["auto", "always", "never"].contains(&color) {
+                        return Err(format!("unknown color choice: {color}"));
+                    }
+                    defaults.color = Some(color.to_string());
+                }
+                other => return Err(format!("unknown key `output.{other}`")),
+            }
+        }
+        Ok(defaults)
+    }
+}
+
+pub(crate) fn table<'a>(key: &str, value: &'a Value) -> Result<&'a Table, String> {
+    value
+        .as_table()
+        .ok_or_else(|| format!("{key} must be a table"))
+}
+
+pub(crate) fn strings<'a>(key: &str, value: &'a Value) -> Result<Vec<&'a str>, String> {
+    value
+        .as_array()
+        .and_then(|values| values.iter().map(Value::as_str).collect())
+        .ok_or_else(|| format!("{key} must be an array of strings"))
+}
+
+/// A table of keys mapped to file type names.
+fn file_types<'a>(key: &str, value: &'a Value) -> Result<Vec<(&'a str, FileType)>, String> {
+    table(key, value)?
+        .iter()
+        .map(|(name, file_type)| {
+            let file_type = file_type
+                .as_str()
+                .ok_or_else(|| format!("{key}.{name} must be a file type name"))?;
+            Ok((name.as_str(), parse_file_type(file_type)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(text: &str) -> Result<Config, String> {
+        let root = Path::new("project");
+        Config::from_table(&root.join(FILE_NAME), root, &text.parse().unwrap())
+    }
+
+    #[test]
+    fn test_overrides_win_over_detection() {
+        let config = config(
+            r#"
+exclude = ["fixtures/"]
+
+[extensions]
+inc = "php"
+
+[filenames]
+"main.rs" = "text"
+
+[globs]
+"/scripts/*" = "sh"
+"#,
+        )
+        .unwrap();
+        let detect = |path: &str, content| config.detect(&Path::new("project").join(path), content);
+        assert_eq!(FileType::Php, detect("lib/util.inc", ""));
+        assert_eq!(FileType::Text, detect("src/main.rs", "fn main() {}\n"));
+        assert_eq!(
+            FileType::Sh,
+            detect("scripts/deploy", "#!/usr/bin/env python3\n")
+        );
+        // Not mapped: detected as usual, including the shebang.
+        assert_eq!(
+            FileType::Python,
+            detect("bin/deploy", "#!/usr/bin/env python3\n")
+        );
+        assert_eq!(FileType::Rust, detect("src/lib.rs", ""));
+        assert!(config.overrides.excludes("project/fixtures/a.json", false));
+    }
+
+    #[test]
+    fn test_aliases() {
+        let config = config("[aliases]\ntsx = \"typescript\"\njsx = \"javascript\"").unwrap();
+        assert_eq!(FileType::TypeScript, config.alias(FileType::Tsx));
+        assert_eq!(FileType::JavaScript, config.alias(FileType::Jsx));
+        assert_eq!(FileType::Rust, config.alias(FileType::Rust));
+    }
+
+    #[test]
+    fn test_output_defaults() {
+        let output = |text: &str| config(&format!("[output]\n{text}")).unwrap().output;
+        let defaults = output("format = \"markdown\"\nby = \"code\"\ntop = 10\ncolor = \"never\"");
+        assert_eq!(Some(OutputFormat::Markdown), defaults.format);
+        assert_eq!(Some(Metric::Code), defaults.by);
+        assert_eq!(Some(10), defaults.top);
+        assert_eq!(Some("never"), defaults.color.as_deref());
+        // `sort` takes a metric or `name`.
+        assert_eq!(Some("name"), output("sort = \"name\"").sort.as_deref());
+        assert_eq!(Some("files"), output("sort = \"files\"").sort.as_deref());
+    }
+
+    #[test]
+    fn test_rejected() {
+        for (text, error) in [
+            ("colour = \"never\"", "unknown key `colour`"),
+            ("[output]\nfmt = \"json\"", "unknown key `output.fmt`"),
+            ("[output]\nformat = \"yaml\"", "unknown output format: yaml"),
+            ("[output]\nby = \"tokens\"", "unknown metric: tokens"),
+            ("[output]\nsort = \"size\"", "unknown metric: size"),
+            ("[output]\nsort = 1", "output.sort must be a string"),
+            ("[output]\ntop = 0", "output.top must be a positive integer"),
+            (
+                "[output]\ntop = \"10\"",
+                "output.top must be a positive integer",
+            ),
+            (
+                "[output]\ncolor = \"sometimes\"",
+                "unknown color choice: sometimes",
+            ),
+            ("output = \"json\"", "output must be a table"),
+            (
+                "exclude = \"fixtures/\"",
+                "exclude must be an array of strings",
+            ),
+            (
+                "[extensions]\ninc = \"hypertext\"",
+                "unknown file type: hypertext",
+            ),
+            (
+                "[extensions]\ninc = 1",
+                "extensions.inc must be a file type name",
+            ),
+            (
+                "[aliases]\nklingon = \"text\"",
+                "unknown file type: klingon",
+            ),
+        ] {
+            assert_eq!(Some(error.to_string()), config(text).err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_load_errors_name_the_file() {
+        let dir = tempfile::Builder::new()
+            .prefix("palate-")
+            .tempdir()
+            .unwrap();
+        let path = dir.path().join(FILE_NAME);
+        std::fs::write(&path, "[output]\ntop = -1\n").unwrap();
+        let error = Config::load(&path).err().unwrap();
+        assert_eq!(
+            format!("{}: output.top must be a positive integer", path.display()),
+            error
+        );
+        std::fs::write(&path, "[output\n").unwrap();
+        assert!(
+            Config::load(&path)
+                .err()
+                .unwrap()
+                .starts_with(&path.display().to_string())
+        );
+    }
+}
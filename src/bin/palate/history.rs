@@ -0,0 +1,53 @@
+//! `palate history`: the language breakdown of commits sampled from a branch, read
+//! from the object database without checking anything out.
+
+use std::collections::HashMap;
+
+use palate::{FileType, scan::Commit, scan::LanguageStats};
+
+/// The breakdown at one commit.
+pub(crate) struct Sample {
+    pub(crate) commit: Commit,
+    pub(crate) languages: HashMap<FileType, LanguageStats>,
+}
+
+/// Up to `count` of `items`, evenly spaced, always keeping the first and the last.
+pub(crate) fn sample<T>(items: Vec<T>, count: usize) -> Vec<T> {
+    let len = items.len();
+    if count >= len {
+        return items;
+    }
+    let mut picked: Vec<usize> = (0..count)
+        .map(|i| match count {
+            1 => 0,
+            _ => (i * (len - 1) + (count - 1) / 2) / (count - 1),
+        })
+        .collect();
+    picked.dedup();
+    items
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| picked.binary_search(index).is_ok())
+        .map(|(_, item)| item)
+        .collect()
+}
+
+/// The UTC date of a Unix time, as `YYYY-MM-DD`.
+pub(crate) fn date(time: i64) -> String {
+    // Howard Hinnant's `civil_from_days`.
+    let days = time.div_euclid(86_400) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,1202 @@
+use clap::{Arg, ArgAction, ArgMatches, Command, error::ErrorKind, parser::ValueSource};
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use palate::{
+    DetectionStage, Explanation, FileType, detect, explain, is_generated,
+    scan::{
+        Codeowners, DEFAULT_MAX_BYTES, LanguageBreakdown, LanguageStats, LineCounts, ScanBuilder,
+        ScannedFile, Sniffed,
+    },
+};
+use termcolor::ColorChoice;
+
+use config::Config;
+use output::{Language, ListColumns, Metric, OutputFormat};
+
+mod check;
+mod config;
+mod history;
+mod output;
+mod report;
+mod summary;
+
+/// What single-file mode found out about a file.
+struct FileReport {
+    path: PathBuf,
+    /// `None` for binary files.
+    file_type: Option<FileType>,
+    mime: &'static str,
+    bytes: Option<u64>,
+    /// Only counted for text files.
+    lines: Option<LineCounts>,
+    generated: bool,
+    /// For files like `schema.sql.gz`, which are reported on by their decompressed
+    /// content.
+    compression: Option<&'static str>,
+}
+
+/// What `--explain` found out about a file.
+struct ExplainReport {
+    path: PathBuf,
+    mime: &'static str,
+    /// `None` for binary files, which are never detected.
+    explanation: Option<Explanation>,
+    /// The file type the config maps the file to, and the config file.
+    mapped: Option<(FileType, PathBuf)>,
+}
+
+/// How the directory breakdown is measured, ordered and cut off.
+struct BreakdownView {
+    /// Metric for the summary percentages (`--by`).
+    by: Metric,
+    sort: SortKey,
+    /// Languages past the first `top` are merged into an "Other" row.
+    top: Option<usize>,
+    color: ColorChoice,
+}
+
+/// How `--group-by` splits the breakdown.
+#[derive(Clone, Copy, Debug)]
+enum GroupBy {
+    /// Per directory below the root, this many levels deep.
+    Dir(usize),
+    /// Per owner in the `CODEOWNERS` file.
+    Owner,
+    /// Test code and the rest, see [`palate::is_test_file`].
+    Test,
+}
+
+/// The group of files no `CODEOWNERS` rule gives an owner.
+const UNOWNED: &str = "(unowned)";
+
+enum SortKey {
+    /// Largest first.
+    Metric(Metric),
+    Name,
+}
+
+fn main() {
+    let matches = get_cli().get_matches();
+    // A bare `palate check` next to a `check` directory may have meant the directory.
+    if let Some(name) = matches.subcommand_name() {
+        let args: Vec<_> = std::env::args_os().skip(1).collect();
+        if args == [name] && Path::new(name).is_dir() {
+            get_cli()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    format!(
+                        "`{name}` is a subcommand and a directory here; write `palate ./{name}` \
+                         to inspect the directory or `palate {name} .` to run the subcommand"
+                    ),
+                )
+                .exit();
+        }
+    }
+    match matches.subcommand() {
+        Some(("check", matches)) => run_check(matches),
+        Some(("history", matches)) => run_history(matches),
+        Some(("diff", matches)) => run_diff(matches),
+        Some(("report", matches)) => run_report(matches),
+        #[cfg(feature = "watch")]
+        Some(("watch", matches)) => run_watch(matches),
+        _ => {}
+    }
+    let paths: Vec<&str> = matches
+        .get_many::<String>("PATH")
+        .map(|paths| paths.map(String::as_str).collect())
+        .unwrap_or_default();
+    let files_from = matches.get_one::<String>("files-from");
+    let stdin_filename = matches.get_one::<String>("stdin-filename");
+    let config = load_config(&matches, &paths);
+    let config = config.as_ref();
+    let format = output_format(&matches, config);
+
+    if paths.contains(&"-") && (paths.len() > 1 || files_from.is_some()) {
+        get_cli()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "reading content from stdin (-) cannot be combined with other paths",
+            )
+            .exit();
+    }
+    if paths == ["-"] || (paths.is_empty() && files_from.is_none() && stdin_filename.is_some()) {
+        let name = Path::new(stdin_filename.map(String::as_str).unwrap_or("-"));
+        let printed = if matches.get_flag("explain") {
+            stdin_explanation(name, config)
+                .and_then(|report| output::print_explanation(&report, format))
+        } else {
+            stdin_report(name, config).and_then(|report| output::print_file(&report, format))
+        };
+        if printed.is_err() {
+            std::process::exit(1);
+        }
+        return;
+    }
+    if stdin_filename.is_some() {
+        get_cli()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--stdin-filename can only be used when reading from stdin (-)",
+            )
+            .exit();
+    }
+
+    let mut roots: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    if let Some(list) = files_from {
+        match read_path_list(list, matches.get_flag("null")) {
+            Ok(listed) => roots.extend(listed),
+            Err(err) => {
+                eprintln!("palate: {list}: {err}");
+                std::process::exit(2);
+            }
+        }
+    } else if roots.is_empty() {
+        roots.push(PathBuf::from("."));
+    }
+
+    // Like `wc`, report missing paths, carry on with the rest and fail at the end.
+    let mut missing = false;
+    roots.retain(|root| {
+        let exists = root.exists();
+        if !exists {
+            eprintln!("palate: {}: No such file or directory", root.display());
+            missing = true;
+        }
+        exists
+    });
+    if roots.is_empty() && missing {
+        std::process::exit(2);
+    }
+
+    if matches.get_flag("explain") {
+        if roots.len() != 1 || !roots[0].is_file() {
+            get_cli()
+                .error(
+                    ErrorKind::InvalidValue,
+                    "--explain takes a single file (or - for stdin)",
+                )
+                .exit();
+        }
+        let printed = file_explanation(&roots[0], config)
+            .and_then(|report| output::print_explanation(&report, format));
+        if printed.is_err() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let revision = matches.get_one::<String>("rev");
+    let group_by = matches.get_one::<GroupBy>("group-by").copied();
+    if files_from.is_none()
+        && revision.is_none()
+        && group_by.is_none()
+        && roots.len() == 1
+        && roots[0].is_file()
+        && !scans_archive(&matches, &roots[0])
+    {
+        let printed =
+            file_report(&roots[0], config).and_then(|report| output::print_file(&report, format));
+        if printed.is_err() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let mut scanner = scanner(&matches, &roots, config);
+    scanner.exclude_generated(!matches.get_flag("include-generated"));
+    let types = matches.get_many::<FileType>("type");
+    let filtered = types.is_some();
+    if let Some(types) = types {
+        scanner.types(types.copied());
+    }
+
+    if matches.get_flag("list") {
+        let shown: HashSet<&str> = matches
+            .get_many::<String>("show")
+            .map(|shown| shown.map(String::as_str).collect())
+            .unwrap_or_default();
+        let columns = ListColumns {
+            // With a `--type` filter, bare paths compose with `xargs`.
+            file_type: !filtered || !shown.is_empty(),
+            stage: shown.contains("stage"),
+            mime: shown.contains("mime"),
+            size: shown.contains("size"),
+        };
+        let files = match revision {
+            Some(revision) => scanner.files_at(revision),
+            None => Ok(scanner.files()),
+        };
+        let mut files = files.unwrap_or_else(|err| fail(err));
+        if let Some(config) = config {
+            for file in &mut files {
+                file.file_type = config.alias(file.file_type);
+            }
+        }
+        if output::print_listing(&files, format, columns).is_err() {
+            std::process::exit(1);
+        }
+    } else {
+        let view = breakdown_view(&matches, config);
+        scanner.collect_paths(format == OutputFormat::Json);
+        if let Some(group_by) = group_by {
+            let groups = grouped_breakdowns(&scanner, &roots, group_by, revision)
+                .unwrap_or_else(|err| fail(err));
+            print_groups(groups, format, &view, config);
+            if missing {
+                std::process::exit(2);
+            }
+            return;
+        }
+        let breakdown = match revision {
+            Some(revision) => scanner.breakdown_at(revision),
+            None => Ok(scanner.breakdown()),
+        };
+        print_breakdown(
+            breakdown.unwrap_or_else(|err| fail(err)),
+            format,
+            &view,
+            config,
+        );
+    }
+    if missing {
+        std::process::exit(2);
+    }
+}
+
+/// `palate check`: scan everything that isn't ignored, including hidden, vendored,
+/// generated and undetected files, and report the files that break the config's policy.
+fn run_check(matches: &ArgMatches) -> ! {
+    let (roots, config) = subcommand_roots(matches);
+    let Some(policy) = config.as_ref().and_then(|config| config.policy.as_ref()) else {
+        eprintln!("palate: no [check] policy in {}", config::FILE_NAME);
+        std::process::exit(2);
+    };
+
+    let mut scanner = scanner(matches, &roots, config.as_ref());
+    scanner
+        .exclude_hidden(false)
+        .exclude_vendored(false)
+        .exclude_documentation(false)
+        .exclude_generated(false)
+        .keep_undetected(true)
+        .count_lines(false);
+    let files = scanner.files();
+    let violations = policy.check(&files);
+    let format = output_format(matches, config.as_ref());
+    if output::print_violations(&violations, files.len(), format).is_err() {
+        std::process::exit(2);
+    }
+    let failed = violations
+        .iter()
+        .any(|violation| violation.level == check::Level::Deny);
+    std::process::exit(i32::from(failed));
+}
+
+/// `palate history`: the breakdown at commits sampled from the history of a revision,
+/// oldest first.
+fn run_history(matches: &ArgMatches) -> ! {
+    let (roots, config) = subcommand_roots(matches);
+    let config = config.as_ref();
+
+    let revision = matches
+        .get_one::<String>("rev")
+        .map_or("HEAD", String::as_str);
+    let commits = matches
+        .get_one::<u64>("commits")
+        .map_or(50, |&n| n as usize);
+    let commits =
+        palate::scan::commits(&roots[0], revision, commits).unwrap_or_else(|err| fail(err));
+    let samples = matches
+        .get_one::<u64>("samples")
+        .map_or(10, |&n| n as usize);
+    let mut scanner = scanner(matches, &roots, config);
+    scanner.exclude_generated(!matches.get_flag("include-generated"));
+    let samples: Vec<history::Sample> = history::sample(commits, samples)
+        .into_iter()
+        .rev()
+        .map(|commit| {
+            let breakdown = scanner
+                .breakdown_at(&commit.id)
+                .unwrap_or_else(|err| fail(err));
+            history::Sample {
+                commit,
+                languages: aliased(breakdown, config),
+            }
+        })
+        .collect();
+
+    let view = breakdown_view(matches, config);
+    let format = output_format(matches, config);
+    let printed = output::print_history(&samples, format, view.by, view.top.unwrap_or(8));
+    std::process::exit(i32::from(printed.is_err()));
+}
+
+/// `palate diff`: the lines a unified diff adds and removes, per language or per file.
+fn run_diff(matches: &ArgMatches) -> ! {
+    let source = matches
+        .get_one::<PathBuf>("PATCH")
+        .map_or(Path::new("-"), PathBuf::as_path);
+    let config = load_config(matches, &[]);
+    let config = config.as_ref();
+    let mut patch = Vec::new();
+    let read = if source == Path::new("-") {
+        io::stdin().lock().read_to_end(&mut patch)
+    } else {
+        std::fs::File::open(source).and_then(|mut file| file.read_to_end(&mut patch))
+    };
+    if let Err(err) = read {
+        eprintln!("palate: {}: {err}", source.display());
+        std::process::exit(2);
+    }
+
+    let patch = String::from_utf8_lossy(&patch);
+    let mut files = palate::diff::parse_with(&patch, |path, content| {
+        match config.and_then(|config| config.overrides.file_type(path)) {
+            Some(file_type) => Some((file_type, DetectionStage::Override)),
+            None => palate::try_detect_with_stage(path, content)
+                .map(|detection| (detection.file_type, detection.stage)),
+        }
+    });
+    if let Some(config) = config {
+        for file in &mut files {
+            file.file_type = config.alias(file.file_type);
+        }
+    }
+    let format = output_format(matches, config);
+    let printed = if matches.get_flag("files") {
+        output::print_changes(&files, format)
+    } else {
+        output::print_churn(&palate::diff::churn(&files), format)
+    };
+    std::process::exit(i32::from(printed.is_err()));
+}
+
+/// `palate report`: the directory breakdown as an SVG language bar or an HTML page.
+fn run_report(matches: &ArgMatches) -> ! {
+    let (roots, config) = subcommand_roots(matches);
+    let config = config.as_ref();
+
+    let mut scanner = scanner(matches, &roots, config);
+    scanner.exclude_generated(!matches.get_flag("include-generated"));
+    let view = breakdown_view(matches, config);
+    let report = if matches.get_one::<String>("FORMAT").map(String::as_str) == Some("html") {
+        let mut files = scanner.files();
+        if let Some(config) = config {
+            for file in &mut files {
+                file.file_type = config.alias(file.file_type);
+            }
+        }
+        let language_stats = breakdown_rows(files.iter().cloned().collect(), &view, config);
+        let title: Vec<String> = roots
+            .iter()
+            .map(|root| root.display().to_string())
+            .collect();
+        report::html(
+            &title.join(", "),
+            &language_stats,
+            &files,
+            view.by,
+            |breakdown| breakdown_rows(breakdown, &view, config),
+        )
+    } else {
+        report::svg(&breakdown_rows(scanner.breakdown(), &view, config), view.by)
+    };
+    let written = io::stdout().lock().write_all(report.as_bytes());
+    std::process::exit(i32::from(written.is_err()));
+}
+
+/// `palate watch`: the directory breakdown, printed again whenever it changes.
+#[cfg(feature = "watch")]
+fn run_watch(matches: &ArgMatches) -> ! {
+    let (roots, config) = subcommand_roots(matches);
+    let config = config.as_ref();
+
+    let mut scanner = scanner(matches, &roots, config);
+    scanner.exclude_generated(!matches.get_flag("include-generated"));
+    let mut watch = scanner.watch().unwrap_or_else(|err| fail(err));
+    let format = output_format(matches, config);
+    let view = breakdown_view(matches, config);
+    // A terminal shows the latest breakdown in place; anything else gets one after the
+    // other.
+    let clear = format != OutputFormat::Json && io::stdout().is_terminal();
+    let mut shown = watch.breakdown();
+    let mut update = None;
+    loop {
+        let language_stats = breakdown_rows(shown.clone(), &view, config);
+        let printed = match format {
+            OutputFormat::Json => output::print_watch_update(&language_stats, update.as_ref()),
+            _ => {
+                if clear {
+                    print!("\x1b[2J\x1b[H");
+                } else if update.is_some() {
+                    println!();
+                }
+                output::print_breakdown(&language_stats, format, view.by, view.color)
+            }
+        };
+        if printed.is_err() {
+            std::process::exit(1);
+        }
+        // Only changes to the breakdown are printed, not every save.
+        loop {
+            let next = watch.wait(None).unwrap_or_else(|err| fail(err));
+            let breakdown = watch.breakdown();
+            if breakdown != shown {
+                (shown, update) = (breakdown, next);
+                break;
+            }
+        }
+    }
+}
+
+/// The `PATH`s of a subcommand and the `.palate.toml` that applies to them; exits when
+/// one of them doesn't exist.
+fn subcommand_roots(matches: &ArgMatches) -> (Vec<PathBuf>, Option<Config>) {
+    let roots: Vec<PathBuf> = match matches.get_many::<PathBuf>("PATH") {
+        Some(roots) => roots.cloned().collect(),
+        None => vec![PathBuf::from(".")],
+    };
+    let paths: Vec<&str> = roots.iter().filter_map(|root| root.to_str()).collect();
+    let config = load_config(matches, &paths);
+    if let Some(root) = roots.iter().find(|root| !root.exists()) {
+        eprintln!("palate: {}: No such file or directory", root.display());
+        std::process::exit(2);
+    }
+    (roots, config)
+}
+
+/// Report a failed scan and exit.
+fn fail(err: io::Error) -> ! {
+    eprintln!("palate: {err}");
+    std::process::exit(2);
+}
+
+/// A scan of `roots` with the options shared by the breakdown, `--list` and `check`.
+fn scanner(matches: &ArgMatches, roots: &[PathBuf], config: Option<&Config>) -> ScanBuilder {
+    let mut scanner = ScanBuilder::default();
+    for root in roots {
+        scanner.add(root);
+    }
+    scanner
+        .exclude_vendored(!matches.get_flag("include-vendored"))
+        .exclude_documentation(!matches.get_flag("include-documentation"))
+        .threads(matches.get_one::<usize>("threads").copied().unwrap_or(0));
+    if let Some(cache) = matches.get_one::<PathBuf>("cache") {
+        scanner.cache(cache);
+    }
+    if let Some(config) = config.filter(|config| !config.overrides.is_empty()) {
+        scanner.overrides(config.overrides.clone());
+    }
+    #[cfg(feature = "archive")]
+    scanner.archives(matches.get_flag("archives"));
+    scanner
+}
+
+/// Whether `--archives` asks to scan the members of the archive `path` rather than
+/// report on the archive itself.
+#[cfg(feature = "archive")]
+fn scans_archive(matches: &ArgMatches, path: &Path) -> bool {
+    matches.get_flag("archives")
+        && Sniffed::open(path, DEFAULT_MAX_BYTES).is_ok_and(|sniffed| sniffed.is_archive())
+}
+
+#[cfg(not(feature = "archive"))]
+fn scans_archive(_matches: &ArgMatches, _path: &Path) -> bool {
+    false
+}
+
+/// `--output`, or else the config's default.
+fn output_format(matches: &ArgMatches, config: Option<&Config>) -> OutputFormat {
+    config
+        .and_then(|config| config.output.format)
+        .filter(|_| matches.value_source("output") != Some(ValueSource::CommandLine))
+        .unwrap_or_else(|| {
+            matches
+                .get_one::<String>("output")
+                .and_then(|format| format.parse::<OutputFormat>().ok())
+                .unwrap_or_default()
+        })
+}
+
+/// `--by`, `--sort`, `--top` and `--color`, or else the config's defaults.
+fn breakdown_view(matches: &ArgMatches, config: Option<&Config>) -> BreakdownView {
+    // Options given on the command line win over the config's defaults, which only
+    // apply to the options the subcommand has.
+    let defaults = config.map(|config| &config.output);
+    let defined = |id: &str| matches.try_get_raw(id).is_ok();
+    let from_config =
+        |id: &str| defined(id) && matches.value_source(id) != Some(ValueSource::CommandLine);
+    let by = defaults
+        .and_then(|defaults| defaults.by)
+        .filter(|_| from_config("by"))
+        .unwrap_or_else(|| {
+            matches
+                .get_one::<String>("by")
+                .and_then(|by| by.parse::<Metric>().ok())
+                .unwrap_or_default()
+        });
+    let sort = matches
+        .try_get_one::<String>("sort")
+        .ok()
+        .flatten()
+        .or(defaults
+            .and_then(|defaults| defaults.sort.as_ref())
+            .filter(|_| defined("sort")));
+    let color = defaults
+        .and_then(|defaults| defaults.color.as_deref())
+        .filter(|_| from_config("color"))
+        .or(matches
+            .try_get_one::<String>("color")
+            .ok()
+            .flatten()
+            .map(String::as_str));
+    BreakdownView {
+        by,
+        sort: match sort.map(String::as_str) {
+            Some("name") => SortKey::Name,
+            Some(metric) => SortKey::Metric(metric.parse().unwrap_or(by)),
+            None => SortKey::Metric(by),
+        },
+        top: matches
+            .get_one::<u64>("top")
+            .map(|&top| top as usize)
+            .or(defaults.and_then(|defaults| defaults.top)),
+        color: color_choice(color),
+    }
+}
+
+/// The breakdown of every group of `--group-by`.
+fn grouped_breakdowns(
+    scanner: &ScanBuilder,
+    roots: &[PathBuf],
+    group_by: GroupBy,
+    revision: Option<&String>,
+) -> io::Result<HashMap<String, LanguageBreakdown>> {
+    let codeowners =
+        match group_by {
+            GroupBy::Owner => Some(Codeowners::discover(&roots[0])?.ok_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, "no CODEOWNERS file found")
+            })?),
+            GroupBy::Dir(_) | GroupBy::Test => None,
+        };
+    // A file with several owners counts toward each of them.
+    let groups = |file: &ScannedFile| match (group_by, &codeowners) {
+        (GroupBy::Dir(depth), _) => vec![directory(roots, &file.path, depth)],
+        (GroupBy::Owner, Some(codeowners)) => match codeowners.owners(&file.path) {
+            [] => vec![UNOWNED.to_string()],
+            owners => owners.to_vec(),
+        },
+        (GroupBy::Owner, None) => Vec::new(),
+        (GroupBy::Test, _) => vec![if file.test { "test" } else { "non-test" }.to_string()],
+    };
+    match revision {
+        Some(revision) => scanner.breakdown_by_at(revision, groups),
+        None => Ok(scanner.breakdown_by(groups)),
+    }
+}
+
+/// The directory of `path` below its root, cut off after `depth` levels and shown with
+/// the root unless that is `.`.
+fn directory(roots: &[PathBuf], path: &Path, depth: usize) -> String {
+    let parent = path.parent().unwrap_or(Path::new(""));
+    let (root, below) = roots
+        .iter()
+        .find_map(|root| Some((root.as_path(), parent.strip_prefix(root).ok()?)))
+        .unwrap_or((Path::new(""), parent));
+    let below: PathBuf = below.components().take(depth).collect();
+    let dir = match (
+        root.as_os_str().is_empty() || root == Path::new("."),
+        below.as_os_str().is_empty(),
+    ) {
+        (true, true) => PathBuf::from("."),
+        (true, false) => below,
+        (false, true) => root.to_path_buf(),
+        (false, false) => root.join(below),
+    };
+    dir.display().to_string()
+}
+
+/// The groups of `--group-by`, each shown the way `view` says, ordered by the metric
+/// `view` sorts by or by name.
+fn print_groups(
+    groups: HashMap<String, LanguageBreakdown>,
+    format: OutputFormat,
+    view: &BreakdownView,
+    config: Option<&Config>,
+) {
+    let mut groups: Vec<(String, LanguageBreakdown)> = groups.into_iter().collect();
+    groups.sort_by(|(a_name, a), (b_name, b)| match view.sort {
+        SortKey::Metric(metric) => metric
+            .of(&b.total())
+            .cmp(&metric.of(&a.total()))
+            .then(a_name.cmp(b_name)),
+        SortKey::Name => a_name.cmp(b_name),
+    });
+    let groups: Vec<(String, Vec<(Language, LanguageStats)>)> = groups
+        .into_iter()
+        .map(|(name, breakdown)| (name, breakdown_rows(breakdown, view, config)))
+        .collect();
+    if output::print_groups(&groups, format).is_err() {
+        std::process::exit(1);
+    }
+}
+
+/// `dir`, `dir:DEPTH`, `owner` or `test`.
+fn parse_group_by(value: &str) -> Result<GroupBy, String> {
+    match value.split_once(':') {
+        None if value == "owner" => Ok(GroupBy::Owner),
+        None if value == "dir" => Ok(GroupBy::Dir(1)),
+        None if value == "test" => Ok(GroupBy::Test),
+        Some(("dir", depth)) => match depth.parse() {
+            Ok(depth) if depth > 0 => Ok(GroupBy::Dir(depth)),
+            _ => Err(format!("invalid depth: {depth}")),
+        },
+        _ => Err(format!(
+            "expected dir, dir:DEPTH, owner or test, not {value}"
+        )),
+    }
+}
+
+fn print_breakdown(
+    breakdown: LanguageBreakdown,
+    format: OutputFormat,
+    view: &BreakdownView,
+    config: Option<&Config>,
+) {
+    let language_stats = breakdown_rows(breakdown, view, config);
+    if output::print_breakdown(&language_stats, format, view.by, view.color).is_err() {
+        std::process::exit(1);
+    }
+}
+
+/// The rows of a breakdown: the languages sorted and cut off the way `view` says, with
+/// the config's aliases merged.
+fn breakdown_rows(
+    breakdown: LanguageBreakdown,
+    view: &BreakdownView,
+    config: Option<&Config>,
+) -> Vec<(Language, LanguageStats)> {
+    let languages = aliased(breakdown, config);
+    let mut language_stats: Vec<(FileType, LanguageStats)> = languages.into_iter().collect();
+    // Ties are broken by name so the structured outputs are stable.
+    language_stats.sort_by(|(a_ft, a), (b_ft, b)| {
+        let by_name = a_ft.canonical().cmp(b_ft.canonical());
+        match view.sort {
+            SortKey::Metric(metric) => metric.of(b).cmp(&metric.of(a)).then(by_name),
+            SortKey::Name => by_name,
+        }
+    });
+
+    let mut other: Option<LanguageStats> = None;
+    if let Some(top) = view.top.filter(|&top| top < language_stats.len()) {
+        let mut rest = LanguageStats::default();
+        for (_, stats) in language_stats.drain(top..) {
+            rest.merge(stats);
+        }
+        other = Some(rest);
+    }
+    let mut language_stats: Vec<(Language, LanguageStats)> = language_stats
+        .into_iter()
+        .map(|(file_type, stats)| (Language::Type(file_type), stats))
+        .chain(other.map(|stats| (Language::Other, stats)))
+        .collect();
+    for (_, data) in language_stats.iter_mut() {
+        data.paths.sort();
+    }
+    language_stats
+}
+
+/// The languages of `breakdown`, with the config's aliases merged into the file types
+/// they stand for.
+fn aliased(
+    breakdown: LanguageBreakdown,
+    config: Option<&Config>,
+) -> HashMap<FileType, LanguageStats> {
+    let mut languages: HashMap<FileType, LanguageStats> = HashMap::new();
+    for (file_type, stats) in breakdown.languages {
+        let file_type = config.map_or(file_type, |config| config.alias(file_type));
+        languages.entry(file_type).or_default().merge(stats);
+    }
+    languages
+}
+
+fn file_report(path: &Path, config: Option<&Config>) -> io::Result<FileReport> {
+    let bytes = std::fs::metadata(path).map(|m| m.len()).ok();
+    let sniffed = Sniffed::open(path, DEFAULT_MAX_BYTES)?;
+    #[cfg(feature = "compression")]
+    if let Some((compression, inner)) = sniffed.compression(path) {
+        let decompressed = sniffed.decompressed(compression, DEFAULT_MAX_BYTES)?;
+        if is_text(&inner, &decompressed, config) {
+            let mut report = sniffed_report(&inner, decompressed, bytes, true, config)?;
+            report.path = path.to_path_buf();
+            report.compression = Some(compression.into());
+            return Ok(report);
+        }
+        // Compressed binary files are reported as what they are on disk.
+        let sniffed = Sniffed::open(path, DEFAULT_MAX_BYTES)?;
+        return sniffed_report(path, sniffed, bytes, false, config);
+    }
+    let is_text = is_text(path, &sniffed, config);
+    sniffed_report(path, sniffed, bytes, is_text, config)
+}
+
+/// Like a scan, files the config maps only need to look like text.
+fn is_text(path: &Path, sniffed: &Sniffed<impl Read>, config: Option<&Config>) -> bool {
+    sniffed.is_text(path)
+        || (config.is_some_and(|config| config.overrides.file_type(path).is_some())
+            && sniffed.looks_like_text())
+}
+
+/// Report on standard input, using `name` as the path for path-based detection.
+fn stdin_report(name: &Path, config: Option<&Config>) -> io::Result<FileReport> {
+    let mut content = Vec::new();
+    io::stdin().lock().read_to_end(&mut content)?;
+    let sniffed = Sniffed::new(content.as_slice(), DEFAULT_MAX_BYTES)?;
+    // There is no file on disk to check, so unnamed text is recognized by its bytes.
+    let is_text = sniffed.is_text(name) || sniffed.looks_like_text();
+    sniffed_report(name, sniffed, Some(content.len() as u64), is_text, config)
+}
+
+fn sniffed_report(
+    path: &Path,
+    sniffed: Sniffed<impl Read>,
+    bytes: Option<u64>,
+    is_text: bool,
+    config: Option<&Config>,
+) -> io::Result<FileReport> {
+    let mut report = FileReport {
+        path: path.to_path_buf(),
+        file_type: None,
+        mime: sniffed
+            .inferred_mime()
+            .unwrap_or("application/octet-stream"),
+        bytes,
+        lines: None,
+        generated: false,
+        compression: None,
+    };
+    if !is_text {
+        return Ok(report);
+    }
+
+    let content = sniffed.content();
+    let file_type = match config {
+        Some(config) => config.detect(path, &content),
+        None => detect(path, &content),
+    };
+    report.file_type = Some(config.map_or(file_type, |config| config.alias(file_type)));
+    report.mime = sniffed.mime();
+    report.generated = is_generated(path, &content);
+    report.lines = Some(sniffed.count_lines(file_type)?);
+
+    Ok(report)
+}
+
+fn file_explanation(path: &Path, config: Option<&Config>) -> io::Result<ExplainReport> {
+    let sniffed = Sniffed::open(path, DEFAULT_MAX_BYTES)?;
+    #[cfg(feature = "compression")]
+    if let Some((compression, inner)) = sniffed.compression(path) {
+        let decompressed = sniffed.decompressed(compression, DEFAULT_MAX_BYTES)?;
+        if is_text(&inner, &decompressed, config) {
+            let mut report = sniffed_explanation(&inner, &decompressed, true, config);
+            report.path = path.to_path_buf();
+            return Ok(report);
+        }
+        let sniffed = Sniffed::open(path, DEFAULT_MAX_BYTES)?;
+        return Ok(sniffed_explanation(path, &sniffed, false, config));
+    }
+    let is_text = is_text(path, &sniffed, config);
+    Ok(sniffed_explanation(path, &sniffed, is_text, config))
+}
+
+/// Like [`stdin_report`], but explaining the detection.
+fn stdin_explanation(name: &Path, config: Option<&Config>) -> io::Result<ExplainReport> {
+    let mut content = Vec::new();
+    io::stdin().lock().read_to_end(&mut content)?;
+    let sniffed = Sniffed::new(content.as_slice(), DEFAULT_MAX_BYTES)?;
+    let is_text = sniffed.is_text(name) || sniffed.looks_like_text();
+    Ok(sniffed_explanation(name, &sniffed, is_text, config))
+}
+
+fn sniffed_explanation(
+    path: &Path,
+    sniffed: &Sniffed<impl Read>,
+    is_text: bool,
+    config: Option<&Config>,
+) -> ExplainReport {
+    if !is_text {
+        return ExplainReport {
+            path: path.to_path_buf(),
+            mime: sniffed
+                .inferred_mime()
+                .unwrap_or("application/octet-stream"),
+            explanation: None,
+            mapped: None,
+        };
+    }
+    ExplainReport {
+        path: path.to_path_buf(),
+        mime: sniffed.mime(),
+        explanation: Some(explain(path, &sniffed.content())),
+        mapped: config.and_then(|config| {
+            let file_type = config.overrides.file_type(path)?;
+            Some((file_type, config.path.clone()))
+        }),
+    }
+}
+
+/// `--config FILE`, or else the `.palate.toml` in the first path or one of its parents,
+/// unless `--no-config`.
+fn load_config(matches: &ArgMatches, paths: &[&str]) -> Option<Config> {
+    if matches.get_flag("no-config") {
+        return None;
+    }
+    let loaded = match matches.get_one::<PathBuf>("config") {
+        Some(path) => Config::load(path).map(Some),
+        None => {
+            let start = paths.iter().find(|&&path| path != "-").unwrap_or(&".");
+            Config::discover(Path::new(start))
+        }
+    };
+    loaded.unwrap_or_else(|err| {
+        eprintln!("palate: {err}");
+        std::process::exit(2);
+    })
+}
+
+/// `auto` only colors a terminal; termcolor itself only looks at `TERM` and `NO_COLOR`.
+fn color_choice(color: Option<&str>) -> ColorChoice {
+    match color {
+        Some("always") => ColorChoice::Always,
+        Some("never") => ColorChoice::Never,
+        _ if io::stdout().is_terminal() => ColorChoice::Auto,
+        _ => ColorChoice::Never,
+    }
+}
+
+/// Read the paths listed in `source` (`-` for stdin), one per line or NUL-delimited.
+fn read_path_list(source: &str, null_delimited: bool) -> io::Result<Vec<PathBuf>> {
+    let mut list = Vec::new();
+    if source == "-" {
+        io::stdin().lock().read_to_end(&mut list)?;
+    } else {
+        list = std::fs::read(source)?;
+    }
+    let delimiter = if null_delimited { b'\0' } else { b'\n' };
+    Ok(list
+        .split(|&b| b == delimiter)
+        .map(|entry| {
+            if null_delimited {
+                entry
+            } else {
+                entry.strip_suffix(b"\r").unwrap_or(entry)
+            }
+        })
+        .filter(|entry| !entry.is_empty())
+        .map(path_from_bytes)
+        .collect())
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+    PathBuf::from(OsStr::from_bytes(bytes))
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
+fn parse_file_type(name: &str) -> Result<FileType, String> {
+    name.trim()
+        .parse()
+        .map_err(|_| format!("unknown file type: {name}"))
+}
+
+fn get_cli() -> Command {
+    let cli = Command::new("palate")
+        .version(env!("CARGO_PKG_VERSION"))
+        .args_conflicts_with_subcommands(true)
+        .about("Palate is a file type detector. It supports detecting the file type of a file or the file type makeup of a directory.")
+        .arg(
+            Arg::new("PATH")
+                .index(1)
+                .num_args(0..)
+                .help("Files or directories to inspect, or - to read from stdin [default: .]. A directory named like a subcommand is written ./NAME, e.g. ./check"),
+        )
+        .arg(
+            Arg::new("files-from")
+                .long("files-from")
+                .value_name("FILE")
+                .help("Also inspect the paths listed in FILE (- for stdin), one per line"),
+        )
+        .arg(
+            Arg::new("null")
+                .short('0')
+                .long("null")
+                .action(ArgAction::SetTrue)
+                .requires("files-from")
+                .help("Paths in --files-from are NUL-delimited (git ls-files -z, find -print0)"),
+        )
+        .arg(
+            Arg::new("stdin-filename")
+                .long("stdin-filename")
+                .value_name("NAME")
+                .help("File name used to detect content read from stdin (implies -)"),
+        )
+        .arg(
+            Arg::new("rev")
+                .long("rev")
+                .value_name("REVISION")
+                .conflicts_with_all(["explain", "files-from", "stdin-filename"])
+                .help("Scan the files committed at REVISION (a commit, tag or branch) instead of the working tree"),
+        )
+        .arg(
+            Arg::new("output")
+                .global(true)
+                .short('o')
+                .long("output")
+                .value_name("FORMAT")
+                .value_parser(["text", "json", "csv", "markdown"])
+                .default_value("text")
+                .help("Output format"),
+        )
+        .arg(
+            Arg::new("list")
+                .short('l')
+                .long("list")
+                .action(ArgAction::SetTrue)
+                .help("List every scanned file with its detected file type instead of a breakdown"),
+        )
+        .arg(
+            Arg::new("explain")
+                .long("explain")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["list", "files-from"])
+                .help("Show how each detection stage handled a single file, and which rule decided"),
+        )
+        .arg(
+            Arg::new("group-by")
+                .long("group-by")
+                .value_name("KEY")
+                .value_parser(parse_group_by)
+                .conflicts_with_all(["list", "explain"])
+                .help("Show a breakdown per directory (dir, or dir:DEPTH for deeper levels), per owner in CODEOWNERS (owner) or for test and non-test code (test)"),
+        )
+        .arg(
+            Arg::new("type")
+                .short('t')
+                .long("type")
+                .value_name("TYPES")
+                .value_delimiter(',')
+                .value_parser(parse_file_type)
+                .help("Only report files of these comma-separated file types (e.g. rust,toml)"),
+        )
+        .arg(
+            Arg::new("show")
+                .long("show")
+                .value_name("COLUMNS")
+                .value_delimiter(',')
+                .value_parser(["stage", "mime", "size"])
+                .requires("list")
+                .help("Extra columns for --list: detection stage, MIME type, size"),
+        )
+        .arg(
+            Arg::new("threads")
+                .global(true)
+                .short('j')
+                .long("threads")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of scanner threads (defaults to the number of CPUs)"),
+        )
+        .args(view_args())
+        .arg(
+            Arg::new("cache")
+                .global(true)
+                .long("cache")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Reuse and update scan results for unchanged files in FILE"),
+        )
+        .arg(
+            Arg::new("config")
+                .global(true)
+                .long("config")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .conflicts_with("no-config")
+                .help("Read mappings and defaults from FILE instead of the nearest .palate.toml"),
+        )
+        .arg(
+            Arg::new("no-config")
+                .global(true)
+                .long("no-config")
+                .action(ArgAction::SetTrue)
+                .help("Ignore .palate.toml"),
+        )
+        .arg(
+            Arg::new("include-vendored")
+                .global(true)
+                .long("include-vendored")
+                .action(ArgAction::SetTrue)
+                .help("Include vendored paths (vendor.yml) in the directory breakdown"),
+        )
+        .arg(
+            Arg::new("include-documentation")
+                .global(true)
+                .long("include-documentation")
+                .action(ArgAction::SetTrue)
+                .help("Include documentation paths (documentation.yml) in the directory breakdown"),
+        )
+        .arg(
+            Arg::new("include-generated")
+                .global(true)
+                .long("include-generated")
+                .action(ArgAction::SetTrue)
+                .help("Include generated files (lockfiles, minified assets, ...) in the directory breakdown"),
+        )
+        .subcommand(
+            Command::new("check")
+                .about("Check the files against the [check] policy in .palate.toml and fail on violations")
+                .arg(
+                    Arg::new("PATH")
+                        .num_args(0..)
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .help("Files or directories to check [default: .]"),
+                ),
+        )
+        .subcommand(
+            Command::new("history")
+                .about("Show how the language breakdown evolved over the commits of a branch")
+                .arg(
+                    Arg::new("PATH")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .help("File or directory in a git repository to follow [default: .]"),
+                )
+                .arg(
+                    Arg::new("rev")
+                        .long("rev")
+                        .value_name("REVISION")
+                        .default_value("HEAD")
+                        .help("Follow the history of REVISION, along first parents"),
+                )
+                .arg(
+                    Arg::new("commits")
+                        .long("commits")
+                        .value_name("N")
+                        .value_parser(clap::value_parser!(u64).range(1..))
+                        .default_value("50")
+                        .help("Go back this many commits"),
+                )
+                .arg(
+                    Arg::new("samples")
+                        .long("samples")
+                        .value_name("N")
+                        .value_parser(clap::value_parser!(u64).range(1..))
+                        .default_value("10")
+                        .help("Scan this many evenly spaced commits, including the first and the last"),
+                )
+                .arg(
+                    Arg::new("by")
+                        .long("by")
+                        .value_name("METRIC")
+                        .value_parser(["files", "lines", "code", "bytes"])
+                        .default_value("bytes")
+                        .help("Metric shown in the text and Markdown tables"),
+                )
+                .arg(
+                    Arg::new("top")
+                        .long("top")
+                        .value_name("N")
+                        .value_parser(clap::value_parser!(u64).range(1..))
+                        .help("Show the N largest languages in the text and Markdown tables and merge the rest into \"Other\" [default: 8]"),
+                ),
+        )
+        .subcommand(
+            Command::new("diff")
+                .about("Count the lines a unified diff (git diff, a .patch file) adds and removes per language")
+                .arg(
+                    Arg::new("PATCH")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .help("The diff to read, - for stdin [default: -]"),
+                )
+                .arg(
+                    Arg::new("files")
+                        .long("files")
+                        .action(ArgAction::SetTrue)
+                        .help("Show every touched file instead of the totals per language"),
+                ),
+        )
+        .subcommand(
+            Command::new("report")
+                .about("Render the language breakdown as an SVG language bar or a static HTML report")
+                .arg(
+                    Arg::new("FORMAT")
+                        .required(true)
+                        .value_parser(["svg", "html"])
+                        .help("svg for a language bar with a legend, html for a page with sortable tables and every directory"),
+                )
+                .arg(
+                    Arg::new("PATH")
+                        .num_args(0..)
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .help("Files or directories to report on [default: .]"),
+                )
+                .args(view_args().into_iter().filter(|arg| arg.get_id() != "color")),
+        );
+    #[cfg(feature = "watch")]
+    let cli = cli.subcommand(
+        Command::new("watch")
+            .about("Show the language breakdown and update it as files change")
+            .arg(
+                Arg::new("PATH")
+                    .num_args(0..)
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("Files or directories to watch [default: .]"),
+            )
+            .args(view_args()),
+    );
+    #[cfg(feature = "archive")]
+    let cli = cli.arg(
+        Arg::new("archives")
+            .global(true)
+            .long("archives")
+            .action(ArgAction::SetTrue)
+            .help("Scan the files inside tar, tar.gz, tar.zst and zip archives"),
+    );
+    cli
+}
+
+/// The options of how a breakdown is shown, see [`breakdown_view`].
+fn view_args() -> [Arg; 4] {
+    [
+        Arg::new("by")
+            .long("by")
+            .value_name("METRIC")
+            .value_parser(["files", "lines", "code", "bytes"])
+            .default_value("bytes")
+            .help("Metric for the language percentages and the default sort order"),
+        Arg::new("sort")
+            .long("sort")
+            .value_name("KEY")
+            .value_parser(["files", "lines", "code", "bytes", "name"])
+            .help("Sort languages by this metric (largest first) or by name [default: --by]"),
+        Arg::new("top")
+            .long("top")
+            .value_name("N")
+            .value_parser(clap::value_parser!(u64).range(1..))
+            .help("Only show the first N languages and merge the rest into \"Other\""),
+        Arg::new("color")
+            .long("color")
+            .value_name("WHEN")
+            .value_parser(["auto", "always", "never"])
+            .default_value("auto")
+            .help("Color the language bar and legend"),
+    ]
+}
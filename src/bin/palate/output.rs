@@ -0,0 +1,1373 @@
+//! Renderers for the `--output` formats.
+//!
+//! The structured formats (JSON, CSV, Markdown) share one schema: `language` is the
+//! Linguist display name, `canonical` the palate filetype, and percentages are of the
+//! respective totals, rounded to two decimals.
+
+use std::{
+    io::{self, Write},
+    str::FromStr,
+};
+
+use palate::{
+    DetectionStage, FileType, Outcome, Step,
+    diff::{ChurnBreakdown, FileChange, LanguageChurn},
+};
+use serde_json::{Value, json};
+use termcolor::{ColorChoice, StandardStream};
+
+#[cfg(feature = "watch")]
+use palate::scan::Update;
+use palate::scan::{LanguageStats, LineCounts, ScannedFile};
+
+use crate::{
+    ExplainReport, FileReport,
+    check::{Level, Violation},
+    history::{self, Sample},
+    summary,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "markdown" => Ok(Self::Markdown),
+            other => Err(format!("unknown output format: {other}")),
+        }
+    }
+}
+
+/// What the breakdown is measured, sorted and truncated by (`--by`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Metric {
+    Files,
+    Lines,
+    Code,
+    /// File sizes, like Linguist.
+    #[default]
+    Bytes,
+}
+
+impl Metric {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Metric::Files => "files",
+            Metric::Lines => "lines",
+            Metric::Code => "code",
+            Metric::Bytes => "bytes",
+        }
+    }
+
+    pub(crate) fn of(self, stats: &LanguageStats) -> u64 {
+        match self {
+            Metric::Files => stats.files as u64,
+            Metric::Lines => stats.lines,
+            Metric::Code => stats.code(),
+            Metric::Bytes => stats.bytes,
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "files" => Ok(Self::Files),
+            "lines" => Ok(Self::Lines),
+            "code" => Ok(Self::Code),
+            "bytes" => Ok(Self::Bytes),
+            other => Err(format!("unknown metric: {other}")),
+        }
+    }
+}
+
+/// A row of the breakdown: one file type, or everything past `--top N`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Language {
+    Type(FileType),
+    Other,
+}
+
+impl Language {
+    pub(crate) fn display_name(self) -> &'static str {
+        match self {
+            Language::Type(file_type) => file_type.display_name(),
+            Language::Other => "Other",
+        }
+    }
+
+    /// `None` for the "Other" row, which has no filetype.
+    pub(crate) fn canonical(self) -> Option<&'static str> {
+        match self {
+            Language::Type(file_type) => Some(file_type.canonical()),
+            Language::Other => None,
+        }
+    }
+}
+
+const FILE_COLUMNS: [&str; 11] = [
+    "path",
+    "language",
+    "canonical",
+    "mime",
+    "bytes",
+    "lines",
+    "code",
+    "comments",
+    "blanks",
+    "generated",
+    "compression",
+];
+
+const LISTING_COLUMNS: [&str; 10] = [
+    "path",
+    "language",
+    "canonical",
+    "stage",
+    "mime",
+    "bytes",
+    "lines",
+    "code",
+    "comments",
+    "blanks",
+];
+
+/// Columns printed next to the path by the text `--list` output. The structured
+/// formats always carry every column.
+#[derive(Clone, Copy)]
+pub(crate) struct ListColumns {
+    pub(crate) file_type: bool,
+    pub(crate) stage: bool,
+    pub(crate) mime: bool,
+    pub(crate) size: bool,
+}
+
+const BREAKDOWN_COLUMNS: [&str; 11] = [
+    "language",
+    "canonical",
+    "files",
+    "lines",
+    "code",
+    "comments",
+    "blanks",
+    "bytes",
+    "files_percent",
+    "lines_percent",
+    "bytes_percent",
+];
+
+/// The breakdown columns with the group of `--group-by` first.
+const GROUP_COLUMNS: [&str; 12] = [
+    "group",
+    "language",
+    "canonical",
+    "files",
+    "lines",
+    "code",
+    "comments",
+    "blanks",
+    "bytes",
+    "files_percent",
+    "lines_percent",
+    "bytes_percent",
+];
+
+pub(crate) fn print_file(report: &FileReport, format: OutputFormat) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        OutputFormat::Text => print_file_like(&mut out, report),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, &file_json(report))?;
+            writeln!(out)
+        }
+        OutputFormat::Csv => {
+            write_csv_row(&mut out, FILE_COLUMNS)?;
+            write_csv_row(&mut out, file_cells(report))
+        }
+        OutputFormat::Markdown => {
+            write_markdown_header(&mut out, &FILE_COLUMNS)?;
+            write_markdown_row(&mut out, file_cells(report))
+        }
+    }
+}
+
+/// One row per detection step; binary files have none.
+const EXPLAIN_COLUMNS: [&str; 4] = ["stage", "outcome", "rule", "canonical"];
+
+pub(crate) fn print_explanation(report: &ExplainReport, format: OutputFormat) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    let steps = report
+        .explanation
+        .as_ref()
+        .map_or(&[][..], |explanation| &explanation.steps[..]);
+    match format {
+        OutputFormat::Text => print_explanation_text(&mut out, report),
+        OutputFormat::Json => {
+            let detected = match &report.mapped {
+                Some((file_type, _)) => Some((*file_type, DetectionStage::Override)),
+                None => report
+                    .explanation
+                    .as_ref()
+                    .and_then(|explanation| explanation.detected),
+            };
+            let value = json!({
+                "path": report.path.display().to_string(),
+                "mime": report.mime,
+                "config": report.mapped.as_ref().map(|(_, config)| config.display().to_string()),
+                "language": detected.map(|(ft, _)| ft.display_name()),
+                "canonical": detected.map(|(ft, _)| ft.canonical()),
+                "stage": detected.map(|(_, stage)| stage.to_string()),
+                "steps": steps.iter().map(|step| {
+                    let [stage, outcome, rule, canonical] = step_cells(step);
+                    json!({
+                        "stage": stage,
+                        "outcome": outcome,
+                        "rule": Some(rule).filter(|rule| !rule.is_empty()),
+                        "canonical": Some(canonical).filter(|canonical| !canonical.is_empty()),
+                    })
+                }).collect::<Vec<_>>(),
+            });
+            serde_json::to_writer_pretty(&mut out, &value)?;
+            writeln!(out)
+        }
+        OutputFormat::Csv => {
+            write_csv_row(&mut out, EXPLAIN_COLUMNS)?;
+            for step in steps {
+                write_csv_row(&mut out, step_cells(step))?;
+            }
+            Ok(())
+        }
+        OutputFormat::Markdown => {
+            write_markdown_header(&mut out, &EXPLAIN_COLUMNS)?;
+            for step in steps {
+                write_markdown_row(&mut out, step_cells(step))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// `stage, outcome, rule, canonical`; for skipped stages the rule is the reason.
+fn step_cells(step: &Step) -> [String; 4] {
+    let (outcome, rule, file_type) = match &step.outcome {
+        Outcome::Skipped(reason) => ("skipped", reason.as_str(), None),
+        Outcome::NoMatch(rule) => ("no-match", rule.as_str(), None),
+        Outcome::Matched { rule, file_type } => ("matched", rule.as_str(), Some(*file_type)),
+        Outcome::NotReached => ("not-reached", "", None),
+        _ => ("", "", None),
+    };
+    [
+        step.stage.to_string(),
+        outcome.to_string(),
+        rule.to_string(),
+        file_type
+            .map(|ft| ft.canonical().to_string())
+            .unwrap_or_default(),
+    ]
+}
+
+fn print_explanation_text(out: &mut impl Write, report: &ExplainReport) -> io::Result<()> {
+    let path = report.path.display();
+    let Some(explanation) = &report.explanation else {
+        return writeln!(
+            out,
+            "{path}: binary ({}), detection only runs on text",
+            report.mime
+        );
+    };
+    if let Some((file_type, config)) = &report.mapped {
+        writeln!(
+            out,
+            "{path}: {} (mapped by {}, mime {})",
+            file_type.canonical(),
+            config.display(),
+            report.mime
+        )?;
+        writeln!(out, "without the mapping:")?;
+    }
+    match explanation.detected {
+        Some((file_type, stage)) if report.mapped.is_some() => {
+            writeln!(out, "  {} (decided by {stage})", file_type.canonical())?
+        }
+        None if report.mapped.is_some() => writeln!(out, "  not detected")?,
+        Some((file_type, stage)) => writeln!(
+            out,
+            "{path}: {} (decided by {stage}, mime {})",
+            file_type.canonical(),
+            report.mime
+        )?,
+        None => writeln!(out, "{path}: not detected (falls back to text)")?,
+    }
+
+    let rows: Vec<[String; 4]> = explanation.steps.iter().map(step_cells).collect();
+    let stage_width = rows.iter().map(|row| row[0].len()).max().unwrap_or(0);
+    let outcome_width = rows.iter().map(|row| row[1].len()).max().unwrap_or(0);
+    for [stage, outcome, rule, canonical] in rows {
+        let mut line = format!("  {stage:<stage_width$}  {outcome:<outcome_width$}  {rule}");
+        if !canonical.is_empty() {
+            line.push_str(&format!(" -> {canonical}"));
+        }
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+/// `by` and `color` only affect the text summary.
+pub(crate) fn print_breakdown(
+    language_stats: &[(Language, LanguageStats)],
+    format: OutputFormat,
+    by: Metric,
+    color: ColorChoice,
+) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    let totals = Totals::of(language_stats);
+    match format {
+        OutputFormat::Text => {
+            let stream = StandardStream::stdout(color);
+            let mut out = stream.lock();
+            summary::print_summary(&mut out, language_stats, by)?;
+            print_tokei_lite(&mut out, language_stats, &totals)
+        }
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, &breakdown_json(language_stats, &totals))?;
+            writeln!(out)
+        }
+        OutputFormat::Csv => {
+            write_csv_row(&mut out, BREAKDOWN_COLUMNS)?;
+            for (language, stats) in language_stats {
+                write_csv_row(&mut out, breakdown_cells(*language, stats, &totals))?;
+            }
+            Ok(())
+        }
+        OutputFormat::Markdown => {
+            write_markdown_header(&mut out, &BREAKDOWN_COLUMNS)?;
+            for (language, stats) in language_stats {
+                write_markdown_row(&mut out, breakdown_cells(*language, stats, &totals))?;
+            }
+            write_markdown_row(
+                &mut out,
+                [
+                    "**Total**".to_string(),
+                    String::new(),
+                    totals.files.to_string(),
+                    totals.lines.to_string(),
+                    totals.code().to_string(),
+                    totals.comments.to_string(),
+                    totals.blanks.to_string(),
+                    totals.bytes.to_string(),
+                    "100.00".to_string(),
+                    "100.00".to_string(),
+                    "100.00".to_string(),
+                ],
+            )
+        }
+    }
+}
+
+pub(crate) fn print_listing(
+    files: &[ScannedFile],
+    format: OutputFormat,
+    columns: ListColumns,
+) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        OutputFormat::Text => {
+            for file in files {
+                let mut cells = vec![file.path.display().to_string()];
+                if columns.file_type {
+                    cells.push(file.file_type.canonical().to_string());
+                }
+                if columns.stage {
+                    cells.push(file.stage.to_string());
+                }
+                if columns.mime {
+                    cells.push(file.mime.to_string());
+                }
+                if columns.size {
+                    cells.push(format_bytes(file.bytes));
+                }
+                writeln!(out, "{}", cells.join("\t"))?;
+            }
+            Ok(())
+        }
+        OutputFormat::Json => {
+            let files: Vec<Value> = files.iter().map(listing_json).collect();
+            serde_json::to_writer_pretty(&mut out, &files)?;
+            writeln!(out)
+        }
+        OutputFormat::Csv => {
+            write_csv_row(&mut out, LISTING_COLUMNS)?;
+            for file in files {
+                write_csv_row(&mut out, listing_cells(file))?;
+            }
+            Ok(())
+        }
+        OutputFormat::Markdown => {
+            write_markdown_header(&mut out, &LISTING_COLUMNS)?;
+            for file in files {
+                write_markdown_row(&mut out, listing_cells(file))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+const VIOLATION_COLUMNS: [&str; 6] = ["path", "canonical", "stage", "level", "rule", "message"];
+
+/// The `palate check` report; `checked` is the number of files the policy saw.
+pub(crate) fn print_violations(
+    violations: &[Violation],
+    checked: usize,
+    format: OutputFormat,
+) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    let count = |level| {
+        violations
+            .iter()
+            .filter(|violation| violation.level == level)
+            .count()
+    };
+    let (errors, warnings) = (count(Level::Deny), count(Level::Warn));
+    match format {
+        OutputFormat::Text => {
+            for violation in violations {
+                writeln!(
+                    out,
+                    "{}: {}: {} [{}]",
+                    violation.level.as_str(),
+                    violation.path.display(),
+                    violation.message,
+                    violation.rule
+                )?;
+            }
+            let plural =
+                |n: usize, word: &str| format!("{n} {word}{}", if n == 1 { "" } else { "s" });
+            writeln!(
+                out,
+                "checked {}: {}, {}",
+                plural(checked, "file"),
+                plural(errors, "error"),
+                plural(warnings, "warning")
+            )
+        }
+        OutputFormat::Json => {
+            let value = json!({
+                "checked": checked,
+                "errors": errors,
+                "warnings": warnings,
+                "violations": violations.iter().map(|violation| json!({
+                    "path": violation.path.display().to_string(),
+                    "language": violation.file_type.display_name(),
+                    "canonical": violation.file_type.canonical(),
+                    "stage": violation.stage.to_string(),
+                    "level": violation.level.as_str(),
+                    "rule": violation.rule,
+                    "message": violation.message,
+                })).collect::<Vec<_>>(),
+            });
+            serde_json::to_writer_pretty(&mut out, &value)?;
+            writeln!(out)
+        }
+        OutputFormat::Csv => {
+            write_csv_row(&mut out, VIOLATION_COLUMNS)?;
+            for violation in violations {
+                write_csv_row(&mut out, violation_cells(violation))?;
+            }
+            Ok(())
+        }
+        OutputFormat::Markdown => {
+            write_markdown_header(&mut out, &VIOLATION_COLUMNS)?;
+            for violation in violations {
+                write_markdown_row(&mut out, violation_cells(violation))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// A `palate watch` breakdown as one line of JSON, with the files that changed since
+/// the last one.
+#[cfg(feature = "watch")]
+pub(crate) fn print_watch_update(
+    language_stats: &[(Language, LanguageStats)],
+    update: Option<&Update>,
+) -> io::Result<()> {
+    let mut value = breakdown_json(language_stats, &Totals::of(language_stats));
+    let paths = |paths: &[std::path::PathBuf]| {
+        paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+    };
+    if let Some(update) = update {
+        value["changed"] = json!(paths(&update.changed));
+        value["removed"] = json!(paths(&update.removed));
+    }
+    let mut out = io::stdout().lock();
+    serde_json::to_writer(&mut out, &value)?;
+    writeln!(out)?;
+    out.flush()
+}
+
+/// A breakdown per group of `--group-by`; percentages are of the group's totals.
+pub(crate) fn print_groups(
+    groups: &[(String, Vec<(Language, LanguageStats)>)],
+    format: OutputFormat,
+) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        OutputFormat::Text => {
+            for (index, (group, language_stats)) in groups.iter().enumerate() {
+                if index > 0 {
+                    writeln!(out)?;
+                }
+                writeln!(out, "{group}")?;
+                print_tokei_lite(&mut out, language_stats, &Totals::of(language_stats))?;
+            }
+            Ok(())
+        }
+        OutputFormat::Json => {
+            let value: Vec<Value> = groups
+                .iter()
+                .map(|(group, language_stats)| {
+                    let mut value = json!({ "group": group });
+                    let breakdown = breakdown_json(language_stats, &Totals::of(language_stats));
+                    if let (Some(value), Value::Object(breakdown)) =
+                        (value.as_object_mut(), breakdown)
+                    {
+                        value.extend(breakdown);
+                    }
+                    value
+                })
+                .collect();
+            serde_json::to_writer_pretty(&mut out, &json!({ "groups": value }))?;
+            writeln!(out)
+        }
+        OutputFormat::Csv | OutputFormat::Markdown => {
+            if format == OutputFormat::Csv {
+                write_csv_row(&mut out, GROUP_COLUMNS)?;
+            } else {
+                write_markdown_header(&mut out, &GROUP_COLUMNS)?;
+            }
+            for (group, language_stats) in groups {
+                let totals = Totals::of(language_stats);
+                for (language, stats) in language_stats {
+                    let cells = std::iter::once(group.clone())
+                        .chain(breakdown_cells(*language, stats, &totals));
+                    if format == OutputFormat::Csv {
+                        write_csv_row(&mut out, cells)?;
+                    } else {
+                        write_markdown_row(&mut out, cells)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// One row per language and commit.
+const HISTORY_COLUMNS: [&str; 11] = [
+    "commit",
+    "date",
+    "summary",
+    "language",
+    "canonical",
+    "files",
+    "lines",
+    "code",
+    "comments",
+    "blanks",
+    "bytes",
+];
+
+/// `palate history`, oldest commit first. The text and Markdown tables have a column per
+/// language, measured `by` and cut off after `top` languages; the other formats carry
+/// every language and metric.
+pub(crate) fn print_history(
+    samples: &[Sample],
+    format: OutputFormat,
+    by: Metric,
+    top: usize,
+) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        OutputFormat::Text | OutputFormat::Markdown => {
+            let (header, rows) = history_table(samples, by, top, format == OutputFormat::Text);
+            if format == OutputFormat::Markdown {
+                write_markdown_header(
+                    &mut out,
+                    &header.iter().map(String::as_str).collect::<Vec<_>>(),
+                )?;
+                for row in rows {
+                    write_markdown_row(&mut out, row)?;
+                }
+                return Ok(());
+            }
+            let rows: Vec<Vec<String>> = std::iter::once(header).chain(rows).collect();
+            write_text_table(&mut out, &rows, 2)
+        }
+        OutputFormat::Json => {
+            let value: Vec<Value> = samples
+                .iter()
+                .map(|sample| {
+                    json!({
+                        "commit": sample.commit.id,
+                        "date": history::date(sample.commit.time),
+                        "summary": sample.commit.summary,
+                        "languages": by_size(sample).into_iter().map(|(file_type, stats)| json!({
+                            "language": file_type.display_name(),
+                            "canonical": file_type.canonical(),
+                            "files": stats.files,
+                            "lines": stats.lines,
+                            "code": stats.code(),
+                            "comments": stats.comments,
+                            "blanks": stats.blanks,
+                            "bytes": stats.bytes,
+                        })).collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            serde_json::to_writer_pretty(&mut out, &value)?;
+            writeln!(out)
+        }
+        OutputFormat::Csv => {
+            write_csv_row(&mut out, HISTORY_COLUMNS)?;
+            for sample in samples {
+                for (file_type, stats) in by_size(sample) {
+                    write_csv_row(
+                        &mut out,
+                        [
+                            sample.commit.id.clone(),
+                            history::date(sample.commit.time),
+                            sample.commit.summary.clone(),
+                            file_type.display_name().to_string(),
+                            file_type.canonical().to_string(),
+                            stats.files.to_string(),
+                            stats.lines.to_string(),
+                            stats.code().to_string(),
+                            stats.comments.to_string(),
+                            stats.blanks.to_string(),
+                            stats.bytes.to_string(),
+                        ],
+                    )?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// The languages of a sample, largest first.
+fn by_size(sample: &Sample) -> Vec<(FileType, &LanguageStats)> {
+    let mut languages: Vec<(FileType, &LanguageStats)> = sample
+        .languages
+        .iter()
+        .map(|(&file_type, stats)| (file_type, stats))
+        .collect();
+    languages.sort_by(|(a_ft, a), (b_ft, b)| {
+        b.bytes
+            .cmp(&a.bytes)
+            .then(a_ft.canonical().cmp(b_ft.canonical()))
+    });
+    languages
+}
+
+/// The header and rows of the wide history table: commit, date, then the `top`
+/// languages that are largest over all samples and "other". `human` formats sizes for
+/// reading.
+fn history_table(
+    samples: &[Sample],
+    by: Metric,
+    top: usize,
+    human: bool,
+) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut totals: Vec<(FileType, u64)> = Vec::new();
+    for sample in samples {
+        for (&file_type, stats) in &sample.languages {
+            match totals.iter_mut().find(|(ft, _)| *ft == file_type) {
+                Some((_, total)) => *total += by.of(stats),
+                None => totals.push((file_type, by.of(stats))),
+            }
+        }
+    }
+    totals.sort_by(|(a_ft, a), (b_ft, b)| b.cmp(a).then(a_ft.canonical().cmp(b_ft.canonical())));
+    let other = totals.len() > top;
+    let shown: Vec<FileType> = totals.iter().take(top).map(|&(ft, _)| ft).collect();
+
+    let mut header: Vec<String> = ["commit", "date"].map(String::from).into();
+    header.extend(shown.iter().map(|ft| ft.canonical().to_string()));
+    if other {
+        header.push("other".to_string());
+    }
+    let format_value = |value: u64| match by {
+        Metric::Bytes if human => format_bytes(value),
+        _ => value.to_string(),
+    };
+    let rows = samples
+        .iter()
+        .map(|sample| {
+            let value = |file_type| {
+                sample
+                    .languages
+                    .get(file_type)
+                    .map_or(0, |stats| by.of(stats))
+            };
+            let mut row = vec![
+                sample.commit.id.chars().take(10).collect(),
+                history::date(sample.commit.time),
+            ];
+            row.extend(shown.iter().map(|ft| format_value(value(ft))));
+            if other {
+                let rest = sample
+                    .languages
+                    .iter()
+                    .filter(|(ft, _)| !shown.contains(ft))
+                    .map(|(_, stats)| by.of(stats))
+                    .sum();
+                row.push(format_value(rest));
+            }
+            row
+        })
+        .collect();
+    (header, rows)
+}
+
+const CHURN_COLUMNS: [&str; 5] = ["language", "canonical", "files", "added", "removed"];
+
+/// `palate diff`: added and removed lines per language, most changed first.
+pub(crate) fn print_churn(churn: &ChurnBreakdown, format: OutputFormat) -> io::Result<()> {
+    let mut languages: Vec<(FileType, LanguageChurn)> = churn
+        .languages
+        .iter()
+        .map(|(&file_type, &churn)| (file_type, churn))
+        .collect();
+    languages.sort_by(|(a_ft, a), (b_ft, b)| {
+        (b.added + b.removed)
+            .cmp(&(a.added + a.removed))
+            .then(a_ft.canonical().cmp(b_ft.canonical()))
+    });
+    let total = churn.total();
+    let cells = |file_type: FileType, churn: &LanguageChurn| {
+        [
+            file_type.display_name().to_string(),
+            file_type.canonical().to_string(),
+            churn.files.to_string(),
+            churn.added.to_string(),
+            churn.removed.to_string(),
+        ]
+    };
+    let mut out = io::stdout().lock();
+    match format {
+        OutputFormat::Text => {
+            let mut rows: Vec<Vec<String>> = vec![
+                ["Language", "Files", "Added", "Removed"]
+                    .map(String::from)
+                    .into(),
+            ];
+            for (file_type, churn) in &languages {
+                let [language, _, files, added, removed] = cells(*file_type, churn);
+                rows.push(vec![language, files, added, removed]);
+            }
+            rows.push(vec![
+                "Total".to_string(),
+                total.files.to_string(),
+                total.added.to_string(),
+                total.removed.to_string(),
+            ]);
+            write_text_table(&mut out, &rows, 1)
+        }
+        OutputFormat::Json => {
+            let value = json!({
+                "languages": languages.iter().map(|(file_type, churn)| json!({
+                    "language": file_type.display_name(),
+                    "canonical": file_type.canonical(),
+                    "files": churn.files,
+                    "added": churn.added,
+                    "removed": churn.removed,
+                })).collect::<Vec<_>>(),
+                "total": {
+                    "files": total.files,
+                    "added": total.added,
+                    "removed": total.removed,
+                },
+            });
+            serde_json::to_writer_pretty(&mut out, &value)?;
+            writeln!(out)
+        }
+        OutputFormat::Csv => {
+            write_csv_row(&mut out, CHURN_COLUMNS)?;
+            for (file_type, churn) in &languages {
+                write_csv_row(&mut out, cells(*file_type, churn))?;
+            }
+            Ok(())
+        }
+        OutputFormat::Markdown => {
+            write_markdown_header(&mut out, &CHURN_COLUMNS)?;
+            for (file_type, churn) in &languages {
+                write_markdown_row(&mut out, cells(*file_type, churn))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+const CHANGE_COLUMNS: [&str; 8] = [
+    "path",
+    "old_path",
+    "language",
+    "canonical",
+    "stage",
+    "added",
+    "removed",
+    "binary",
+];
+
+/// `palate diff --files`: every touched file, in diff order.
+pub(crate) fn print_changes(files: &[FileChange], format: OutputFormat) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        OutputFormat::Text => {
+            let rows: Vec<Vec<String>> = files
+                .iter()
+                .map(|file| {
+                    let path = match (&file.old_path, &file.new_path) {
+                        (Some(old), Some(new)) if old != new => {
+                            format!("{} -> {}", old.display(), new.display())
+                        }
+                        _ => file.path().display().to_string(),
+                    };
+                    let (added, removed) = if file.binary {
+                        ("binary".to_string(), String::new())
+                    } else {
+                        (format!("+{}", file.added), format!("-{}", file.removed))
+                    };
+                    vec![path, file.file_type.canonical().to_string(), added, removed]
+                })
+                .collect();
+            write_text_table(&mut out, &rows, 2)
+        }
+        OutputFormat::Json => {
+            let value: Vec<Value> = files
+                .iter()
+                .map(|file| {
+                    json!({
+                        "path": file.new_path.as_ref().map(|path| path.display().to_string()),
+                        "old_path": file.old_path.as_ref().map(|path| path.display().to_string()),
+                        "language": file.file_type.display_name(),
+                        "canonical": file.file_type.canonical(),
+                        "stage": file.stage.to_string(),
+                        "added": file.added,
+                        "removed": file.removed,
+                        "binary": file.binary,
+                    })
+                })
+                .collect();
+            serde_json::to_writer_pretty(&mut out, &value)?;
+            writeln!(out)
+        }
+        OutputFormat::Csv => {
+            write_csv_row(&mut out, CHANGE_COLUMNS)?;
+            for file in files {
+                write_csv_row(&mut out, change_cells(file))?;
+            }
+            Ok(())
+        }
+        OutputFormat::Markdown => {
+            write_markdown_header(&mut out, &CHANGE_COLUMNS)?;
+            for file in files {
+                write_markdown_row(&mut out, change_cells(file))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn change_cells(file: &FileChange) -> [String; 8] {
+    let path = |path: &Option<std::path::PathBuf>| {
+        path.as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default()
+    };
+    [
+        path(&file.new_path),
+        path(&file.old_path),
+        file.file_type.display_name().to_string(),
+        file.file_type.canonical().to_string(),
+        file.stage.to_string(),
+        file.added.to_string(),
+        file.removed.to_string(),
+        file.binary.to_string(),
+    ]
+}
+
+/// Write `rows` as columns separated by a space, the first `left` columns aligned left
+/// and the rest right.
+fn write_text_table(out: &mut impl Write, rows: &[Vec<String>], left: usize) -> io::Result<()> {
+    let mut widths = vec![0; rows.iter().map(Vec::len).max().unwrap_or(0)];
+    for cells in rows {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for cells in rows {
+        let mut line = String::new();
+        for (index, (cell, &width)) in cells.iter().zip(&widths).enumerate() {
+            if index > 0 {
+                line.push(' ');
+            }
+            if index < left {
+                line.push_str(&format!("{cell:<width$}"));
+            } else {
+                line.push_str(&format!("{cell:>width$}"));
+            }
+        }
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+fn violation_cells(violation: &Violation) -> [String; 6] {
+    [
+        violation.path.display().to_string(),
+        violation.file_type.canonical().to_string(),
+        violation.stage.to_string(),
+        violation.level.as_str().to_string(),
+        violation.rule.clone(),
+        violation.message.clone(),
+    ]
+}
+
+#[derive(Default)]
+struct Totals {
+    files: usize,
+    lines: u64,
+    comments: u64,
+    blanks: u64,
+    bytes: u64,
+}
+
+impl Totals {
+    fn of(language_stats: &[(Language, LanguageStats)]) -> Self {
+        let mut totals = Totals::default();
+        for (_, stats) in language_stats {
+            totals.files += stats.files;
+            totals.lines += stats.lines;
+            totals.comments += stats.comments;
+            totals.blanks += stats.blanks;
+            totals.bytes += stats.bytes;
+        }
+        totals
+    }
+
+    fn code(&self) -> u64 {
+        self.lines
+            .saturating_sub(self.blanks)
+            .saturating_sub(self.comments)
+    }
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (part as f64 * 10_000.0 / total as f64).round() / 100.0
+}
+
+fn file_json(report: &FileReport) -> Value {
+    json!({
+        "path": report.path.display().to_string(),
+        "language": report.file_type.map(FileType::display_name),
+        "canonical": report.file_type.map(FileType::canonical),
+        "mime": report.mime,
+        "bytes": report.bytes,
+        "lines": report.lines.map(|counts| counts.lines),
+        "code": report.lines.map(|counts| counts.code()),
+        "comments": report.lines.map(|counts| counts.comments),
+        "blanks": report.lines.map(|counts| counts.blanks),
+        "generated": report.generated,
+        "compression": report.compression,
+    })
+}
+
+fn file_cells(report: &FileReport) -> [String; 11] {
+    let optional = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+    [
+        report.path.display().to_string(),
+        report
+            .file_type
+            .map(|ft| ft.display_name().to_string())
+            .unwrap_or_default(),
+        report
+            .file_type
+            .map(|ft| ft.canonical().to_string())
+            .unwrap_or_default(),
+        report.mime.to_string(),
+        optional(report.bytes),
+        optional(report.lines.map(|counts| counts.lines)),
+        optional(report.lines.map(|counts| counts.code())),
+        optional(report.lines.map(|counts| counts.comments)),
+        optional(report.lines.map(|counts| counts.blanks)),
+        report.generated.to_string(),
+        report.compression.unwrap_or_default().to_string(),
+    ]
+}
+
+fn listing_json(file: &ScannedFile) -> Value {
+    json!({
+        "path": file.path.display().to_string(),
+        "language": file.file_type.display_name(),
+        "canonical": file.file_type.canonical(),
+        "stage": file.stage.to_string(),
+        "mime": file.mime,
+        "bytes": file.bytes,
+        "lines": file.lines.map(|counts| counts.lines),
+        "code": file.lines.map(|counts| counts.code()),
+        "comments": file.lines.map(|counts| counts.comments),
+        "blanks": file.lines.map(|counts| counts.blanks),
+    })
+}
+
+fn listing_cells(file: &ScannedFile) -> [String; 10] {
+    let optional = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+    [
+        file.path.display().to_string(),
+        file.file_type.display_name().to_string(),
+        file.file_type.canonical().to_string(),
+        file.stage.to_string(),
+        file.mime.to_string(),
+        file.bytes.to_string(),
+        optional(file.lines.map(|counts| counts.lines)),
+        optional(file.lines.map(|counts| counts.code())),
+        optional(file.lines.map(|counts| counts.comments)),
+        optional(file.lines.map(|counts| counts.blanks)),
+    ]
+}
+
+fn breakdown_json(language_stats: &[(Language, LanguageStats)], totals: &Totals) -> Value {
+    let languages: Vec<Value> = language_stats
+        .iter()
+        .map(|(language, stats)| {
+            json!({
+                "language": language.display_name(),
+                "canonical": language.canonical(),
+                "files": stats.files,
+                "lines": stats.lines,
+                "code": stats.code(),
+                "comments": stats.comments,
+                "blanks": stats.blanks,
+                "bytes": stats.bytes,
+                "percentages": {
+                    "files": percent(stats.files as u64, totals.files as u64),
+                    "lines": percent(stats.lines, totals.lines),
+                    "bytes": percent(stats.bytes, totals.bytes),
+                },
+                "paths": stats
+                    .paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    json!({
+        "languages": languages,
+        "total": {
+            "files": totals.files,
+            "lines": totals.lines,
+            "code": totals.code(),
+            "comments": totals.comments,
+            "blanks": totals.blanks,
+            "bytes": totals.bytes,
+        },
+    })
+}
+
+fn breakdown_cells(language: Language, stats: &LanguageStats, totals: &Totals) -> [String; 11] {
+    [
+        language.display_name().to_string(),
+        language.canonical().unwrap_or_default().to_string(),
+        stats.files.to_string(),
+        stats.lines.to_string(),
+        stats.code().to_string(),
+        stats.comments.to_string(),
+        stats.blanks.to_string(),
+        stats.bytes.to_string(),
+        format!("{:.2}", percent(stats.files as u64, totals.files as u64)),
+        format!("{:.2}", percent(stats.lines, totals.lines)),
+        format!("{:.2}", percent(stats.bytes, totals.bytes)),
+    ]
+}
+
+fn write_csv_row<I, S>(out: &mut impl Write, cells: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let cells: Vec<String> = cells
+        .into_iter()
+        .map(|cell| {
+            let cell = cell.as_ref();
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect();
+    writeln!(out, "{}", cells.join(","))
+}
+
+fn write_markdown_header(out: &mut impl Write, columns: &[&str]) -> io::Result<()> {
+    writeln!(out, "| {} |", columns.join(" | "))?;
+    let alignments: Vec<&str> = columns
+        .iter()
+        .map(|column| match *column {
+            "path" | "language" | "canonical" | "stage" | "mime" | "outcome" | "rule" | "level"
+            | "message" | "compression" | "commit" | "date" | "summary" | "old_path" | "group" => {
+                ":---"
+            }
+            _ => "---:",
+        })
+        .collect();
+    writeln!(out, "| {} |", alignments.join(" | "))
+}
+
+fn write_markdown_row<I, S>(out: &mut impl Write, cells: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    // A line break would end the row; `<br>` keeps it in the cell.
+    let cells: Vec<String> = cells
+        .into_iter()
+        .map(|cell| {
+            cell.as_ref()
+                .replace('|', "\\|")
+                .replace("\r\n", "<br>")
+                .replace(['\n', '\r'], "<br>")
+        })
+        .collect();
+    writeln!(out, "| {} |", cells.join(" | "))
+}
+
+fn print_file_like(out: &mut impl Write, report: &FileReport) -> io::Result<()> {
+    let path = report.path.display();
+    let (Some(file_type), Some(counts)) = (report.file_type, report.lines) else {
+        return match report.mime {
+            "application/octet-stream" => writeln!(out, "{path}: data"),
+            mime => writeln!(out, "{path}: {mime}"),
+        };
+    };
+    let LineCounts {
+        lines,
+        blanks,
+        comments,
+    } = counts;
+
+    let mut extra: Vec<String> = Vec::new();
+    extra.push(format!("mime {}", report.mime));
+    if let Some(compression) = report.compression {
+        extra.push(format!("{compression} compressed"));
+    }
+    if let Some(size) = report.bytes {
+        extra.push(format_bytes(size));
+    }
+    extra.push(format!(
+        "lines {lines}, code {}, comments {comments}, blanks {blanks}",
+        counts.code()
+    ));
+    if report.generated {
+        extra.push("generated".to_string());
+    }
+
+    writeln!(
+        out,
+        "{}: {} ({})",
+        path,
+        file_type.canonical(),
+        extra.join(", ")
+    )
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = 1024.0 * 1024.0;
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+    match bytes {
+        0..=1023 => format!("{bytes}B"),
+        _ if (bytes as f64) < MIB => format!("{:.1}KiB", (bytes as f64) / KIB),
+        _ if (bytes as f64) < GIB => format!("{:.1}MiB", (bytes as f64) / MIB),
+        _ => format!("{:.1}GiB", (bytes as f64) / GIB),
+    }
+}
+
+fn print_tokei_lite(
+    out: &mut impl Write,
+    language_stats: &[(Language, LanguageStats)],
+    totals: &Totals,
+) -> io::Result<()> {
+    let mut rows = vec![
+        [
+            "Language", "Files", "Lines", "Code", "Comments", "Blanks", "Bytes",
+        ]
+        .map(String::from),
+    ];
+    for (language, stats) in language_stats {
+        rows.push([
+            language.canonical().unwrap_or("Other").to_string(),
+            stats.files.to_string(),
+            stats.lines.to_string(),
+            stats.code().to_string(),
+            stats.comments.to_string(),
+            stats.blanks.to_string(),
+            stats.bytes.to_string(),
+        ]);
+    }
+    rows.push([
+        "Total".to_string(),
+        totals.files.to_string(),
+        totals.lines.to_string(),
+        totals.code().to_string(),
+        totals.comments.to_string(),
+        totals.blanks.to_string(),
+        totals.bytes.to_string(),
+    ]);
+
+    let mut widths = [0; 7];
+    for cells in &rows {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    for cells in &rows {
+        let mut line = format!("{:<w$}", cells[0], w = widths[0]);
+        for (cell, width) in cells.iter().zip(widths).skip(1) {
+            line.push_str(&format!(" {cell:>width$}"));
+        }
+        writeln!(out, "{line}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn written(write: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> String {
+        let mut out = Vec::new();
+        write(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn stats(files: usize, lines: u64, comments: u64, blanks: u64, bytes: u64) -> LanguageStats {
+        let mut stats = LanguageStats::default();
+        stats.files = files;
+        stats.lines = lines;
+        stats.comments = comments;
+        stats.blanks = blanks;
+        stats.bytes = bytes;
+        stats
+    }
+
+    #[test]
+    fn test_csv_escaping() {
+        let row = written(|out| {
+            write_csv_row(
+                out,
+                ["plain", "a,b", "say \"hi\"", "two\nlines", "cr\r", ""],
+            )
+        });
+        assert_eq!(
+            "plain,\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\",\"cr\r\",\n",
+            row
+        );
+    }
+
+    #[test]
+    fn test_markdown_escaping() {
+        let table = written(|out| {
+            write_markdown_header(out, &["path", "bytes"])?;
+            write_markdown_row(out, ["a|b.rs", "1"])?;
+            write_markdown_row(out, ["two\nlines\r\n.rs", "2"])
+        });
+        assert_eq!(
+            "| path | bytes |\n\
+             | :--- | ---: |\n\
+             | a\\|b.rs | 1 |\n\
+             | two<br>lines<br>.rs | 2 |\n",
+            table
+        );
+    }
+
+    #[test]
+    fn test_breakdown_json() {
+        let language_stats = [
+            (Language::Type(FileType::Rust), stats(3, 100, 20, 10, 3000)),
+            (Language::Other, stats(1, 0, 0, 0, 1000)),
+        ];
+        let json = breakdown_json(&language_stats, &Totals::of(&language_stats));
+        assert_eq!(
+            json!({
+                "languages": [
+                    {
+                        "language": "Rust",
+                        "canonical": "rust",
+                        "files": 3,
+                        "lines": 100,
+                        "code": 70,
+                        "comments": 20,
+                        "blanks": 10,
+                        "bytes": 3000,
+                        "percentages": { "files": 75.0, "lines": 100.0, "bytes": 75.0 },
+                        "paths": [],
+                    },
+                    {
+                        "language": "Other",
+                        "canonical": null,
+                        "files": 1,
+                        "lines": 0,
+                        "code": 0,
+                        "comments": 0,
+                        "blanks": 0,
+                        "bytes": 1000,
+                        "percentages": { "files": 25.0, "lines": 0.0, "bytes": 25.0 },
+                        "paths": [],
+                    },
+                ],
+                "total": {
+                    "files": 4,
+                    "lines": 100,
+                    "code": 70,
+                    "comments": 20,
+                    "blanks": 10,
+                    "bytes": 4000,
+                },
+            }),
+            json
+        );
+        let cells = breakdown_cells(
+            language_stats[0].0,
+            &language_stats[0].1,
+            &Totals::default(),
+        );
+        assert_eq!(["0.00", "0.00", "0.00"], cells[8..]);
+    }
+}
@@ -0,0 +1,470 @@
+//! `palate report`: the language breakdown as a self-contained SVG language bar, like the
+//! one on a GitHub repository page, or as a static HTML page with sortable tables and a
+//! drill-down into every directory.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Component, Path},
+};
+
+use palate::scan::{LanguageBreakdown, LanguageStats, ScannedFile};
+
+use crate::output::{Language, Metric, format_bytes};
+
+/// Width of the SVG in pixels.
+const SVG_WIDTH: usize = 480;
+
+/// Height of the bar in pixels.
+const BAR_HEIGHT: usize = 8;
+
+/// Legend entries per row, each this many pixels wide.
+const LEGEND_COLUMNS: usize = 3;
+const LEGEND_COLUMN_WIDTH: usize = SVG_WIDTH / LEGEND_COLUMNS;
+
+/// Distance between the baselines of the legend rows in pixels.
+const LEGEND_ROW_HEIGHT: usize = 20;
+
+/// Linguist's color for languages that don't define one.
+const DEFAULT_COLOR: &str = "#cccccc";
+
+/// Fonts of the GitHub interface.
+const FONT: &str = "-apple-system, BlinkMacSystemFont, 'Segoe UI', Helvetica, Arial, sans-serif";
+
+/// The languages of `language_stats` as a bar in their Linguist colors, sized by `by`,
+/// over a legend with their percentages. Languages of size zero are left out.
+pub(crate) fn svg(language_stats: &[(Language, LanguageStats)], by: Metric) -> String {
+    let total: u64 = language_stats.iter().map(|(_, stats)| by.of(stats)).sum();
+    let shares: Vec<(Language, u64)> = language_stats
+        .iter()
+        .map(|(language, stats)| (*language, by.of(stats)))
+        .filter(|(_, value)| *value > 0)
+        .collect();
+    let rows = shares.len().div_ceil(LEGEND_COLUMNS);
+    let height = if rows == 0 {
+        BAR_HEIGHT
+    } else {
+        BAR_HEIGHT + 6 + rows * LEGEND_ROW_HEIGHT
+    };
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{SVG_WIDTH}" height="{height}" viewBox="0 0 {SVG_WIDTH} {height}" role="img" aria-label="Languages by {by}">"#,
+        by = by.as_str(),
+    );
+    let _ = writeln!(
+        svg,
+        "<style>text {{ font: 12px {FONT}; fill: #1f2328; }} .share {{ fill: #59636e; }} \
+         @media (prefers-color-scheme: dark) {{ text {{ fill: #f0f6fc; }} .share {{ fill: #9198a1; }} }}</style>"
+    );
+    let _ = writeln!(
+        svg,
+        r#"<clipPath id="bar"><rect width="{SVG_WIDTH}" height="{BAR_HEIGHT}" rx="{}"/></clipPath>"#,
+        BAR_HEIGHT / 2
+    );
+    let _ = writeln!(svg, r#"<g clip-path="url(#bar)">"#);
+    let _ = writeln!(
+        svg,
+        r#"<rect width="{SVG_WIDTH}" height="{BAR_HEIGHT}" fill="{DEFAULT_COLOR}"/>"#
+    );
+    // Each segment overlaps the next by a pixel, so no background shows between them.
+    let mut x = 0.0;
+    for (language, value) in &shares {
+        let width = *value as f64 * SVG_WIDTH as f64 / total as f64;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x:.2}" width="{:.2}" height="{BAR_HEIGHT}" fill="{}"><title>{}</title></rect>"#,
+            width + 1.0,
+            color(*language),
+            escape(&label(*language, *value, total)),
+        );
+        x += width;
+    }
+    let _ = writeln!(svg, "</g>");
+    for (index, (language, value)) in shares.iter().enumerate() {
+        let x = (index % LEGEND_COLUMNS) * LEGEND_COLUMN_WIDTH;
+        let y = BAR_HEIGHT + (index / LEGEND_COLUMNS + 1) * LEGEND_ROW_HEIGHT;
+        let _ = writeln!(
+            svg,
+            r#"<circle cx="{}" cy="{}" r="4" fill="{}"/><text x="{}" y="{y}">{} <tspan class="share">{}</tspan></text>"#,
+            x + 4,
+            y - 4,
+            color(*language),
+            x + 14,
+            escape(language.display_name()),
+            share(*value, total),
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// A page titled `title` with the bar of [`svg`], the table of `language_stats` and a
+/// drill-down into the directories of `files` with their own tables and file lists.
+/// `rows` turns the breakdown of a directory into its table rows.
+pub(crate) fn html(
+    title: &str,
+    language_stats: &[(Language, LanguageStats)],
+    files: &[ScannedFile],
+    by: Metric,
+    rows: impl Fn(LanguageBreakdown) -> Vec<(Language, LanguageStats)>,
+) -> String {
+    let mut tree = Dir::default();
+    for file in files {
+        let parent = file.path.parent().unwrap_or(Path::new(""));
+        tree.insert(parent.components(), file);
+    }
+    let (path, top) = tree.collapsed(String::new());
+
+    let mut html = String::new();
+    let _ = writeln!(
+        html,
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="generator" content="palate {}">
+<title>Languages of {title}</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<h1>Languages of {title}</h1>"#,
+        env!("CARGO_PKG_VERSION"),
+        title = escape(title),
+    );
+    html.push_str(&svg(language_stats, by));
+    let _ = writeln!(
+        html,
+        "<p>Percentages are of the {} of each language.</p>",
+        by.as_str()
+    );
+    write_language_table(&mut html, language_stats, by);
+    let _ = writeln!(html, "<h2>Files in {}</h2>", escape(display(&path)));
+    write_dir(&mut html, top, by, &rows);
+    let _ = writeln!(html, "<script>{SCRIPT}</script>\n</body>\n</html>");
+    html
+}
+
+/// A directory of the drill-down: the files directly in it and its subdirectories.
+#[derive(Default)]
+struct Dir<'a> {
+    files: Vec<&'a ScannedFile>,
+    dirs: BTreeMap<String, Dir<'a>>,
+}
+
+impl<'a> Dir<'a> {
+    fn insert(&mut self, mut components: std::path::Components, file: &'a ScannedFile) {
+        match components.next() {
+            Some(component) => self
+                .dirs
+                .entry(name(component))
+                .or_default()
+                .insert(components, file),
+            None => self.files.push(file),
+        }
+    }
+
+    /// `self`, or the first directory below it that has files or more than one
+    /// subdirectory, with `path` extended to it: `src/main/java` is one step.
+    fn collapsed(&self, mut path: String) -> (String, &Self) {
+        let mut dir = self;
+        while dir.files.is_empty() && dir.dirs.len() == 1 {
+            let (name, only) = dir.dirs.iter().next().expect("one subdirectory");
+            if !path.is_empty() && !path.ends_with('/') {
+                path.push('/');
+            }
+            path.push_str(name);
+            dir = only;
+        }
+        (path, dir)
+    }
+
+    fn all_files(&self, files: &mut Vec<ScannedFile>) {
+        files.extend(self.files.iter().map(|&file| file.clone()));
+        for dir in self.dirs.values() {
+            dir.all_files(files);
+        }
+    }
+}
+
+fn write_dir(
+    html: &mut String,
+    dir: &Dir,
+    by: Metric,
+    rows: &impl Fn(LanguageBreakdown) -> Vec<(Language, LanguageStats)>,
+) {
+    for (name, subdir) in &dir.dirs {
+        let (name, subdir) = subdir.collapsed(name.clone());
+        let mut files = Vec::new();
+        subdir.all_files(&mut files);
+        let language_stats = rows(files.into_iter().collect());
+        let count: usize = language_stats.iter().map(|(_, stats)| stats.files).sum();
+        let bytes: u64 = language_stats.iter().map(|(_, stats)| stats.bytes).sum();
+        let _ = writeln!(
+            html,
+            r#"<details><summary><span class="name">{}/</span> <span class="meta">{} {}, {}</span>"#,
+            escape(&name),
+            count,
+            if count == 1 { "file" } else { "files" },
+            format_bytes(bytes),
+        );
+        write_mini_bar(html, &language_stats, by);
+        html.push_str("</summary>\n<div class=\"dir\">\n");
+        write_language_table(html, &language_stats, by);
+        write_dir(html, subdir, by, rows);
+        html.push_str("</div></details>\n");
+    }
+    if dir.files.is_empty() {
+        return;
+    }
+    html.push_str(
+        "<table class=\"files\"><thead><tr><th>File</th><th>Language</th><th>Lines</th>\
+         <th>Code</th><th>Comments</th><th>Blanks</th><th>Bytes</th></tr></thead><tbody>\n",
+    );
+    for file in &dir.files {
+        let lines = file.lines.unwrap_or_default();
+        let language = Language::Type(file.file_type);
+        let _ = writeln!(
+            html,
+            r#"<tr><td title="{path}">{}</td><td>{}{}</td>{}{}{}{}<td data-value="{}">{}</td></tr>"#,
+            escape(&file.path.file_name().map_or_else(
+                || file.path.display().to_string(),
+                |name| { name.to_string_lossy().into_owned() }
+            )),
+            dot(language),
+            escape(language.display_name()),
+            number(lines.lines),
+            number(lines.code()),
+            number(lines.comments),
+            number(lines.blanks),
+            file.bytes,
+            format_bytes(file.bytes),
+            path = escape(&file.path.display().to_string()),
+        );
+    }
+    html.push_str("</tbody></table>\n");
+}
+
+fn write_language_table(
+    html: &mut String,
+    language_stats: &[(Language, LanguageStats)],
+    by: Metric,
+) {
+    let total: u64 = language_stats.iter().map(|(_, stats)| by.of(stats)).sum();
+    html.push_str(
+        "<table><thead><tr><th>Language</th><th>Files</th><th>Lines</th><th>Code</th>\
+         <th>Comments</th><th>Blanks</th><th>Bytes</th><th>%</th></tr></thead><tbody>\n",
+    );
+    for (language, stats) in language_stats {
+        let value = by.of(stats);
+        let _ = writeln!(
+            html,
+            r#"<tr><td>{}{}</td>{}{}{}{}{}<td data-value="{}">{}</td><td data-value="{value}">{}</td></tr>"#,
+            dot(*language),
+            escape(language.display_name()),
+            number(stats.files as u64),
+            number(stats.lines),
+            number(stats.code()),
+            number(stats.comments),
+            number(stats.blanks),
+            stats.bytes,
+            format_bytes(stats.bytes),
+            share(value, total),
+        );
+    }
+    html.push_str("</tbody></table>\n");
+}
+
+/// The bar of a directory, drawn with HTML so it scales with the page.
+fn write_mini_bar(html: &mut String, language_stats: &[(Language, LanguageStats)], by: Metric) {
+    let total: u64 = language_stats.iter().map(|(_, stats)| by.of(stats)).sum();
+    html.push_str(r#"<span class="bar">"#);
+    for (language, stats) in language_stats {
+        let value = by.of(stats);
+        if value == 0 {
+            continue;
+        }
+        let _ = write!(
+            html,
+            r#"<span style="width: {:.2}%; background: {}" title="{}"></span>"#,
+            value as f64 * 100.0 / total as f64,
+            color(*language),
+            escape(&label(*language, value, total)),
+        );
+    }
+    html.push_str("</span>");
+}
+
+fn number(value: u64) -> String {
+    format!("<td>{value}</td>")
+}
+
+fn dot(language: Language) -> String {
+    format!(
+        r#"<span class="dot" style="background: {}"></span>"#,
+        color(language)
+    )
+}
+
+fn color(language: Language) -> &'static str {
+    match language {
+        Language::Type(file_type) => file_type.linguist_color(),
+        Language::Other => None,
+    }
+    .unwrap_or(DEFAULT_COLOR)
+}
+
+fn share(value: u64, total: u64) -> String {
+    if total == 0 {
+        return "0.0%".to_string();
+    }
+    format!("{:.1}%", value as f64 * 100.0 / total as f64)
+}
+
+fn label(language: Language, value: u64, total: u64) -> String {
+    format!("{} {}", language.display_name(), share(value, total))
+}
+
+fn name(component: Component) -> String {
+    component.as_os_str().to_string_lossy().into_owned()
+}
+
+fn display(path: &str) -> &str {
+    if path.is_empty() { "." } else { path }
+}
+
+/// `text` with the characters that are special in HTML and XML escaped.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const STYLE: &str = r#"
+body { font: 14px/1.5 -apple-system, BlinkMacSystemFont, 'Segoe UI', Helvetica, Arial, sans-serif; color: #1f2328; max-width: 980px; margin: 2em auto; padding: 0 1em; }
+h1 { font-size: 1.6em; }
+h2 { font-size: 1.2em; margin-top: 2em; }
+table { border-collapse: collapse; margin: 0.5em 0 1em; width: 100%; }
+th, td { padding: 0.25em 0.75em; border-bottom: 1px solid #d1d9e0; text-align: right; white-space: nowrap; }
+th:first-child, td:first-child, table.files td:nth-child(2), table.files th:nth-child(2) { text-align: left; }
+th { cursor: pointer; user-select: none; background: #f6f8fa; }
+th[data-order=ascending]::after { content: " ▲"; }
+th[data-order=descending]::after { content: " ▼"; }
+.dot { display: inline-block; width: 0.75em; height: 0.75em; border-radius: 50%; margin-right: 0.5em; }
+details { margin: 0.25em 0; }
+summary { cursor: pointer; padding: 0.25em 0; }
+.name { font-weight: 600; }
+.meta { color: #59636e; }
+.bar { display: inline-flex; width: 200px; height: 8px; margin-left: 1em; border-radius: 4px; overflow: hidden; background: #cccccc; vertical-align: middle; }
+.dir { margin-left: 1.5em; }
+@media (prefers-color-scheme: dark) {
+  body { background: #0d1117; color: #f0f6fc; }
+  th { background: #151b23; }
+  th, td { border-color: #3d444d; }
+  .meta { color: #9198a1; }
+}
+"#;
+
+/// Sorts a table by the column whose header is clicked, numerically by `data-value` or
+/// the text of the cells, and the other way around on the next click.
+const SCRIPT: &str = r#"
+for (const th of document.querySelectorAll("th")) {
+  th.addEventListener("click", () => {
+    const body = th.closest("table").tBodies[0];
+    const ascending = th.dataset.order !== "ascending";
+    for (const other of th.parentNode.children) delete other.dataset.order;
+    th.dataset.order = ascending ? "ascending" : "descending";
+    const key = (row) => {
+      const cell = row.cells[th.cellIndex];
+      return cell.dataset.value ?? cell.textContent.replace(/%$/, "");
+    };
+    const rows = [...body.rows].sort((a, b) => {
+      const [x, y] = [key(a), key(b)];
+      const order = isNaN(x) || isNaN(y) ? x.localeCompare(y) : x - y;
+      return ascending ? order : -order;
+    });
+    body.append(...rows);
+  });
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use palate::{FileType, scan::ScanBuilder};
+
+    use super::*;
+
+    fn rows(breakdown: LanguageBreakdown) -> Vec<(Language, LanguageStats)> {
+        breakdown
+            .languages
+            .into_iter()
+            .map(|(file_type, stats)| (Language::Type(file_type), stats))
+            .collect()
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;",
+            escape(r#"<a href="x">Tom & Jerry's</a>"#)
+        );
+    }
+
+    #[test]
+    fn test_svg() {
+        let stats = |bytes| {
+            let mut stats = LanguageStats::default();
+            stats.bytes = bytes;
+            stats
+        };
+        let language_stats = [
+            (Language::Type(FileType::Rust), stats(300)),
+            (Language::Type(FileType::Go), stats(100)),
+            (Language::Type(FileType::C), stats(0)),
+        ];
+        let bar = svg(&language_stats, Metric::Bytes);
+        assert!(
+            bar.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="480" height="34""#)
+        );
+        assert!(bar.contains(r##"<rect x="0.00" width="361.00" height="8" fill="#dea584"><title>Rust 75.0%</title></rect>"##));
+        assert!(bar.contains(r##"<rect x="360.00" width="121.00" height="8" fill="#00ADD8"><title>Go 25.0%</title></rect>"##));
+        assert!(
+            bar.contains(r#"<text x="174" y="28">Go <tspan class="share">25.0%</tspan></text>"#)
+        );
+        assert!(!bar.contains(">C "));
+
+        let empty = svg(&[], Metric::Bytes);
+        assert!(empty.contains(r#"height="8""#) && !empty.contains("<text"));
+    }
+
+    #[test]
+    fn test_html_escapes_file_names() {
+        let dir = tempfile::Builder::new()
+            .prefix("palate-")
+            .tempdir()
+            .unwrap();
+        std::fs::create_dir_all(dir.path().join("<b>")).unwrap();
+        std::fs::write(dir.path().join("<b>/x\"&'.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.path().join("main.go"), "package main\n").unwrap();
+        let files = ScanBuilder::new(dir.path()).files();
+        let language_stats = rows(files.iter().cloned().collect());
+        let html = html("<repo> & co", &language_stats, &files, Metric::Bytes, rows);
+
+        assert!(html.contains("<title>Languages of &lt;repo&gt; &amp; co</title>"));
+        assert!(html.contains(r#"<span class="name">&lt;b&gt;/</span>"#));
+        assert!(html.contains("<td title=\""));
+        assert!(html.contains(">x&quot;&amp;&#39;.rs</td>"));
+        assert!(!html.contains("<b>") && !html.contains("x\"&"));
+    }
+}
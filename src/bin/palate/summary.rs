@@ -0,0 +1,195 @@
+//! Linguist-style language summary for the text breakdown: a proportional bar in each
+//! language's Linguist color followed by a legend with percentages.
+
+use std::io;
+
+use palate::scan::LanguageStats;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+use crate::output::{Language, Metric};
+
+/// Width of the language bar in terminal cells.
+const BAR_WIDTH: usize = 60;
+
+/// Legend lines wrap before this many columns.
+const LEGEND_WIDTH: usize = 80;
+
+/// Linguist's color for languages that don't define one.
+const DEFAULT_COLOR: Color = Color::Rgb(0xcc, 0xcc, 0xcc);
+
+/// Write the bar (only when `out` supports color) and the legend, followed by a blank
+/// line. Writes nothing when there is nothing to measure.
+pub(crate) fn print_summary(
+    out: &mut impl WriteColor,
+    language_stats: &[(Language, LanguageStats)],
+    by: Metric,
+) -> io::Result<()> {
+    let total: u64 = language_stats.iter().map(|(_, stats)| by.of(stats)).sum();
+    if total == 0 {
+        return Ok(());
+    }
+
+    // Same order as the table, which is sorted by `--sort`.
+    let shares: Vec<(Language, u64)> = language_stats
+        .iter()
+        .map(|(language, stats)| (*language, by.of(stats)))
+        .filter(|(_, value)| *value > 0)
+        .collect();
+
+    if out.supports_color() {
+        print_bar(out, &shares, total)?;
+    }
+    print_legend(out, &shares, total)?;
+    writeln!(out)
+}
+
+fn print_bar(out: &mut impl WriteColor, shares: &[(Language, u64)], total: u64) -> io::Result<()> {
+    for ((language, _), cells) in shares.iter().zip(bar_cells(shares, total)) {
+        if cells == 0 {
+            continue;
+        }
+        out.set_color(ColorSpec::new().set_fg(Some(color(*language))))?;
+        write!(out, "{}", "█".repeat(cells))?;
+    }
+    out.reset()?;
+    writeln!(out)
+}
+
+fn print_legend(
+    out: &mut impl WriteColor,
+    shares: &[(Language, u64)],
+    total: u64,
+) -> io::Result<()> {
+    let mut column = 0;
+    for (language, value) in shares {
+        let label = format!(
+            "{} {:.1}%",
+            language.display_name(),
+            *value as f64 * 100.0 / total as f64
+        );
+        // "● " plus the label, and two spaces between entries.
+        let width = label.chars().count() + 2;
+        if column > 0 && column + 2 + width > LEGEND_WIDTH {
+            writeln!(out)?;
+            column = 0;
+        }
+        if column > 0 {
+            write!(out, "  ")?;
+            column += 2;
+        }
+        out.set_color(ColorSpec::new().set_fg(Some(color(*language))))?;
+        write!(out, "●")?;
+        out.reset()?;
+        write!(out, " {label}")?;
+        column += width;
+    }
+    writeln!(out)
+}
+
+/// Split [`BAR_WIDTH`] cells proportionally with the largest remainder method, so the
+/// bar always has the same width.
+fn bar_cells(shares: &[(Language, u64)], total: u64) -> Vec<usize> {
+    let exact: Vec<f64> = shares
+        .iter()
+        .map(|(_, value)| *value as f64 * BAR_WIDTH as f64 / total as f64)
+        .collect();
+    let mut cells: Vec<usize> = exact.iter().map(|cells| cells.floor() as usize).collect();
+    let mut by_remainder: Vec<usize> = (0..exact.len()).collect();
+    by_remainder.sort_by(|&a, &b| {
+        let remainder = |i: usize| exact[i] - exact[i].floor();
+        remainder(b).total_cmp(&remainder(a)).then(a.cmp(&b))
+    });
+    let assigned: usize = cells.iter().sum();
+    for i in by_remainder
+        .into_iter()
+        .take(BAR_WIDTH.saturating_sub(assigned))
+    {
+        cells[i] += 1;
+    }
+    cells
+}
+
+fn color(language: Language) -> Color {
+    match language {
+        Language::Type(file_type) => file_type.linguist_color().and_then(parse_hex_color),
+        Language::Other => None,
+    }
+    .unwrap_or(DEFAULT_COLOR)
+}
+
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use palate::FileType;
+    use termcolor::{Ansi, NoColor};
+
+    use super::*;
+
+    fn shares(values: &[u64]) -> Vec<(Language, u64)> {
+        values
+            .iter()
+            .map(|&value| (Language::Other, value))
+            .collect()
+    }
+
+    #[test]
+    fn test_bar_cells() {
+        // Always the full width, split by the largest remainders.
+        assert_eq!(vec![20, 20, 20], bar_cells(&shares(&[1, 1, 1]), 3));
+        assert_eq!(vec![59, 1], bar_cells(&shares(&[990, 10]), 1000));
+        assert_eq!(vec![60, 0, 0], bar_cells(&shares(&[995, 4, 1]), 1000));
+        assert_eq!(vec![60], bar_cells(&shares(&[7]), 7));
+    }
+
+    #[test]
+    fn test_print_summary() {
+        let language_stats = |values: &[(FileType, u64)]| -> Vec<(Language, LanguageStats)> {
+            values
+                .iter()
+                .map(|&(file_type, bytes)| {
+                    let mut stats = LanguageStats::default();
+                    stats.bytes = bytes;
+                    (Language::Type(file_type), stats)
+                })
+                .collect()
+        };
+        let rust_and_go =
+            language_stats(&[(FileType::Rust, 750), (FileType::Go, 250), (FileType::C, 0)]);
+
+        let mut plain = NoColor::new(Vec::new());
+        print_summary(&mut plain, &rust_and_go, Metric::Bytes).unwrap();
+        assert_eq!(
+            "● Rust 75.0%  ● Go 25.0%\n\n",
+            String::from_utf8(plain.into_inner()).unwrap()
+        );
+
+        let mut colored = Ansi::new(Vec::new());
+        print_summary(&mut colored, &rust_and_go, Metric::Bytes).unwrap();
+        let colored = String::from_utf8(colored.into_inner()).unwrap();
+        assert_eq!(60, colored.matches('█').count());
+        assert!(colored.contains("\x1b[38;2;222;165;132m"), "{colored:?}");
+
+        let mut empty = NoColor::new(Vec::new());
+        print_summary(&mut empty, &rust_and_go, Metric::Files).unwrap();
+        assert!(empty.into_inner().is_empty());
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(
+            Some(Color::Rgb(0xde, 0xa5, 0x84)),
+            parse_hex_color("#dea584")
+        );
+        assert_eq!(None, parse_hex_color("dea584"));
+        assert_eq!(None, parse_hex_color("#fff"));
+        assert_eq!(None, parse_hex_color("#zzzzzz"));
+    }
+}
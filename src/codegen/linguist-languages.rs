@@ -0,0 +1,1353 @@
+const fn linguist_name(file_type: FileType) -> Option<&'static str> {
+    match file_type {
+        FileType::Abap => Some("ABAP"),
+        FileType::AbapCds => Some("ABAP CDS"),
+        FileType::Abnf => Some("ABNF"),
+        FileType::Actionscript => Some("ActionScript"),
+        FileType::AdBlockFilters => Some("Adblock Filter List"),
+        FileType::Ada => Some("Ada"),
+        FileType::Afdko => Some("OpenType Feature File"),
+        FileType::Agda => Some("Agda"),
+        FileType::Ags => Some("AGS Script"),
+        FileType::Aidl => Some("AIDL"),
+        FileType::Aiken => Some("Aiken"),
+        FileType::Algol => Some("ALGOL"),
+        FileType::Alloy => Some("Alloy"),
+        FileType::Altium => Some("Altium Designer"),
+        FileType::Ampl => Some("AMPL"),
+        FileType::Amusewiki => Some("Muse"),
+        FileType::AnswerSetProgramming => Some("Answer Set Programming"),
+        FileType::Ant => Some("Ant Build System"),
+        FileType::Antlers => Some("Antlers"),
+        FileType::Antlr4 => Some("ANTLR"),
+        FileType::Apache => Some("ApacheConf"),
+        FileType::Apex => Some("Apex"),
+        FileType::ApiBlueprint => Some("API Blueprint"),
+        FileType::Apkbuild => Some("Alpine Abuild"),
+        FileType::Apl => Some("APL"),
+        FileType::ApolloGuidanceComputer => Some("Apollo Guidance Computer"),
+        FileType::AppleScript => Some("AppleScript"),
+        FileType::Arc => Some("Arc"),
+        FileType::Arduino => Some("Processing"),
+        FileType::AsciiDoc => Some("AsciiDoc"),
+        FileType::AsciiStl => Some("STL"),
+        FileType::Asl => Some("ASL"),
+        FileType::Asn => Some("ASN.1"),
+        FileType::Asp => Some("Classic ASP"),
+        FileType::Aspectj => Some("AspectJ"),
+        FileType::Aspx => Some("ASP.NET"),
+        FileType::Astro => Some("Astro"),
+        FileType::Asy => Some("Asymptote"),
+        FileType::Atlas => Some("AngelScript"),
+        FileType::Ats2 => Some("ATS"),
+        FileType::Augeas => Some("Augeas"),
+        FileType::AutoHotKey => Some("AutoHotkey"),
+        FileType::AutoIt => Some("AutoIt"),
+        FileType::AvroIdl => Some("Avro IDL"),
+        FileType::Awk => Some("Awk"),
+        FileType::B => Some("B (Formal Method)"),
+        FileType::Ballerina => Some("Ballerina"),
+        FileType::BashSession => Some("ShellSession"),
+        FileType::Basic => Some("BASIC"),
+        FileType::BasicForAndroid => Some("B4X"),
+        FileType::Bdf => Some("Glyph Bitmap Distribution Format"),
+        FileType::Be => Some("Berry"),
+        FileType::Beef => Some("Beef"),
+        FileType::Befunge => Some("Befunge"),
+        FileType::Bib => Some("BibTeX"),
+        FileType::Bicep => Some("Bicep"),
+        FileType::Bindzone => Some("DNS Zone"),
+        FileType::Bison => Some("Bison"),
+        FileType::Bitbake => Some("BitBake"),
+        FileType::Blade => Some("Blade"),
+        FileType::BluespecBsv => Some("Bluespec"),
+        FileType::Bmax => Some("BlitzMax"),
+        FileType::Boo => Some("Boo"),
+        FileType::Boogie => Some("Boogie"),
+        FileType::Bqn => Some("BQN"),
+        FileType::Brainfuck => Some("Brainfuck"),
+        FileType::Brighterscript => Some("BrighterScript"),
+        FileType::Brightscript => Some("Brightscript"),
+        FileType::Bro => Some("Zeek"),
+        FileType::Browserslist => Some("Browserslist"),
+        FileType::Bru => Some("Bru"),
+        FileType::Bst => Some("BibTeX Style"),
+        FileType::Byond => Some("DM"),
+        FileType::C => Some("C"),
+        FileType::C3 => Some("C3"),
+        FileType::CMake => Some("CMake"),
+        FileType::CObjdump => Some("C-ObjDump"),
+        FileType::CSharp => Some("C#"),
+        FileType::Cabal => Some("Cabal Config"),
+        FileType::Caddy => Some("Caddyfile"),
+        FileType::Cairo => Some("Cairo"),
+        FileType::Cameligo => Some("CameLIGO"),
+        FileType::Cangjie => Some("Cangjie"),
+        FileType::Capnp => Some("Cap'n Proto"),
+        FileType::Carbon => Some("Carbon"),
+        FileType::Cdc => Some("Cadence"),
+        FileType::Cdrtoc => Some("World of Warcraft Addon Data"),
+        FileType::Cds => Some("CAP CDS"),
+        FileType::Ceylon => Some("Ceylon"),
+        FileType::Cf => Some("ColdFusion"),
+        FileType::Charity => Some("Charity"),
+        FileType::Chaskell => Some("C2hs Haskell"),
+        FileType::Checksum => Some("Checksums"),
+        FileType::Chpl => Some("Chapel"),
+        FileType::Chuck => Some("ChucK"),
+        FileType::Cil => Some("CIL"),
+        FileType::Circom => Some("Circom"),
+        FileType::Cirru => Some("Cirru"),
+        FileType::Clarion => Some("Clarion"),
+        FileType::Clarity => Some("Clarity"),
+        FileType::Clean => Some("Clean"),
+        FileType::Click => Some("Click"),
+        FileType::Clipper => Some("xBase"),
+        FileType::Clojure => Some("Clojure"),
+        FileType::CloudFirestoreSecurityRules => Some("Cloud Firestore Security Rules"),
+        FileType::Clue => Some("Clue"),
+        FileType::Cobol => Some("COBOL"),
+        FileType::Coccinelle => Some("SmPL"),
+        FileType::Codeowners => Some("CODEOWNERS"),
+        FileType::Collada => Some("COLLADA"),
+        FileType::ComponentPascal => Some("Component Pascal"),
+        FileType::ConfIni => Some("INI"),
+        FileType::Config => Some("M4Sugar"),
+        FileType::Conll => Some("CoNLL-U"),
+        FileType::Cook => Some("Cooklang"),
+        FileType::Coq => Some("Rocq Prover"),
+        FileType::Cpp => Some("C++"),
+        FileType::Creole => Some("Creole"),
+        FileType::Crontab => Some("crontab"),
+        FileType::Crystal => Some("Crystal"),
+        FileType::Csc => Some("GSC"),
+        FileType::Cson => Some("CSON"),
+        FileType::CsoundCsd => Some("Csound Document"),
+        FileType::CsoundOrc => Some("Csound"),
+        FileType::CsoundSco => Some("Csound Score"),
+        FileType::Css => Some("CSS"),
+        FileType::Csv => Some("CSV"),
+        FileType::Cucumber => Some("Gherkin"),
+        FileType::Cuda => Some("Cuda"),
+        FileType::Cue => Some("CUE"),
+        FileType::Curlrc => Some("cURL Config"),
+        FileType::Curry => Some("Curry"),
+        FileType::Cweb => Some("CWeb"),
+        FileType::Cwl => Some("Common Workflow Language"),
+        FileType::Cycript => Some("Cycript"),
+        FileType::Cylc => Some("Cylc"),
+        FileType::Cypher => Some("Cypher"),
+        FileType::D => Some("D"),
+        FileType::D2lang => Some("D2"),
+        FileType::DObjdump => Some("D-ObjDump"),
+        FileType::DTrace => Some("DTrace"),
+        FileType::Dafny => Some("Dafny"),
+        FileType::Dart => Some("Dart"),
+        FileType::Daslang => Some("Daslang"),
+        FileType::Dataweave => Some("DataWeave"),
+        FileType::Dcl => Some("DIGITAL Command Language"),
+        FileType::DebianPackageControlFile => Some("Debian Package Control File"),
+        FileType::Denizenscript => Some("DenizenScript"),
+        FileType::Desktop => Some("desktop"),
+        FileType::Dhall => Some("Dhall"),
+        FileType::Diff => Some("Diff"),
+        FileType::DirColors => Some("dircolors"),
+        FileType::Dockerfile => Some("Dockerfile"),
+        FileType::Dogescript => Some("Dogescript"),
+        FileType::DosBatch => Some("Batchfile"),
+        FileType::DosIni => Some("NPM Config"),
+        FileType::Dot => Some("Graphviz (DOT)"),
+        FileType::Dotenv => Some("Dotenv"),
+        FileType::Dpatch => Some("Darcs Patch"),
+        FileType::Dune => Some("Dune"),
+        FileType::Dylan => Some("Dylan"),
+        FileType::E => Some("E"),
+        FileType::EJavaScript => Some("EJS"),
+        FileType::ERuby => Some("HTML+ERB"),
+        FileType::Eagle => Some("Eagle"),
+        FileType::Earthfile => Some("Earthly"),
+        FileType::Easybuild => Some("Easybuild"),
+        FileType::Ebnf => Some("EBNF"),
+        FileType::EcereProjects => Some("Ecere Projects"),
+        FileType::Ecl => Some("ECL"),
+        FileType::Eclipse => Some("ECLiPSe"),
+        FileType::Ecmarkdown => Some("Ecmarkup"),
+        FileType::Ecr => Some("HTML+ECR"),
+        FileType::Edge => Some("Edge"),
+        FileType::EditorConfig => Some("EditorConfig"),
+        FileType::EdjeDataCollection => Some("Edje Data Collection"),
+        FileType::Edn => Some("edn"),
+        FileType::EeschemaSchematic => Some("KiCad Schematic"),
+        FileType::Eiffel => Some("Eiffel"),
+        FileType::Elixir => Some("Elixir"),
+        FileType::Elm => Some("Elm"),
+        FileType::Elvish => Some("Elvish"),
+        FileType::ElvishTranscript => Some("Elvish Transcript"),
+        FileType::Emberscript => Some("EmberScript"),
+        FileType::Eq => Some("EQ"),
+        FileType::Erlang => Some("Erlang"),
+        FileType::Esdl => Some("EdgeQL"),
+        FileType::Esqlc => Some("eC"),
+        FileType::Euphoria => Some("Euphoria"),
+        FileType::FSharp => Some("F#"),
+        FileType::Factor => Some("Factor"),
+        FileType::Fan => Some("Fantom"),
+        FileType::Fancy => Some("Fancy"),
+        FileType::Faust => Some("Faust"),
+        FileType::Fennel => Some("Fennel"),
+        FileType::Fgl => Some("Genero 4gl"),
+        FileType::Figfont => Some("FIGlet Font"),
+        FileType::Filterscript => Some("Filterscript"),
+        FileType::Firrtl => Some("FIRRTL"),
+        FileType::Fish => Some("fish"),
+        FileType::Flix => Some("Flix"),
+        FileType::Fluent => Some("Fluent"),
+        FileType::Forth => Some("Forth"),
+        FileType::Fortran => Some("Fortran"),
+        FileType::FreeBasic => Some("FreeBASIC"),
+        FileType::Frege => Some("Frege"),
+        FileType::Fstar => Some("F*"),
+        FileType::Ft1cEnterprise => Some("1C Enterprise"),
+        FileType::Ft2DimensionalArray => Some("2-Dimensional Array"),
+        FileType::Ft4d => Some("4D"),
+        FileType::Futhark => Some("Futhark"),
+        FileType::GameMakerLanguage => Some("Game Maker Language"),
+        FileType::Gaml => Some("GAML"),
+        FileType::Gams => Some("GAMS"),
+        FileType::Gap => Some("GAP"),
+        FileType::Gas => Some("Unix Assembly"),
+        FileType::GdResource => Some("Godot Resource"),
+        FileType::GdScript => Some("GDScript"),
+        FileType::GdShader => Some("GDShader"),
+        FileType::Gdb => Some("GDB"),
+        FileType::Gdmo => Some("Modelica"),
+        FileType::Gedcom => Some("GEDCOM"),
+        FileType::GemText => Some("Gemini"),
+        FileType::GemfileLock => Some("Gemfile.lock"),
+        FileType::GeneroPer => Some("Genero per"),
+        FileType::GentooEbuild => Some("Gentoo Ebuild"),
+        FileType::GentooEclass => Some("Gentoo Eclass"),
+        FileType::Gf => Some("Grammatical Framework"),
+        FileType::GitAttributes => Some("Git Attributes"),
+        FileType::GitBlameIgnoreRevs => Some("Git Revision List"),
+        FileType::GitCommit => Some("Git Commit"),
+        FileType::GitConfig => Some("Git Config"),
+        FileType::GitIgnore => Some("Ignore List"),
+        FileType::Gleam => Some("Gleam"),
+        FileType::Glsl => Some("GLSL"),
+        FileType::Glyph => Some("Glyph"),
+        FileType::Gn => Some("GN"),
+        FileType::GnuPlot => Some("Gnuplot"),
+        FileType::Go => Some("Go"),
+        FileType::GoMod => Some("Go Module"),
+        FileType::GoSum => Some("Go Checksums"),
+        FileType::GoWork => Some("Go Workspace"),
+        FileType::Golo => Some("Golo"),
+        FileType::Grace => Some("Grace"),
+        FileType::GradleKotlinDsl => Some("Gradle Kotlin DSL"),
+        FileType::Grads => Some("Genie"),
+        FileType::GraphModelingLanguage => Some("Graph Modeling Language"),
+        FileType::GraphQl => Some("GraphQL"),
+        FileType::Groovy => Some("Groovy"),
+        FileType::Gsp => Some("Groovy Server Pages"),
+        FileType::Hack => Some("Hack"),
+        FileType::Haml => Some("Haml"),
+        FileType::Handlebars => Some("Handlebars"),
+        FileType::Haproxy => Some("HAProxy"),
+        FileType::Hare => Some("Hare"),
+        FileType::Haskell => Some("Haskell"),
+        FileType::Haxe => Some("Haxe"),
+        FileType::Hb => Some("Harbour"),
+        FileType::Hcl => Some("HCL"),
+        FileType::Heex => Some("HTML+EEX"),
+        FileType::Hip => Some("HIP"),
+        FileType::Hiveql => Some("HiveQL"),
+        FileType::HlsPlaylist => Some("M3U"),
+        FileType::Hlsl => Some("HLSL"),
+        FileType::Hocon => Some("HOCON"),
+        FileType::Holyc => Some("HolyC"),
+        FileType::Hoon => Some("hoon"),
+        FileType::Hosts => Some("Hosts File"),
+        FileType::Html => Some("HTML"),
+        FileType::Http => Some("HTTP"),
+        FileType::Hurl => Some("Hurl"),
+        FileType::Hxml => Some("HXML"),
+        FileType::Hy => Some("Hy"),
+        FileType::Hyphy => Some("HyPhy"),
+        FileType::I7 => Some("Inform 7"),
+        FileType::Ical => Some("iCalendar"),
+        FileType::Idl => Some("IDL"),
+        FileType::Idris => Some("Idris"),
+        FileType::Igor => Some("IGOR Pro"),
+        FileType::Ijm => Some("ImageJ Macro"),
+        FileType::Imba => Some("Imba"),
+        FileType::Ink => Some("Ink"),
+        FileType::Io => Some("Io"),
+        FileType::Ioke => Some("Ioke"),
+        FileType::Irc => Some("IRC log"),
+        FileType::Isabelle => Some("Isabelle"),
+        FileType::IsabelleRoot => Some("Isabelle ROOT"),
+        FileType::Ispc => Some("ISPC"),
+        FileType::Iss => Some("Inno Setup"),
+        FileType::J => Some("J"),
+        FileType::JProperties => Some("Java Properties"),
+        FileType::Jac => Some("Jac"),
+        FileType::Jai => Some("Jai"),
+        FileType::JanetSimple => Some("Janet"),
+        FileType::JarManifest => Some("JAR Manifest"),
+        FileType::Jasmin => Some("Jasmin"),
+        FileType::Java => Some("Java"),
+        FileType::JavaScript => Some("JavaScript"),
+        FileType::JavaScriptGlimmer => Some("Glimmer JS"),
+        FileType::JavascriptErb => Some("JavaScript+ERB"),
+        FileType::Jcl => Some("JCL"),
+        FileType::Jess => Some("CLIPS"),
+        FileType::JestSnapshot => Some("Jest Snapshot"),
+        FileType::Jflex => Some("JFlex"),
+        FileType::Jinja => Some("Jinja"),
+        FileType::Jison => Some("Jison"),
+        FileType::JisonLex => Some("Jison Lex"),
+        FileType::Jolie => Some("Jolie"),
+        FileType::Jq => Some("jq"),
+        FileType::Json => Some("JSON"),
+        FileType::Json5 => Some("JSON5"),
+        FileType::JsonC => Some("JSON with Comments"),
+        FileType::Jsonld => Some("JSONLD"),
+        FileType::Jsonnet => Some("Jsonnet"),
+        FileType::Jsp => Some("Java Server Pages"),
+        FileType::Jte => Some("Java Template Engine"),
+        FileType::Julia => Some("Julia"),
+        FileType::JuliaRepl => Some("Julia REPL"),
+        FileType::Just => Some("Just"),
+        FileType::KScript => Some("KerboScript"),
+        FileType::Kak => Some("KakouneScript"),
+        FileType::Kcl => Some("KCL"),
+        FileType::Kdl => Some("KDL"),
+        FileType::KicadLegacyLayout => Some("KiCad Legacy Layout"),
+        FileType::Kit => Some("Kit"),
+        FileType::Kivy => Some("kvlang"),
+        FileType::Koka => Some("Koka"),
+        FileType::KolmafiaAsh => Some("KoLmafia ASH"),
+        FileType::Kotlin => Some("Kotlin"),
+        FileType::Krl => Some("KRL"),
+        FileType::Ksy => Some("Kaitai Struct"),
+        FileType::Kusto => Some("Kusto"),
+        FileType::Kwt => Some("KFramework"),
+        FileType::LHaskell => Some("Literate Haskell"),
+        FileType::Labview => Some("LabVIEW"),
+        FileType::Lambdapi => Some("Lambdapi"),
+        FileType::Langium => Some("Langium"),
+        FileType::Lark => Some("Lark"),
+        FileType::Lassoscript => Some("Lasso"),
+        FileType::Latte => Some("Latte"),
+        FileType::Ld => Some("Linker Script"),
+        FileType::Lean => Some("Lean"),
+        FileType::Leo => Some("Leo"),
+        FileType::Less => Some("Less"),
+        FileType::Lex => Some("Lex"),
+        FileType::Lfe => Some("LFE"),
+        FileType::Ligolang => Some("LigoLANG"),
+        FileType::Lilypond => Some("LilyPond"),
+        FileType::Limbo => Some("Limbo"),
+        FileType::LinearProgramming => Some("Linear Programming"),
+        FileType::LinuxKernelModule => Some("Linux Kernel Module"),
+        FileType::Liquid => Some("Liquid"),
+        FileType::Lisp => Some("Common Lisp"),
+        FileType::Litcoffee => Some("Literate CoffeeScript"),
+        FileType::LiterateAgda => Some("Literate Agda"),
+        FileType::LiveScript => Some("LiveScript"),
+        FileType::LivecodeScript => Some("LiveCode Script"),
+        FileType::Llvm => Some("LLVM"),
+        FileType::Logtalk => Some("Logtalk"),
+        FileType::Lolcode => Some("LOLCODE"),
+        FileType::Lookml => Some("LookML"),
+        FileType::Loomscript => Some("LoomScript"),
+        FileType::Lsl => Some("LSL"),
+        FileType::Lua => Some("Lua"),
+        FileType::Luau => Some("Luau"),
+        FileType::M4 => Some("M4"),
+        FileType::M68k => Some("Motorola 68K Assembly"),
+        FileType::Mail => Some("E-mail"),
+        FileType::Make => Some("Makefile"),
+        FileType::Mako => Some("Mako"),
+        FileType::Maple => Some("JetBrains MPS"),
+        FileType::Markdown => Some("Markdown"),
+        FileType::Markojs => Some("Marko"),
+        FileType::Mask => Some("Mask"),
+        FileType::Matlab => Some("MATLAB"),
+        FileType::MavenPom => Some("Maven POM"),
+        FileType::MaxMsp => Some("Max"),
+        FileType::Maxscript => Some("MAXScript"),
+        FileType::Mcfunction => Some("mcfunction"),
+        FileType::Mdsvex => Some("mdsvex"),
+        FileType::Mdx => Some("MDX"),
+        FileType::Mediawiki => Some("Wikitext"),
+        FileType::Mermaid => Some("Mermaid"),
+        FileType::Meson => Some("Meson"),
+        FileType::Metal => Some("Metal"),
+        FileType::Minid => Some("MiniD"),
+        FileType::Minizinc => Some("MiniZinc"),
+        FileType::MinizincData => Some("MiniZinc Data"),
+        FileType::Mint => Some("Mint"),
+        FileType::Mirah => Some("Mirah"),
+        FileType::MircScript => Some("mIRC Script"),
+        FileType::Mlir => Some("MLIR"),
+        FileType::Mma => Some("Wolfram Language"),
+        FileType::Modula2 => Some("Macaulay2"),
+        FileType::Modula3 => Some("Modula-3"),
+        FileType::ModuleManagementSystem => Some("Module Management System"),
+        FileType::Mojo => Some("Mojo"),
+        FileType::Monkey => Some("Monkey"),
+        FileType::MonkeyC => Some("Monkey C"),
+        FileType::Moo => Some("Mercury"),
+        FileType::MoonScript => Some("MoonScript"),
+        FileType::Moonbit => Some("MoonBit"),
+        FileType::Move => Some("Move"),
+        FileType::Mql4 => Some("MQL4"),
+        FileType::Mql5 => Some("MQL5"),
+        FileType::Mss => Some("CartoCSS"),
+        FileType::Mtml => Some("MTML"),
+        FileType::Muf => Some("MUF"),
+        FileType::Mumps => Some("M"),
+        FileType::Mupad => Some("mupad"),
+        FileType::Mustache => Some("Mustache"),
+        FileType::Myghty => Some("Myghty"),
+        FileType::Nanorc => Some("nanorc"),
+        FileType::Nasal => Some("Nasal"),
+        FileType::Nasl => Some("NASL"),
+        FileType::Nasm => Some("Assembly"),
+        FileType::Nearley => Some("Nearley"),
+        FileType::Nemerle => Some("Nemerle"),
+        FileType::Nesc => Some("nesC"),
+        FileType::Netlinx => Some("NetLinx"),
+        FileType::NetlinxErb => Some("NetLinx+ERB"),
+        FileType::Netlogo => Some("NetLogo"),
+        FileType::NetteObjectNotation => Some("NEON"),
+        FileType::Nextflow => Some("Nextflow"),
+        FileType::Nginx => Some("Nginx"),
+        FileType::Nickel => Some("Nickel"),
+        FileType::Nim => Some("Nim"),
+        FileType::Ninja => Some("Ninja"),
+        FileType::Nit => Some("Nit"),
+        FileType::Nix => Some("Nix"),
+        FileType::Njk => Some("Nunjucks"),
+        FileType::Nl => Some("NL"),
+        FileType::Nmodl => Some("NMODL"),
+        FileType::Nroff => Some("Noir"),
+        FileType::Nsis => Some("NSIS"),
+        FileType::Nu => Some("Nu"),
+        FileType::Numpy => Some("NumPy"),
+        FileType::Nwscript => Some("NWScript"),
+        FileType::OCaml => Some("OCaml"),
+        FileType::Oasv2 => Some("OpenAPI Specification v2"),
+        FileType::Oasv3 => Some("OpenAPI Specification v3"),
+        FileType::Oberon => Some("Oberon"),
+        FileType::Obj => Some("Wavefront Object"),
+        FileType::ObjC => Some("Objective-C"),
+        FileType::ObjCpp => Some("Objective-C++"),
+        FileType::ObjJ => Some("Objective-J"),
+        FileType::Objdump => Some("ObjDump"),
+        FileType::Objectscript => Some("ObjectScript"),
+        FileType::Odin => Some("Odin"),
+        FileType::Omgrofl => Some("Omgrofl"),
+        FileType::OmnetppMsg => Some("OMNeT++ MSG"),
+        FileType::OmnetppNed => Some("OMNeT++ NED"),
+        FileType::Ooc => Some("ooc"),
+        FileType::Opa => Some("Opa"),
+        FileType::Opal => Some("Opal"),
+        FileType::OpenScad => Some("OpenSCAD"),
+        FileType::Openqasm => Some("OpenQASM"),
+        FileType::Openrc => Some("OpenRC runscript"),
+        FileType::OpenstepPropertyList => Some("OpenStep Property List"),
+        FileType::Opts => Some("Option List"),
+        FileType::Org => Some("Org"),
+        FileType::Overpassql => Some("OverpassQL"),
+        FileType::Ox => Some("Ox"),
+        FileType::Oxygene => Some("Oxygene"),
+        FileType::Oz => Some("Oz"),
+        FileType::P4 => Some("P4"),
+        FileType::Pact => Some("Pact"),
+        FileType::Pan => Some("Pan"),
+        FileType::Pandoc => Some("Pure Data"),
+        FileType::Papyrus => Some("Papyrus"),
+        FileType::Parrot => Some("Parrot"),
+        FileType::Pascal => Some("Pascal"),
+        FileType::Pasm => Some("Parrot Assembly"),
+        FileType::Pawn => Some("Pawn"),
+        FileType::Pbtxt => Some("Protocol Buffer Text Format"),
+        FileType::Pcbnew => Some("KiCad Layout"),
+        FileType::Pccts => Some("G-code"),
+        FileType::Pddl => Some("PDDL"),
+        FileType::PegJs => Some("PEG.js"),
+        FileType::Pep8 => Some("Pep8"),
+        FileType::Perl => Some("Perl"),
+        FileType::Php => Some("PHP"),
+        FileType::Piglatin => Some("PigLatin"),
+        FileType::Pikchr => Some("Pic"),
+        FileType::Pike => Some("Pike"),
+        FileType::Pir => Some("Parrot Internal Representation"),
+        FileType::Pkl => Some("Pkl"),
+        FileType::Plantuml => Some("PlantUML"),
+        FileType::Plpgsql => Some("PLpgSQL"),
+        FileType::Plsql => Some("PLSQL"),
+        FileType::Po => Some("Gettext Catalog"),
+        FileType::Pod => Some("Pod"),
+        FileType::Pogoscript => Some("PogoScript"),
+        FileType::Polar => Some("Polar"),
+        FileType::Pony => Some("Pony"),
+        FileType::Portugol => Some("Portugol"),
+        FileType::Postcss => Some("PostCSS"),
+        FileType::Postscr => Some("Adobe Font Metrics"),
+        FileType::Pov => Some("POV-Ray SDL"),
+        FileType::Powerbuilder => Some("PowerBuilder"),
+        FileType::Praat => Some("Praat"),
+        FileType::Prisma => Some("Prisma"),
+        FileType::Privoxy => Some("ROS Interface"),
+        FileType::Procfile => Some("Procfile"),
+        FileType::Progress => Some("OpenEdge ABL"),
+        FileType::Proguard => Some("Proguard"),
+        FileType::Prolog => Some("Prolog"),
+        FileType::Promela => Some("Promela"),
+        FileType::PropellerSpin => Some("Propeller Spin"),
+        FileType::Proto => Some("Protocol Buffer"),
+        FileType::Ps1 => Some("PowerShell"),
+        FileType::PublicKey => Some("Public Key"),
+        FileType::Pug => Some("Pug"),
+        FileType::Puppet => Some("Puppet"),
+        FileType::Purebasic => Some("PureBasic"),
+        FileType::Purescript => Some("PureScript"),
+        FileType::Pycon => Some("Python console"),
+        FileType::Pyret => Some("Pyret"),
+        FileType::Pyrex => Some("Cython"),
+        FileType::Python => Some("Python"),
+        FileType::PythonTraceback => Some("Python traceback"),
+        FileType::Q => Some("q"),
+        FileType::Qb64 => Some("QuickBASIC"),
+        FileType::Ql => Some("CodeQL"),
+        FileType::Qmake => Some("QMake"),
+        FileType::Qmljs => Some("QML"),
+        FileType::QtScript => Some("Qt Script"),
+        FileType::Quake => Some("Quake"),
+        FileType::Quarto => Some("RMarkdown"),
+        FileType::R => Some("R"),
+        FileType::Racket => Some("Racket"),
+        FileType::Radiance => Some("Unity3D Asset"),
+        FileType::RagelRb => Some("Ragel"),
+        FileType::Raku => Some("Raku"),
+        FileType::Raml => Some("RAML"),
+        FileType::Rascript => Some("RAScript"),
+        FileType::Raw => Some("Raw token data"),
+        FileType::Rbs => Some("RBS"),
+        FileType::Rdoc => Some("RDoc"),
+        FileType::ReScript => Some("ReScript"),
+        FileType::Readline => Some("Readline Config"),
+        FileType::Realbasic => Some("REALbasic"),
+        FileType::Reason => Some("Reason"),
+        FileType::Reasonligo => Some("ReasonLIGO"),
+        FileType::Rebol => Some("Rebol"),
+        FileType::RecordJar => Some("Record Jar"),
+        FileType::RedSystem => Some("Red"),
+        FileType::Redcode => Some("Redcode"),
+        FileType::Redirects => Some("Redirect Rules"),
+        FileType::Regex => Some("Regular Expression"),
+        FileType::Rego => Some("Open Policy Agent"),
+        FileType::Renpy => Some("Ren'Py"),
+        FileType::Requirements => Some("Pip Requirements"),
+        FileType::Rexx => Some("REXX"),
+        FileType::Rez => Some("Rez"),
+        FileType::Ring => Some("Ring"),
+        FileType::Riot => Some("Riot"),
+        FileType::Rnoweb => Some("Sweave"),
+        FileType::Robot => Some("RobotFramework"),
+        FileType::Robots => Some("robots.txt"),
+        FileType::Roc => Some("Roc"),
+        FileType::Ron => Some("RON"),
+        FileType::Rouge => Some("Rouge"),
+        FileType::RouterOs => Some("Rascal"),
+        FileType::Rpcgen => Some("DirectX 3D File"),
+        FileType::Rpgle => Some("RPGLE"),
+        FileType::Rst => Some("reStructuredText"),
+        FileType::Rtf => Some("Rich Text Format"),
+        FileType::Ruby => Some("Ruby"),
+        FileType::Runoff => Some("RUNOFF"),
+        FileType::Rust => Some("Rust"),
+        FileType::Sage => Some("Sage"),
+        FileType::Sail => Some("Sail"),
+        FileType::Salt => Some("SaltStack"),
+        FileType::Sas => Some("SAS"),
+        FileType::Sass => Some("Sass"),
+        FileType::Scala => Some("Scala"),
+        FileType::Scaml => Some("Scaml"),
+        FileType::Scenic => Some("Scenic"),
+        FileType::Scheme => Some("Scheme"),
+        FileType::Scilab => Some("Scilab"),
+        FileType::Scss => Some("SCSS"),
+        FileType::Sed => Some("sed"),
+        FileType::SelfLang => Some("Self"),
+        FileType::SelinuxKernelPolicyLanguage => Some("SELinux Policy"),
+        FileType::Sfv => Some("Simple File Verification"),
+        FileType::Sh => Some("Shell"),
+        FileType::Shellcheckrc => Some("ShellCheck Config"),
+        FileType::Shen => Some("Shen"),
+        FileType::Sieve => Some("Sieve"),
+        FileType::Singularity => Some("Singularity"),
+        FileType::Slang => Some("Slang"),
+        FileType::Slice => Some("Slice"),
+        FileType::Slim => Some("Slim"),
+        FileType::Slint => Some("Slint"),
+        FileType::Smali => Some("Smali"),
+        FileType::Smarty => Some("Smarty"),
+        FileType::Smith => Some("SMT"),
+        FileType::Smithy => Some("Smithy"),
+        FileType::Sml => Some("Standard ML"),
+        FileType::Snakemake => Some("Snakemake"),
+        FileType::Snipmate => Some("Vim Snippet"),
+        FileType::Snippet => Some("YASnippet"),
+        FileType::Solidity => Some("Solidity"),
+        FileType::Solution => Some("Microsoft Visual Studio Solution"),
+        FileType::Soong => Some("Soong"),
+        FileType::Sourcepawn => Some("SourcePawn"),
+        FileType::Soy => Some("Closure Templates"),
+        FileType::Sparql => Some("SPARQL"),
+        FileType::Spec => Some("RPM Spec"),
+        FileType::SplineFontDatabase => Some("Spline Font Database"),
+        FileType::Sqf => Some("SQF"),
+        FileType::Sql => Some("SQL"),
+        FileType::Sqlpl => Some("SQLPL"),
+        FileType::Squirrel => Some("Squirrel"),
+        FileType::Srt => Some("SRecode Template"),
+        FileType::SshConfig => Some("SSH Config"),
+        FileType::St => Some("Smalltalk"),
+        FileType::Stan => Some("Stan"),
+        FileType::Starlark => Some("Starlark"),
+        FileType::Stata => Some("Stata"),
+        FileType::Ston => Some("STON"),
+        FileType::Stylus => Some("Stylus"),
+        FileType::Sugarss => Some("SugarSS"),
+        FileType::Supercollider => Some("SuperCollider"),
+        FileType::Surql => Some("SurrealQL"),
+        FileType::SurvexData => Some("Survex data"),
+        FileType::Svelte => Some("Svelte"),
+        FileType::Svg => Some("SVG"),
+        FileType::Sway => Some("Sway"),
+        FileType::Swift => Some("Swift"),
+        FileType::Swig => Some("SWIG"),
+        FileType::SystemVerilog => Some("SystemVerilog"),
+        FileType::Tact => Some("Tact"),
+        FileType::Talon => Some("Talon"),
+        FileType::Tcl => Some("Tcl"),
+        FileType::Tcsh => Some("Tcsh"),
+        FileType::Tea => Some("Tea"),
+        FileType::Teal => Some("Teal"),
+        FileType::Templ => Some("templ"),
+        FileType::Template => Some("Go Template"),
+        FileType::Terra => Some("Terra"),
+        FileType::TerraformTemplate => Some("Terraform Template"),
+        FileType::Tex => Some("TeX"),
+        FileType::TexInfo => Some("Texinfo"),
+        FileType::Text => Some("Text"),
+        FileType::Textgrid => Some("TextGrid"),
+        FileType::Textile => Some("Textile"),
+        FileType::Thrift => Some("Thrift"),
+        FileType::TiProgram => Some("TI Program"),
+        FileType::TlVerilog => Some("TL-Verilog"),
+        FileType::Tla => Some("TLA"),
+        FileType::TmProperties => Some("TextMate Properties"),
+        FileType::Toit => Some("Toit"),
+        FileType::Toml => Some("TOML"),
+        FileType::Torrc => Some("Tor Config"),
+        FileType::Tsql => Some("TSQL"),
+        FileType::Tsv => Some("TSV"),
+        FileType::Tsx => Some("TSX"),
+        FileType::Turing => Some("Turing"),
+        FileType::Turtle => Some("Turtle"),
+        FileType::Twig => Some("Twig"),
+        FileType::Txl => Some("TXL"),
+        FileType::TypeScript => Some("TypeScript"),
+        FileType::TypeScriptGlimmer => Some("Gerber Image"),
+        FileType::Typespec => Some("TypeSpec"),
+        FileType::Typst => Some("Typst"),
+        FileType::Uc => Some("UnrealScript"),
+        FileType::UnifiedParallelC => Some("Unified Parallel C"),
+        FileType::Uno => Some("Uno"),
+        FileType::UntypedPlutusCore => Some("Untyped Plutus Core"),
+        FileType::UrWeb => Some("UrWeb"),
+        FileType::V => Some("V"),
+        FileType::Vala => Some("Vala"),
+        FileType::Vb => Some("VBScript"),
+        FileType::Vcl => Some("VCL"),
+        FileType::Vdf => Some("Valve Data Format"),
+        FileType::Vento => Some("Vento"),
+        FileType::Vhdl => Some("VHDL"),
+        FileType::Vim => Some("VBA"),
+        FileType::VimHelp => Some("Vim Help File"),
+        FileType::VirtualContactFile => Some("vCard"),
+        FileType::Volt => Some("Volt"),
+        FileType::Vtl => Some("Velocity Template Language"),
+        FileType::Vtt => Some("WebVTT"),
+        FileType::Vue => Some("Vue"),
+        FileType::Vyper => Some("Vyper"),
+        FileType::Wast => Some("WebAssembly"),
+        FileType::WavefrontMaterial => Some("Wavefront Material"),
+        FileType::Wdl => Some("WDL"),
+        FileType::WebOntologyLanguage => Some("Web Ontology Language"),
+        FileType::Webidl => Some("WebIDL"),
+        FileType::Wget => Some("Wget Config"),
+        FileType::Wgsl => Some("WGSL"),
+        FileType::Whiley => Some("Whiley"),
+        FileType::Win32MessageFile => Some("Win32 Message File"),
+        FileType::WindowsRegistryEntries => Some("Windows Registry Entries"),
+        FileType::Wisp => Some("wisp"),
+        FileType::Wit => Some("WebAssembly Interface Type"),
+        FileType::WitcherScript => Some("Witcher Script"),
+        FileType::Wollok => Some("Wollok"),
+        FileType::Wrenlang => Some("Wren"),
+        FileType::XFontDirectoryIndex => Some("X Font Directory Index"),
+        FileType::XQuery => Some("XQuery"),
+        FileType::Xbm => Some("X BitMap"),
+        FileType::Xc => Some("XC"),
+        FileType::Xcompose => Some("XCompose"),
+        FileType::Xmake => Some("Xmake"),
+        FileType::Xml => Some("XML"),
+        FileType::XmlGenshi => Some("Genshi"),
+        FileType::XmlPropertyList => Some("XML Property List"),
+        FileType::Xojo => Some("Xojo"),
+        FileType::Xonsh => Some("Xonsh"),
+        FileType::Xpages => Some("XPages"),
+        FileType::Xpm => Some("X PixMap"),
+        FileType::Xproc => Some("XProc"),
+        FileType::Xs => Some("XS"),
+        FileType::Xslt => Some("XSLT"),
+        FileType::Xten => Some("X10"),
+        FileType::Xtend => Some("Xtend"),
+        FileType::Yacc => Some("Yacc"),
+        FileType::Yaml => Some("YAML"),
+        FileType::Yang => Some("YANG"),
+        FileType::Yara => Some("YARA"),
+        FileType::Yul => Some("Yul"),
+        FileType::Zap => Some("ZAP"),
+        FileType::Zephir => Some("Zephir"),
+        FileType::Zig => Some("Zig"),
+        FileType::Zil => Some("ZIL"),
+        FileType::Zimpl => Some("Zimpl"),
+        FileType::Zmodel => Some("Zmodel"),
+        FileType::Zserio => Some("ZenScript"),
+        _ => None,
+    }
+}
+
+const fn linguist_color(file_type: FileType) -> Option<&'static str> {
+    match file_type {
+        FileType::Abap => Some("#E8274B"),
+        FileType::AbapCds => Some("#555e25"),
+        FileType::Actionscript => Some("#882B0F"),
+        FileType::AdBlockFilters => Some("#800000"),
+        FileType::Ada => Some("#02f88c"),
+        FileType::Agda => Some("#315665"),
+        FileType::Ags => Some("#B9D9FF"),
+        FileType::Aidl => Some("#34EB6B"),
+        FileType::Aiken => Some("#640ff8"),
+        FileType::Algol => Some("#D1E0DB"),
+        FileType::Alloy => Some("#64C800"),
+        FileType::Altium => Some("#A89663"),
+        FileType::Ampl => Some("#E6EFBB"),
+        FileType::AnswerSetProgramming => Some("#A9CC29"),
+        FileType::Ant => Some("#A9157E"),
+        FileType::Antlers => Some("#ff269e"),
+        FileType::Antlr4 => Some("#9DC3FF"),
+        FileType::Apache => Some("#d12127"),
+        FileType::Apex => Some("#1797c0"),
+        FileType::ApiBlueprint => Some("#2ACCA8"),
+        FileType::Apkbuild => Some("#0D597F"),
+        FileType::Apl => Some("#5A8164"),
+        FileType::ApolloGuidanceComputer => Some("#0B3D91"),
+        FileType::AppleScript => Some("#101F1F"),
+        FileType::Arc => Some("#aa2afe"),
+        FileType::Arduino => Some("#0096D8"),
+        FileType::AsciiDoc => Some("#73a0c5"),
+        FileType::AsciiStl => Some("#373b5e"),
+        FileType::Asp => Some("#6a40fd"),
+        FileType::Aspectj => Some("#a957b0"),
+        FileType::Aspx => Some("#9400ff"),
+        FileType::Astro => Some("#ff5a03"),
+        FileType::Asy => Some("#ff0000"),
+        FileType::Atlas => Some("#C7D7DC"),
+        FileType::Ats2 => Some("#1ac620"),
+        FileType::Augeas => Some("#9CC134"),
+        FileType::AutoHotKey => Some("#6594b9"),
+        FileType::AutoIt => Some("#1C3552"),
+        FileType::AvroIdl => Some("#0040FF"),
+        FileType::Awk => Some("#c30e9b"),
+        FileType::B => Some("#8aa8c5"),
+        FileType::Ballerina => Some("#FF5000"),
+        FileType::Basic => Some("#ff0000"),
+        FileType::BasicForAndroid => Some("#00e4ff"),
+        FileType::Be => Some("#15A13C"),
+        FileType::Beef => Some("#a52f4e"),
+        FileType::Bib => Some("#778899"),
+        FileType::Bicep => Some("#519aba"),
+        FileType::Bison => Some("#6A463F"),
+        FileType::Bitbake => Some("#00bce4"),
+        FileType::Blade => Some("#f7523f"),
+        FileType::BluespecBsv => Some("#12223c"),
+        FileType::Bmax => Some("#cd6400"),
+        FileType::Boo => Some("#d4bec1"),
+        FileType::Boogie => Some("#c80fa0"),
+        FileType::Bqn => Some("#2b7067"),
+        FileType::Brainfuck => Some("#2F2530"),
+        FileType::Brighterscript => Some("#66AABB"),
+        FileType::Brightscript => Some("#662D91"),
+        FileType::Browserslist => Some("#ffd539"),
+        FileType::Bru => Some("#F4AA41"),
+        FileType::Byond => Some("#447265"),
+        FileType::C => Some("#555555"),
+        FileType::C3 => Some("#2563eb"),
+        FileType::CMake => Some("#DA3434"),
+        FileType::CSharp => Some("#178600"),
+        FileType::Cabal => Some("#483465"),
+        FileType::Caddy => Some("#22b638"),
+        FileType::Cairo => Some("#ff4a48"),
+        FileType::Cameligo => Some("#3be133"),
+        FileType::Cangjie => Some("#00868B"),
+        FileType::Capnp => Some("#c42727"),
+        FileType::Carbon => Some("#222222"),
+        FileType::Cdc => Some("#00ef8b"),
+        FileType::Cdrtoc => Some("#f7e43f"),
+        FileType::Cds => Some("#0092d1"),
+        FileType::Ceylon => Some("#dfa535"),
+        FileType::Cf => Some("#ed2cd6"),
+        FileType::Chpl => Some("#8dc63f"),
+        FileType::Chuck => Some("#3f8000"),
+        FileType::Circom => Some("#707575"),
+        FileType::Cirru => Some("#ccccff"),
+        FileType::Clarion => Some("#db901e"),
+        FileType::Clarity => Some("#5546ff"),
+        FileType::Clean => Some("#3F85AF"),
+        FileType::Click => Some("#E4E6F3"),
+        FileType::Clipper => Some("#403a40"),
+        FileType::Clojure => Some("#db5855"),
+        FileType::CloudFirestoreSecurityRules => Some("#FFA000"),
+        FileType::Clue => Some("#0009b5"),
+        FileType::Coccinelle => Some("#c94949"),
+        FileType::Collada => Some("#F1A42B"),
+        FileType::ComponentPascal => Some("#B0CE4E"),
+        FileType::ConfIni => Some("#d1dbe0"),
+        FileType::Cook => Some("#E15A29"),
+        FileType::Coq => Some("#d0b68c"),
+        FileType::Cpp => Some("#f34b7d"),
+        FileType::Crontab => Some("#ead7ac"),
+        FileType::Crystal => Some("#000100"),
+        FileType::Csc => Some("#FF6800"),
+        FileType::Cson => Some("#244776"),
+        FileType::CsoundCsd => Some("#1a1a1a"),
+        FileType::CsoundOrc => Some("#1a1a1a"),
+        FileType::CsoundSco => Some("#1a1a1a"),
+        FileType::Css => Some("#663399"),
+        FileType::Csv => Some("#237346"),
+        FileType::Cucumber => Some("#5B2063"),
+        FileType::Cuda => Some("#3A4E3A"),
+        FileType::Cue => Some("#5886E1"),
+        FileType::Curry => Some("#531242"),
+        FileType::Cweb => Some("#00007a"),
+        FileType::Cwl => Some("#B5314C"),
+        FileType::Cylc => Some("#00b3fd"),
+        FileType::Cypher => Some("#34c0eb"),
+        FileType::D => Some("#ba595e"),
+        FileType::D2lang => Some("#526ee8"),
+        FileType::Dafny => Some("#FFEC25"),
+        FileType::Dart => Some("#00B4AB"),
+        FileType::Daslang => Some("#d3d3d3"),
+        FileType::Dataweave => Some("#003a52"),
+        FileType::DebianPackageControlFile => Some("#D70751"),
+        FileType::Denizenscript => Some("#FBEE96"),
+        FileType::Dhall => Some("#dfafff"),
+        FileType::Dockerfile => Some("#384d54"),
+        FileType::Dogescript => Some("#cca760"),
+        FileType::DosBatch => Some("#C1F12E"),
+        FileType::DosIni => Some("#cb3837"),
+        FileType::Dot => Some("#2596be"),
+        FileType::Dotenv => Some("#e5d559"),
+        FileType::Dpatch => Some("#8eff23"),
+        FileType::Dune => Some("#89421e"),
+        FileType::Dylan => Some("#6c616e"),
+        FileType::E => Some("#ccce35"),
+        FileType::EJavaScript => Some("#a91e50"),
+        FileType::ERuby => Some("#701516"),
+        FileType::Earthfile => Some("#2af0ff"),
+        FileType::Easybuild => Some("#069406"),
+        FileType::EcereProjects => Some("#913960"),
+        FileType::Ecl => Some("#8a1267"),
+        FileType::Eclipse => Some("#001d9d"),
+        FileType::Ecmarkdown => Some("#eb8131"),
+        FileType::Ecr => Some("#2e1052"),
+        FileType::Edge => Some("#0dffe0"),
+        FileType::EditorConfig => Some("#fff1f2"),
+        FileType::EeschemaSchematic => Some("#2f4aab"),
+        FileType::Eiffel => Some("#4d6977"),
+        FileType::Elixir => Some("#6e4a7e"),
+        FileType::Elm => Some("#60B5CC"),
+        FileType::Elvish => Some("#55BB55"),
+        FileType::ElvishTranscript => Some("#55BB55"),
+        FileType::Emberscript => Some("#FFF4F3"),
+        FileType::Eq => Some("#a78649"),
+        FileType::Erlang => Some("#B83998"),
+        FileType::Esdl => Some("#31A7FF"),
+        FileType::Esqlc => Some("#913960"),
+        FileType::Euphoria => Some("#FF790B"),
+        FileType::FSharp => Some("#b845fc"),
+        FileType::Factor => Some("#636746"),
+        FileType::Fan => Some("#14253c"),
+        FileType::Fancy => Some("#7b9db4"),
+        FileType::Faust => Some("#c37240"),
+        FileType::Fennel => Some("#fff3d7"),
+        FileType::Fgl => Some("#63408e"),
+        FileType::Figfont => Some("#FFDDBB"),
+        FileType::Firrtl => Some("#2f632f"),
+        FileType::Fish => Some("#4aae47"),
+        FileType::Flix => Some("#d44a45"),
+        FileType::Fluent => Some("#ffcc33"),
+        FileType::Forth => Some("#341708"),
+        FileType::Fortran => Some("#4d41b1"),
+        FileType::FreeBasic => Some("#141AC9"),
+        FileType::Frege => Some("#00cafe"),
+        FileType::Fstar => Some("#572e30"),
+        FileType::Ft1cEnterprise => Some("#814CCC"),
+        FileType::Ft2DimensionalArray => Some("#38761D"),
+        FileType::Ft4d => Some("#004289"),
+        FileType::Futhark => Some("#5f021f"),
+        FileType::GameMakerLanguage => Some("#71b417"),
+        FileType::Gaml => Some("#FFC766"),
+        FileType::Gams => Some("#f49a22"),
+        FileType::Gap => Some("#0000cc"),
+        FileType::GdResource => Some("#355570"),
+        FileType::GdScript => Some("#355570"),
+        FileType::GdShader => Some("#478CBF"),
+        FileType::Gdmo => Some("#de1d31"),
+        FileType::Gedcom => Some("#003058"),
+        FileType::GemText => Some("#ff6900"),
+        FileType::GemfileLock => Some("#701516"),
+        FileType::GeneroPer => Some("#d8df39"),
+        FileType::GentooEbuild => Some("#9400ff"),
+        FileType::GentooEclass => Some("#9400ff"),
+        FileType::Gf => Some("#ff0000"),
+        FileType::GitAttributes => Some("#F44D27"),
+        FileType::GitBlameIgnoreRevs => Some("#F44D27"),
+        FileType::GitCommit => Some("#F44D27"),
+        FileType::GitConfig => Some("#F44D27"),
+        FileType::GitIgnore => Some("#000000"),
+        FileType::Gleam => Some("#ffaff3"),
+        FileType::Glsl => Some("#5686a5"),
+        FileType::Glyph => Some("#c1ac7f"),
+        FileType::GnuPlot => Some("#f0a9f0"),
+        FileType::Go => Some("#00ADD8"),
+        FileType::GoMod => Some("#00ADD8"),
+        FileType::GoSum => Some("#00ADD8"),
+        FileType::GoWork => Some("#00ADD8"),
+        FileType::Golo => Some("#88562A"),
+        FileType::Grace => Some("#615f8b"),
+        FileType::GradleKotlinDsl => Some("#02303a"),
+        FileType::Grads => Some("#fb855d"),
+        FileType::GraphQl => Some("#e10098"),
+        FileType::Groovy => Some("#4298b8"),
+        FileType::Gsp => Some("#4298b8"),
+        FileType::Hack => Some("#878787"),
+        FileType::Haml => Some("#ece2a9"),
+        FileType::Handlebars => Some("#f7931e"),
+        FileType::Haproxy => Some("#106da9"),
+        FileType::Hare => Some("#9d7424"),
+        FileType::Haskell => Some("#5e5086"),
+        FileType::Haxe => Some("#df7900"),
+        FileType::Hb => Some("#0e60e3"),
+        FileType::Hcl => Some("#844FBA"),
+        FileType::Heex => Some("#6e4a7e"),
+        FileType::Hip => Some("#4F3A4F"),
+        FileType::Hiveql => Some("#dce200"),
+        FileType::HlsPlaylist => Some("#179C7D"),
+        FileType::Hlsl => Some("#aace60"),
+        FileType::Hocon => Some("#9ff8ee"),
+        FileType::Holyc => Some("#ffefaf"),
+        FileType::Hoon => Some("#00b171"),
+        FileType::Hosts => Some("#308888"),
+        FileType::Html => Some("#e34c26"),
+        FileType::Http => Some("#005C9C"),
+        FileType::Hurl => Some("#FF0288"),
+        FileType::Hxml => Some("#f68712"),
+        FileType::Hy => Some("#7790B2"),
+        FileType::Ical => Some("#ec564c"),
+        FileType::Idl => Some("#a3522f"),
+        FileType::Idris => Some("#b30000"),
+        FileType::Igor => Some("#0000cc"),
+        FileType::Ijm => Some("#99AAFF"),
+        FileType::Imba => Some("#16cec6"),
+        FileType::Io => Some("#a9188d"),
+        FileType::Ioke => Some("#078193"),
+        FileType::Isabelle => Some("#FEFE00"),
+        FileType::IsabelleRoot => Some("#FEFE00"),
+        FileType::Ispc => Some("#2D68B1"),
+        FileType::Iss => Some("#264b99"),
+        FileType::J => Some("#9EEDFF"),
+        FileType::JProperties => Some("#2A6277"),
+        FileType::Jac => Some("#FC792D"),
+        FileType::Jai => Some("#ab8b4b"),
+        FileType::JanetSimple => Some("#0886a5"),
+        FileType::JarManifest => Some("#b07219"),
+        FileType::Jasmin => Some("#d03600"),
+        FileType::Java => Some("#b07219"),
+        FileType::JavaScript => Some("#f1e05a"),
+        FileType::JavaScriptGlimmer => Some("#F5835F"),
+        FileType::JavascriptErb => Some("#f1e05a"),
+        FileType::Jcl => Some("#d90e09"),
+        FileType::Jess => Some("#00A300"),
+        FileType::JestSnapshot => Some("#15c213"),
+        FileType::Jflex => Some("#DBCA00"),
+        FileType::Jinja => Some("#a52a22"),
+        FileType::Jison => Some("#56b3cb"),
+        FileType::JisonLex => Some("#56b3cb"),
+        FileType::Jolie => Some("#843179"),
+        FileType::Jq => Some("#c7254e"),
+        FileType::Json => Some("#292929"),
+        FileType::Json5 => Some("#267CB9"),
+        FileType::JsonC => Some("#292929"),
+        FileType::Jsonld => Some("#0c479c"),
+        FileType::Jsonnet => Some("#0064bd"),
+        FileType::Jsp => Some("#2A6277"),
+        FileType::Jte => Some("#2A6277"),
+        FileType::Julia => Some("#a270ba"),
+        FileType::JuliaRepl => Some("#a270ba"),
+        FileType::Just => Some("#384d54"),
+        FileType::KScript => Some("#41adf0"),
+        FileType::Kak => Some("#6f8042"),
+        FileType::Kcl => Some("#7ABABF"),
+        FileType::Kdl => Some("#ffb3b3"),
+        FileType::KicadLegacyLayout => Some("#2f4aab"),
+        FileType::Kivy => Some("#1da6e0"),
+        FileType::Koka => Some("#215166"),
+        FileType::KolmafiaAsh => Some("#B9D9B9"),
+        FileType::Kotlin => Some("#A97BFF"),
+        FileType::Krl => Some("#28430A"),
+        FileType::Ksy => Some("#773b37"),
+        FileType::Kwt => Some("#4195c5"),
+        FileType::LHaskell => Some("#5e5086"),
+        FileType::Labview => Some("#fede06"),
+        FileType::Lambdapi => Some("#8027a3"),
+        FileType::Langium => Some("#2c8c87"),
+        FileType::Lark => Some("#2980B9"),
+        FileType::Lassoscript => Some("#999999"),
+        FileType::Latte => Some("#f2a542"),
+        FileType::Leo => Some("#C4FFC2"),
+        FileType::Less => Some("#1d365d"),
+        FileType::Lex => Some("#DBCA00"),
+        FileType::Lfe => Some("#4C3023"),
+        FileType::Ligolang => Some("#0e74ff"),
+        FileType::Lilypond => Some("#9ccc7c"),
+        FileType::Liquid => Some("#67b8de"),
+        FileType::Lisp => Some("#3fb68b"),
+        FileType::Litcoffee => Some("#244776"),
+        FileType::LiterateAgda => Some("#315665"),
+        FileType::LiveScript => Some("#499886"),
+        FileType::LivecodeScript => Some("#0c5ba5"),
+        FileType::Llvm => Some("#185619"),
+        FileType::Logtalk => Some("#295b9a"),
+        FileType::Lolcode => Some("#cc9900"),
+        FileType::Lookml => Some("#652B81"),
+        FileType::Lsl => Some("#3d9970"),
+        FileType::Lua => Some("#000080"),
+        FileType::Luau => Some("#00A2FF"),
+        FileType::M68k => Some("#005daa"),
+        FileType::Make => Some("#427819"),
+        FileType::Mako => Some("#7e858d"),
+        FileType::Maple => Some("#21D789"),
+        FileType::Markdown => Some("#083fa1"),
+        FileType::Markojs => Some("#42bff2"),
+        FileType::Mask => Some("#f97732"),
+        FileType::Matlab => Some("#e16737"),
+        FileType::MaxMsp => Some("#c4a79c"),
+        FileType::Maxscript => Some("#00a6a6"),
+        FileType::Mcfunction => Some("#E22837"),
+        FileType::Mdsvex => Some("#5f9ea0"),
+        FileType::Mdx => Some("#fcb32c"),
+        FileType::Mediawiki => Some("#fc5757"),
+        FileType::Mermaid => Some("#ff3670"),
+        FileType::Meson => Some("#007800"),
+        FileType::Metal => Some("#8f14e9"),
+        FileType::Minizinc => Some("#06a9e6"),
+        FileType::Mint => Some("#02b046"),
+        FileType::Mirah => Some("#c7a938"),
+        FileType::MircScript => Some("#3d57c3"),
+        FileType::Mlir => Some("#5EC8DB"),
+        FileType::Mma => Some("#dd1100"),
+        FileType::Modula2 => Some("#d8ffff"),
+        FileType::Modula3 => Some("#223388"),
+        FileType::Mojo => Some("#ff4c1f"),
+        FileType::MonkeyC => Some("#8D6747"),
+        FileType::Moo => Some("#ff2b2b"),
+        FileType::MoonScript => Some("#ff4585"),
+        FileType::Moonbit => Some("#b92381"),
+        FileType::Move => Some("#4a137a"),
+        FileType::Mql4 => Some("#62A8D6"),
+        FileType::Mql5 => Some("#4A76B8"),
+        FileType::Mtml => Some("#b7e1f4"),
+        FileType::Mupad => Some("#244963"),
+        FileType::Mustache => Some("#724b3b"),
+        FileType::Nanorc => Some("#2d004d"),
+        FileType::Nasal => Some("#1d2c4e"),
+        FileType::Nasm => Some("#6E4C13"),
+        FileType::Nearley => Some("#990000"),
+        FileType::Nemerle => Some("#3d3c6e"),
+        FileType::Nesc => Some("#94B0C7"),
+        FileType::Netlinx => Some("#0aa0ff"),
+        FileType::NetlinxErb => Some("#747faa"),
+        FileType::Netlogo => Some("#ff6375"),
+        FileType::Nextflow => Some("#3ac486"),
+        FileType::Nginx => Some("#009639"),
+        FileType::Nickel => Some("#E0C3FC"),
+        FileType::Nim => Some("#ffc200"),
+        FileType::Nit => Some("#009917"),
+        FileType::Nix => Some("#7e7eff"),
+        FileType::Njk => Some("#3d8137"),
+        FileType::Nmodl => Some("#00356B"),
+        FileType::Nroff => Some("#2f1f49"),
+        FileType::Nu => Some("#c9df40"),
+        FileType::Numpy => Some("#9C8AF9"),
+        FileType::Nwscript => Some("#111522"),
+        FileType::OCaml => Some("#ef7a08"),
+        FileType::Oasv2 => Some("#85ea2d"),
+        FileType::Oasv3 => Some("#85ea2d"),
+        FileType::ObjC => Some("#438eff"),
+        FileType::ObjCpp => Some("#6866fb"),
+        FileType::ObjJ => Some("#ff0c5a"),
+        FileType::Objectscript => Some("#424893"),
+        FileType::Odin => Some("#60AFFE"),
+        FileType::Omgrofl => Some("#cabbff"),
+        FileType::OmnetppMsg => Some("#a0e0a0"),
+        FileType::OmnetppNed => Some("#08607c"),
+        FileType::Ooc => Some("#b0b77e"),
+        FileType::Opal => Some("#f7ede0"),
+        FileType::OpenScad => Some("#e5cd45"),
+        FileType::Openqasm => Some("#AA70FF"),
+        FileType::Opts => Some("#476732"),
+        FileType::Org => Some("#77aa99"),
+        FileType::Overpassql => Some("#cce2aa"),
+        FileType::Oxygene => Some("#cdd0e3"),
+        FileType::Oz => Some("#fab738"),
+        FileType::P4 => Some("#7055b5"),
+        FileType::Pact => Some("#F7A8B8"),
+        FileType::Pan => Some("#cc0000"),
+        FileType::Papyrus => Some("#6600cc"),
+        FileType::Parrot => Some("#f3ca0a"),
+        FileType::Pascal => Some("#E3F171"),
+        FileType::Pawn => Some("#dbb284"),
+        FileType::Pcbnew => Some("#2f4aab"),
+        FileType::Pccts => Some("#D08CF2"),
+        FileType::Pddl => Some("#0d00ff"),
+        FileType::PegJs => Some("#234d6b"),
+        FileType::Pep8 => Some("#C76F5B"),
+        FileType::Perl => Some("#0298c3"),
+        FileType::Php => Some("#4F5D95"),
+        FileType::Piglatin => Some("#fcd7de"),
+        FileType::Pike => Some("#005390"),
+        FileType::Pkl => Some("#6b9543"),
+        FileType::Plantuml => Some("#fbbd16"),
+        FileType::Plpgsql => Some("#336790"),
+        FileType::Plsql => Some("#dad8d8"),
+        FileType::Pogoscript => Some("#d80074"),
+        FileType::Polar => Some("#ae81ff"),
+        FileType::Portugol => Some("#f8bd00"),
+        FileType::Postcss => Some("#dc3a0c"),
+        FileType::Postscr => Some("#fa0f00"),
+        FileType::Pov => Some("#6bac65"),
+        FileType::Powerbuilder => Some("#8f0f8d"),
+        FileType::Praat => Some("#c8506d"),
+        FileType::Prisma => Some("#0c344b"),
+        FileType::Privoxy => Some("#22314e"),
+        FileType::Procfile => Some("#3B2F63"),
+        FileType::Progress => Some("#5ce600"),
+        FileType::Prolog => Some("#74283c"),
+        FileType::Promela => Some("#de0000"),
+        FileType::PropellerSpin => Some("#7fa2a7"),
+        FileType::Ps1 => Some("#012456"),
+        FileType::Pug => Some("#a86454"),
+        FileType::Puppet => Some("#302B6D"),
+        FileType::Purebasic => Some("#5a6986"),
+        FileType::Purescript => Some("#1D222D"),
+        FileType::Pycon => Some("#3572A5"),
+        FileType::Pyret => Some("#ee1e10"),
+        FileType::Pyrex => Some("#fedf5b"),
+        FileType::Python => Some("#3572A5"),
+        FileType::PythonTraceback => Some("#3572A5"),
+        FileType::Q => Some("#0040cd"),
+        FileType::Qb64 => Some("#008080"),
+        FileType::Ql => Some("#140f46"),
+        FileType::Qmljs => Some("#44a51c"),
+        FileType::QtScript => Some("#00b841"),
+        FileType::Quake => Some("#882233"),
+        FileType::Quarto => Some("#198ce7"),
+        FileType::R => Some("#198CE7"),
+        FileType::Racket => Some("#3c5caa"),
+        FileType::Radiance => Some("#222c37"),
+        FileType::RagelRb => Some("#9d5200"),
+        FileType::Raku => Some("#0000fb"),
+        FileType::Raml => Some("#77d9fb"),
+        FileType::Rascript => Some("#2C97FA"),
+        FileType::Rbs => Some("#701516"),
+        FileType::Rdoc => Some("#701516"),
+        FileType::ReScript => Some("#ed5051"),
+        FileType::Reason => Some("#ff5847"),
+        FileType::Reasonligo => Some("#ff5847"),
+        FileType::Rebol => Some("#358a5b"),
+        FileType::RecordJar => Some("#0673ba"),
+        FileType::RedSystem => Some("#f50000"),
+        FileType::Regex => Some("#009a00"),
+        FileType::Rego => Some("#7d9199"),
+        FileType::Renpy => Some("#ff7f7f"),
+        FileType::Requirements => Some("#FFD343"),
+        FileType::Rexx => Some("#d90e09"),
+        FileType::Rez => Some("#FFDAB3"),
+        FileType::Ring => Some("#2D54CB"),
+        FileType::Riot => Some("#A71E49"),
+        FileType::Rnoweb => Some("#198ce7"),
+        FileType::Robot => Some("#00c0b5"),
+        FileType::Roc => Some("#7c38f5"),
+        FileType::Ron => Some("#a62c00"),
+        FileType::Rouge => Some("#cc0088"),
+        FileType::RouterOs => Some("#fffaa0"),
+        FileType::Rpcgen => Some("#aace60"),
+        FileType::Rpgle => Some("#2BDE21"),
+        FileType::Rst => Some("#141414"),
+        FileType::Ruby => Some("#701516"),
+        FileType::Runoff => Some("#665a4e"),
+        FileType::Rust => Some("#dea584"),
+        FileType::Sail => Some("#259dd5"),
+        FileType::Salt => Some("#646464"),
+        FileType::Sas => Some("#B34936"),
+        FileType::Sass => Some("#a53b70"),
+        FileType::Scala => Some("#c22d40"),
+        FileType::Scaml => Some("#bd181a"),
+        FileType::Scenic => Some("#fdc700"),
+        FileType::Scheme => Some("#1e4aec"),
+        FileType::Scilab => Some("#ca0f21"),
+        FileType::Scss => Some("#c6538c"),
+        FileType::Sed => Some("#64b970"),
+        FileType::SelfLang => Some("#0579aa"),
+        FileType::Sfv => Some("#C9BFED"),
+        FileType::Sh => Some("#89e051"),
+        FileType::Shellcheckrc => Some("#cecfcb"),
+        FileType::Shen => Some("#120F14"),
+        FileType::Singularity => Some("#64E6AD"),
+        FileType::Slang => Some("#1fbec9"),
+        FileType::Slice => Some("#003fa2"),
+        FileType::Slim => Some("#2b2b2b"),
+        FileType::Slint => Some("#2379F4"),
+        FileType::Smarty => Some("#f0c040"),
+        FileType::Smithy => Some("#c44536"),
+        FileType::Sml => Some("#dc566d"),
+        FileType::Snakemake => Some("#419179"),
+        FileType::Snipmate => Some("#199f4b"),
+        FileType::Snippet => Some("#32AB90"),
+        FileType::Solidity => Some("#AA6746"),
+        FileType::Sourcepawn => Some("#f69e1d"),
+        FileType::Soy => Some("#0d948f"),
+        FileType::Sparql => Some("#0C4597"),
+        FileType::Sqf => Some("#3F3F3F"),
+        FileType::Sql => Some("#e38c00"),
+        FileType::Sqlpl => Some("#e38c00"),
+        FileType::Squirrel => Some("#800000"),
+        FileType::Srt => Some("#348a34"),
+        FileType::St => Some("#596706"),
+        FileType::Stan => Some("#b2011d"),
+        FileType::Starlark => Some("#76d275"),
+        FileType::Stata => Some("#1a5f91"),
+        FileType::Stylus => Some("#ff6347"),
+        FileType::Sugarss => Some("#2fcc9f"),
+        FileType::Supercollider => Some("#46390b"),
+        FileType::Surql => Some("#ff00a0"),
+        FileType::SurvexData => Some("#ffcc99"),
+        FileType::Svelte => Some("#ff3e00"),
+        FileType::Svg => Some("#ff9900"),
+        FileType::Sway => Some("#00F58C"),
+        FileType::Swift => Some("#F05138"),
+        FileType::SystemVerilog => Some("#DAE1C2"),
+        FileType::Tact => Some("#48b5ff"),
+        FileType::Talon => Some("#333333"),
+        FileType::Tcl => Some("#e4cc98"),
+        FileType::Teal => Some("#00B1BC"),
+        FileType::Templ => Some("#66D0DD"),
+        FileType::Template => Some("#00ADD8"),
+        FileType::Terra => Some("#00004c"),
+        FileType::TerraformTemplate => Some("#7b42bb"),
+        FileType::Tex => Some("#3D6117"),
+        FileType::Textgrid => Some("#c8506d"),
+        FileType::Textile => Some("#ffe7ac"),
+        FileType::Thrift => Some("#D12127"),
+        FileType::TiProgram => Some("#A0AA87"),
+        FileType::TlVerilog => Some("#C40023"),
+        FileType::Tla => Some("#4b0079"),
+        FileType::TmProperties => Some("#df66e4"),
+        FileType::Toit => Some("#c2c9fb"),
+        FileType::Toml => Some("#9c4221"),
+        FileType::Torrc => Some("#59316b"),
+        FileType::Tsql => Some("#e38c00"),
+        FileType::Tsv => Some("#237346"),
+        FileType::Tsx => Some("#3178c6"),
+        FileType::Turing => Some("#cf142b"),
+        FileType::Twig => Some("#c1d026"),
+        FileType::Txl => Some("#0178b8"),
+        FileType::TypeScript => Some("#3178c6"),
+        FileType::TypeScriptGlimmer => Some("#d20b00"),
+        FileType::Typespec => Some("#4A3665"),
+        FileType::Typst => Some("#239dad"),
+        FileType::Uc => Some("#a54c4d"),
+        FileType::UnifiedParallelC => Some("#4e3617"),
+        FileType::Uno => Some("#9933cc"),
+        FileType::UntypedPlutusCore => Some("#36adbd"),
+        FileType::UrWeb => Some("#ccccee"),
+        FileType::V => Some("#4f87c4"),
+        FileType::Vala => Some("#a56de2"),
+        FileType::Vb => Some("#15dcdc"),
+        FileType::Vcl => Some("#148AA8"),
+        FileType::Vdf => Some("#f26025"),
+        FileType::Vento => Some("#ff0080"),
+        FileType::Vhdl => Some("#adb2cb"),
+        FileType::Vim => Some("#867db1"),
+        FileType::VimHelp => Some("#199f4b"),
+        FileType::VirtualContactFile => Some("#ee2647"),
+        FileType::Volt => Some("#1F1F1F"),
+        FileType::Vtl => Some("#507cff"),
+        FileType::Vue => Some("#41b883"),
+        FileType::Vyper => Some("#9F4CF2"),
+        FileType::Wast => Some("#04133b"),
+        FileType::Wdl => Some("#42f1f4"),
+        FileType::WebOntologyLanguage => Some("#5b70bd"),
+        FileType::Wgsl => Some("#1a5e9a"),
+        FileType::Whiley => Some("#d5c397"),
+        FileType::WindowsRegistryEntries => Some("#52d5ff"),
+        FileType::Wisp => Some("#7582D1"),
+        FileType::Wit => Some("#6250e7"),
+        FileType::WitcherScript => Some("#ff0000"),
+        FileType::Wollok => Some("#a23738"),
+        FileType::Wrenlang => Some("#383838"),
+        FileType::XQuery => Some("#5232e7"),
+        FileType::Xc => Some("#99DA07"),
+        FileType::Xmake => Some("#22a079"),
+        FileType::Xml => Some("#0060ac"),
+        FileType::XmlGenshi => Some("#951531"),
+        FileType::XmlPropertyList => Some("#0060ac"),
+        FileType::Xojo => Some("#81bd41"),
+        FileType::Xonsh => Some("#285EEF"),
+        FileType::Xslt => Some("#EB8CEB"),
+        FileType::Xten => Some("#4B6BEF"),
+        FileType::Xtend => Some("#24255d"),
+        FileType::Yacc => Some("#4B6C4B"),
+        FileType::Yaml => Some("#cb171e"),
+        FileType::Yara => Some("#220000"),
+        FileType::Yul => Some("#794932"),
+        FileType::Zap => Some("#0d665e"),
+        FileType::Zephir => Some("#118f9e"),
+        FileType::Zig => Some("#ec915c"),
+        FileType::Zil => Some("#dc75e5"),
+        FileType::Zimpl => Some("#d67711"),
+        FileType::Zmodel => Some("#ff7100"),
+        FileType::Zserio => Some("#00BCD1"),
+        _ => None,
+    }
+}
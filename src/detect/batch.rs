@@ -0,0 +1,83 @@
+//! Parallel detection of many files at once.
+
+use std::path::Path;
+
+use rayon::prelude::*;
+
+use super::{Detection, Scratch, detect_traced, explain::Trace};
+
+/// [`try_detect_with_stage`](crate::try_detect_with_stage) for every `(path, content)`
+/// pair of `items`, in parallel; the answers are in the order of `items`.
+///
+/// Each worker thread reuses one set of buffers for the lowercase file names and
+/// extensions the pipeline looks up, instead of allocating them for every item.
+///
+/// # Example
+/// ```
+/// use palate::{DetectionStage, FileType, detect_many};
+///
+/// let blobs = vec![
+///     ("src/main.go", "package main\n"),
+///     ("bin/run", "#!/usr/bin/env python3\n"),
+///     ("unsupported.filetype", ""),
+/// ];
+/// let detected: Vec<_> = detect_many(blobs)
+///     .into_iter()
+///     .map(|detection| detection.map(|detection| (detection.file_type, detection.stage)))
+///     .collect();
+/// assert_eq!(
+///     vec![
+///         Some((FileType::Go, DetectionStage::Extension)),
+///         Some((FileType::Python, DetectionStage::Shebang)),
+///         None,
+///     ],
+///     detected
+/// );
+/// ```
+pub fn detect_many<P, C>(items: impl IntoParallelIterator<Item = (P, C)>) -> Vec<Option<Detection>>
+where
+    P: AsRef<Path> + Send,
+    C: AsRef<str> + Send,
+{
+    items
+        .into_par_iter()
+        .map_init(Scratch::default, |scratch, (path, content)| {
+            let (path, content) = (path.as_ref(), content.as_ref());
+            detect_traced(path, content, &mut Trace::inactive(), scratch)
+                .map(|detected| Detection::new(path, content, detected))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::try_detect_with_stage;
+
+    #[test]
+    fn test_same_as_try_detect_in_order() {
+        let paths = [
+            "main.rs",
+            "App.JSX",
+            "view.html.erb",
+            "terraform.tfstate.backup",
+            "Makefile",
+            "include/util.h",
+            "a..b.c",
+            "noext",
+            "x.unknown-extension",
+        ];
+        let contents = ["", "#!/bin/sh\n", "@interface Util : NSObject\n@end\n"];
+        let items: Vec<(PathBuf, String)> = (0..20)
+            .flat_map(|_| paths)
+            .flat_map(|path| contents.map(|content| (PathBuf::from(path), content.to_string())))
+            .collect();
+        let expected: Vec<_> = items
+            .iter()
+            .map(|(path, content)| try_detect_with_stage(path, content))
+            .collect();
+        assert_eq!(expected, detect_many(items));
+    }
+}
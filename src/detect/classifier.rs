@@ -8,12 +8,13 @@ use std::collections::HashMap;
 
 use crate::FileType;
 
-/// Classify file content using naive Bayes token analysis.
+/// Classify file content using naive Bayes token analysis, returning the score of
+/// every language that scored at all, best first.
 ///
 /// This is a simplified version that uses keyword detection.
 /// The full implementation would use trained log probabilities from
 /// hyperpolyglot's token data.
-pub(crate) fn classify(content: &str) -> Option<FileType> {
+pub(crate) fn scores(content: &str) -> Vec<(FileType, i32)> {
     // Tokenize content and count language-specific keywords
     let tokens = tokenize(content);
 
@@ -61,25 +62,15 @@ pub(crate) fn classify(content: &str) -> Option<FileType> {
         }
     }
 
-    // Return the language with the highest score.
-    //
-    // Tie-break deterministically so results don't depend on HashMap iteration order.
-    let mut best: Option<(FileType, i32)> = None;
-    for (ft, score) in scores.into_iter() {
-        match best {
-            None => best = Some((ft, score)),
-            Some((best_ft, best_score)) => {
-                let ft_name: &'static str = ft.into();
-                let best_name: &'static str = best_ft.into();
-                let choose = score > best_score
-                    || (score == best_score && ft_name < best_name);
-                if choose {
-                    best = Some((ft, score));
-                }
-            }
-        }
-    }
-    best.map(|(ft, _)| ft)
+    // Highest score first; ties are broken by name so results don't depend on
+    // HashMap iteration order.
+    let mut scores: Vec<(FileType, i32)> = scores.into_iter().collect();
+    scores.sort_by(|(a_ft, a), (b_ft, b)| {
+        let a_name: &'static str = (*a_ft).into();
+        let b_name: &'static str = (*b_ft).into();
+        b.cmp(a).then(a_name.cmp(b_name))
+    });
+    scores
 }
 
 /// Simple tokenizer that extracts identifiers and keywords.
@@ -112,17 +103,17 @@ mod tests {
     #[test]
     fn test_classify_rust() {
         let rust_code = "fn main() {\n    let x = 42;\n    println!(\"{}\");\n}";
-        assert_eq!(Some(FileType::Rust), classify(rust_code));
+        assert_eq!(Some(FileType::Rust), scores(rust_code).first().map(|(ft, _)| *ft));
     }
 
     #[test]
     fn test_classify_python() {
         let python_code = "def main():\n    import os\n    class MyClass:\n        pass";
-        assert_eq!(Some(FileType::Python), classify(python_code));
+        assert_eq!(Some(FileType::Python), scores(python_code).first().map(|(ft, _)| *ft));
     }
 
     #[test]
     fn test_classify_empty() {
-        assert_eq!(None, classify(""));
+        assert_eq!(None, scores("").first().map(|(ft, _)| *ft));
     }
 }
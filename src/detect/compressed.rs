@@ -0,0 +1,269 @@
+//! Detection through compression suffixes: `schema.sql.gz` is detected as SQL by
+//! stripping the suffix and decompressing the start of the file for content checks.
+
+use std::{
+    fmt,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use flate2::read::MultiGzDecoder;
+use infer::MatcherType;
+use ruzstd::decoding::StreamingDecoder;
+
+use super::{DetectionStage, try_detect_with_stage};
+use crate::FileType;
+
+/// Number of decompressed bytes [`try_detect_compressed`] hands to the content-based
+/// stages.
+const MAX_DECOMPRESSED: usize = 51_200;
+
+/// A compression format whose file suffix detection looks through.
+#[derive(
+    Clone, Copy, Debug, Hash, PartialEq, Eq, strum_macros::IntoStaticStr, strum_macros::EnumString,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Compression {
+    /// `.gz`
+    Gzip,
+    /// `.bz2`
+    Bzip2,
+    /// `.xz`
+    Xz,
+    /// `.zst`
+    Zstd,
+}
+
+impl Compression {
+    /// The compression named by the suffix of `path` (`.gz`, `.bz2`, `.xz` or `.zst`, in
+    /// any case) and the path without it.
+    ///
+    /// # Example
+    /// ```
+    /// use std::path::Path;
+    /// use palate::Compression;
+    ///
+    /// assert_eq!(
+    ///     Some((Compression::Gzip, Path::new("dumps/schema.sql").to_path_buf())),
+    ///     Compression::from_path("dumps/schema.sql.gz")
+    /// );
+    /// assert_eq!(None, Compression::from_path("schema.sql"));
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> Option<(Self, PathBuf)> {
+        let path = path.as_ref();
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        let compression = match extension.as_str() {
+            "gz" => Self::Gzip,
+            "bz2" => Self::Bzip2,
+            "xz" => Self::Xz,
+            "zst" => Self::Zstd,
+            _ => return None,
+        };
+        Some((compression, path.with_extension("")))
+    }
+
+    /// The file suffix, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Gzip => "gz",
+            Self::Bzip2 => "bz2",
+            Self::Xz => "xz",
+            Self::Zstd => "zst",
+        }
+    }
+
+    /// The MIME type of the compressed file.
+    pub fn mime(self) -> &'static str {
+        match self {
+            Self::Gzip => "application/gzip",
+            Self::Bzip2 => "application/x-bzip2",
+            Self::Xz => "application/x-xz",
+            Self::Zstd => "application/zstd",
+        }
+    }
+
+    /// Whether `head`, the start of a file, starts with this format's magic bytes.
+    pub fn matches(self, head: &[u8]) -> bool {
+        infer::get(head).is_some_and(|kind| kind.mime_type() == self.mime())
+    }
+
+    /// A reader decompressing `compressed`.
+    pub fn decoder<'a>(self, compressed: impl Read + 'a) -> io::Result<Box<dyn Read + 'a>> {
+        Ok(match self {
+            Self::Gzip => Box::new(MultiGzDecoder::new(compressed)),
+            Self::Bzip2 => Box::new(bzip2_rs::DecoderReader::new(compressed)),
+            Self::Xz => Box::new(lzma_rust2::XzReader::new(compressed, true)),
+            Self::Zstd => Box::new(StreamingDecoder::new(compressed).map_err(io::Error::other)?),
+        })
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.into())
+    }
+}
+
+/// What [`try_detect_compressed`] found out about a compressed file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Compressed {
+    /// The file type of the decompressed content.
+    pub file_type: FileType,
+    /// The stage that decided it.
+    pub stage: DetectionStage,
+    /// How the file is compressed.
+    pub compression: Compression,
+    /// Whether the decompressed file is generated, see [`is_generated`].
+    pub generated: bool,
+}
+
+/// Try to detect the [`FileType`] of a compressed file like `schema.sql.gz` from its
+/// path and the start of its (compressed) content.
+///
+/// The compression suffix is stripped from the path, and up to 50 KiB of `compressed`
+/// is decompressed for the content-based stages; passing only the head of a large
+/// file is fine. Returns [`None`] when the path has no compression suffix, the content
+/// isn't compressed that way, the decompressed content isn't text, or nothing detects
+/// the file type.
+///
+/// # Example
+/// ```
+/// use std::io::Write;
+/// use palate::{Compression, DetectionStage, FileType};
+///
+/// let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+/// gz.write_all(b"#!/usr/bin/env python3\nprint('hi')\n").unwrap();
+/// let compressed = palate::try_detect_compressed("run.gz", &gz.finish().unwrap()).unwrap();
+/// assert_eq!(FileType::Python, compressed.file_type);
+/// assert_eq!(DetectionStage::Shebang, compressed.stage);
+/// assert_eq!(Compression::Gzip, compressed.compression);
+/// assert!(!compressed.generated);
+/// ```
+pub fn try_detect_compressed(path: impl AsRef<Path>, compressed: &[u8]) -> Option<Compressed> {
+    let (compression, inner) = Compression::from_path(path)?;
+    if !compression.matches(compressed) {
+        return None;
+    }
+    let head = decompress_head(compression, compressed, MAX_DECOMPRESSED).ok()?;
+    let is_text = match infer::get(&head) {
+        Some(kind) => kind.matcher_type() == MatcherType::Text,
+        None => !head.contains(&0),
+    };
+    if !is_text {
+        return None;
+    }
+    let content = String::from_utf8_lossy(&head);
+    let detection = try_detect_with_stage(&inner, &content)?;
+    Some(Compressed {
+        file_type: detection.file_type,
+        stage: detection.stage,
+        compression,
+        generated: detection.generated,
+    })
+}
+
+/// Decompress up to `max_bytes` of `compressed`. A truncated stream yields what could
+/// be decompressed; it is only an error if nothing could.
+fn decompress_head(
+    compression: Compression,
+    compressed: &[u8],
+    max_bytes: usize,
+) -> io::Result<Vec<u8>> {
+    let mut decoder = compression.decoder(compressed)?;
+    let mut head = vec![0; max_bytes];
+    let mut filled = 0;
+    while filled < max_bytes {
+        match decoder.read(&mut head[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) if filled == 0 => return Err(err),
+            Err(_) => break,
+        }
+    }
+    head.truncate(filled);
+    Ok(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn gzip(content: &[u8]) -> Vec<u8> {
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gz.write_all(content).unwrap();
+        gz.finish().unwrap()
+    }
+
+    #[test]
+    fn test_formats() {
+        // `printf '#!/usr/bin/env python3\nprint(1)\n' | bzip2`
+        let bz2 = [
+            0x42, 0x5a, 0x68, 0x39, 0x31, 0x41, 0x59, 0x26, 0x53, 0x59, 0x43, 0x0f, 0xcf, 0x98,
+            0x00, 0x00, 0x02, 0x59, 0x80, 0x00, 0x10, 0x68, 0x60, 0xa8, 0x00, 0x12, 0x61, 0xdf,
+            0x20, 0x20, 0x00, 0x22, 0x8d, 0x34, 0xd0, 0x32, 0x32, 0x68, 0x14, 0x68, 0xc8, 0x1a,
+            0x34, 0xc8, 0xd2, 0x96, 0xe9, 0x0b, 0xe3, 0x8b, 0x8c, 0x81, 0xc0, 0x90, 0x95, 0xa4,
+            0xac, 0x6c, 0xef, 0xd4, 0xf6, 0x80, 0x7e, 0x2e, 0xe4, 0x8a, 0x70, 0xa1, 0x20, 0x86,
+            0x1f, 0x9f, 0x30,
+        ];
+        // `printf 'CREATE TABLE t (id int);\n' | xz --check=crc32`
+        let xz = [
+            0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00, 0x00, 0x01, 0x69, 0x22, 0xde, 0x36, 0x04, 0xc0,
+            0x1d, 0x19, 0x21, 0x01, 0x1c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x20, 0x45, 0xa4, 0x21, 0x01, 0x00, 0x18, 0x43, 0x52, 0x45, 0x41, 0x54, 0x45, 0x20,
+            0x54, 0x41, 0x42, 0x4c, 0x45, 0x20, 0x74, 0x20, 0x28, 0x69, 0x64, 0x20, 0x69, 0x6e,
+            0x74, 0x29, 0x3b, 0x0a, 0x00, 0x00, 0x00, 0x00, 0xaf, 0xa9, 0x01, 0xb4, 0x00, 0x01,
+            0x35, 0x19, 0x5d, 0xdf, 0xdc, 0xe6, 0x90, 0x42, 0x99, 0x0d, 0x01, 0x00, 0x00, 0x00,
+            0x00, 0x01, 0x59, 0x5a,
+        ];
+        let zst = ruzstd::encoding::compress_to_vec(
+            &b"{\"a\": 1}\n"[..],
+            ruzstd::encoding::CompressionLevel::Fastest,
+        );
+        let detected = |path: &str, compressed: &[u8]| {
+            try_detect_compressed(path, compressed)
+                .map(|detected| (detected.file_type, detected.compression))
+        };
+        assert_eq!(
+            Some((FileType::Python, Compression::Bzip2)),
+            detected("bin/run.BZ2", &bz2)
+        );
+        assert_eq!(
+            Some((FileType::Sql, Compression::Xz)),
+            detected("schema.sql.xz", &xz)
+        );
+        assert_eq!(
+            Some((FileType::Json, Compression::Zstd)),
+            detected("events.json.zst", &zst)
+        );
+    }
+
+    #[test]
+    fn test_truncated_and_mismatched() {
+        let log: String = (0..20_000)
+            .map(|i| format!("#!/bin/sh\necho {i}\n"))
+            .collect();
+        let gz = gzip(log.as_bytes());
+        let head = &gz[..gz.len() / 2];
+        let detected = try_detect_compressed("deploy.gz", head).unwrap();
+        assert_eq!(FileType::Sh, detected.file_type);
+        assert_eq!(DetectionStage::Shebang, detected.stage);
+
+        assert_eq!(None, try_detect_compressed("schema.sql.gz", b"SELECT 1;\n"));
+        assert_eq!(
+            None,
+            try_detect_compressed("schema.sql.xz", &gzip(b"SELECT 1;\n"))
+        );
+        assert_eq!(
+            None,
+            try_detect_compressed("blob.bin.gz", &gzip(&[0, 1, 2, 0]))
+        );
+        assert_eq!(
+            None,
+            try_detect_compressed("schema.sql", &gzip(b"SELECT 1;\n"))
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Step-by-step account of how the detection pipeline handled one input.
+
+use std::path::Path;
+
+use crate::{FileType, FileTypeResolver};
+
+use super::{DetectionStage, Scratch, detect_traced, is_generated};
+
+/// What one stage of the pipeline did.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Step {
+    /// The stage.
+    pub stage: DetectionStage,
+    /// What happened in it.
+    pub outcome: Outcome,
+}
+
+/// The outcome of a [`Step`]. Rules are described the way they are written in the
+/// tables they come from: table keys, regexes, `heuristics.yml` rules, shebang
+/// interpreters or classifier scores.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Outcome {
+    /// The stage has no rule for this input; the reason says why.
+    Skipped(String),
+    /// A rule applied but didn't decide, e.g. a content check found nothing it
+    /// recognized.
+    NoMatch(String),
+    /// The rule decided the file type.
+    Matched {
+        /// The rule.
+        rule: String,
+        /// The file type it picked.
+        file_type: FileType,
+    },
+    /// An earlier stage already decided the file type.
+    NotReached,
+}
+
+/// How [`try_detect`](super::try_detect) arrived at its answer, see [`explain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Explanation {
+    /// The detected file type and the stage that decided it, as in the
+    /// [`Detection`](super::Detection) from [`try_detect_with_stage`](super::try_detect_with_stage).
+    pub detected: Option<(FileType, DetectionStage)>,
+    /// Every stage in pipeline order. A stage can appear more than once when it
+    /// tried several rules.
+    pub steps: Vec<Step>,
+    /// Whether the file is generated, see [`is_generated`].
+    pub generated: bool,
+}
+
+/// Run the detection pipeline on `path` and `content` and record what every stage
+/// did, for finding out why a file was detected the way it was.
+///
+/// # Example
+/// ```
+/// use palate::{DetectionStage, FileType, Outcome, explain};
+///
+/// let explanation = explain("run", "#!/usr/bin/env python3\n");
+/// assert_eq!(
+///     Some((FileType::Python, DetectionStage::Shebang)),
+///     explanation.detected
+/// );
+/// let shebang = explanation
+///     .steps
+///     .iter()
+///     .find(|step| step.stage == DetectionStage::Shebang)
+///     .unwrap();
+/// assert_eq!(
+///     Outcome::Matched {
+///         rule: "interpreter `python3`".to_string(),
+///         file_type: FileType::Python
+///     },
+///     shebang.outcome
+/// );
+/// assert!(!explanation.generated);
+/// ```
+pub fn explain(path: impl AsRef<Path>, content: &str) -> Explanation {
+    let path = path.as_ref();
+    let mut trace = Trace {
+        steps: Some(Vec::new()),
+    };
+    let detected = detect_traced(path, content, &mut trace, &mut Scratch::default());
+    let mut steps = trace.steps.unwrap_or_default();
+    for stage in STAGES {
+        if !steps.iter().any(|step| step.stage == stage) {
+            steps.push(Step {
+                stage,
+                outcome: Outcome::NotReached,
+            });
+        }
+    }
+    Explanation {
+        detected,
+        steps,
+        generated: is_generated(path, content),
+    }
+}
+
+/// Stages in the order the pipeline runs them first.
+const STAGES: [DetectionStage; 9] = [
+    DetectionStage::PathSuffix,
+    DetectionStage::Shebang,
+    DetectionStage::Filename,
+    DetectionStage::CompoundExtension,
+    DetectionStage::Disambiguation,
+    DetectionStage::Heuristics,
+    DetectionStage::Pattern,
+    DetectionStage::Extension,
+    DetectionStage::Classifier,
+];
+
+/// Collects steps while [`explain`] runs the pipeline; plain detection passes an
+/// inactive trace, which never builds an outcome.
+pub(super) struct Trace {
+    steps: Option<Vec<Step>>,
+}
+
+impl Trace {
+    pub(super) fn inactive() -> Self {
+        Self { steps: None }
+    }
+
+    pub(super) fn record(&mut self, stage: DetectionStage, outcome: impl FnOnce() -> Outcome) {
+        if let Some(steps) = &mut self.steps {
+            steps.push(Step {
+                stage,
+                outcome: outcome(),
+            });
+        }
+    }
+
+    /// Record the outcome of a table entry: the rule matched when `resolved` holds a
+    /// file type, and its content resolver declined otherwise.
+    pub(super) fn resolved(
+        &mut self,
+        stage: DetectionStage,
+        rule: impl FnOnce() -> String,
+        resolver: &FileTypeResolver,
+        resolved: Option<FileType>,
+    ) {
+        self.record(stage, || {
+            let rule = match resolver {
+                FileTypeResolver::Static(_) => rule(),
+                FileTypeResolver::Dynamic(_) => format!("{} (content resolver)", rule()),
+            };
+            match resolved {
+                Some(file_type) => Outcome::Matched { rule, file_type },
+                None => Outcome::NoMatch(rule),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcomes(explanation: &Explanation, stage: DetectionStage) -> Vec<&Outcome> {
+        explanation
+            .steps
+            .iter()
+            .filter(|step| step.stage == stage)
+            .map(|step| &step.outcome)
+            .collect()
+    }
+
+    #[test]
+    fn test_explain_extension() {
+        let explanation = explain("src/main.go", "package main\n");
+        assert_eq!(
+            Some((FileType::Go, DetectionStage::Extension)),
+            explanation.detected
+        );
+        assert_eq!(
+            vec![&Outcome::Matched {
+                rule: "extension `go`".to_string(),
+                file_type: FileType::Go
+            }],
+            outcomes(&explanation, DetectionStage::Extension)
+        );
+        assert!(matches!(
+            outcomes(&explanation, DetectionStage::Shebang)[..],
+            [Outcome::Skipped(_)]
+        ));
+        assert_eq!(
+            vec![&Outcome::NotReached],
+            outcomes(&explanation, DetectionStage::Classifier)
+        );
+    }
+
+    #[test]
+    fn test_explain_heuristics_and_classifier() {
+        let explanation = explain("src/main.rs", "fn main() {}\n");
+        assert_eq!(
+            Some((FileType::Rust, DetectionStage::Heuristics)),
+            explanation.detected
+        );
+        match outcomes(&explanation, DetectionStage::Heuristics)[..] {
+            [Outcome::Matched { rule, .. }] => {
+                assert!(rule.starts_with("heuristics.yml `.rs` rule "), "{rule}")
+            }
+            ref other => panic!("unexpected heuristics outcome {other:?}"),
+        }
+
+        let explanation = explain("notes", "fn main() { let x = 1; }");
+        assert_eq!(
+            Some((FileType::Rust, DetectionStage::Classifier)),
+            explanation.detected
+        );
+        match outcomes(&explanation, DetectionStage::Classifier)[..] {
+            [Outcome::Matched { rule, .. }] => assert!(rule.starts_with("scores: rust "), "{rule}"),
+            ref other => panic!("unexpected classifier outcome {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Generated file detection.
+//!
+//! A port of the rules in Linguist's `generated.rb`: lockfiles, protobuf/gRPC outputs,
+//! IDE project files, minified assets and source maps are recognized by path, and
+//! code generator banners are recognized in the first lines of the content, as are
+//! `phf_codegen` maps and source files that are one big lookup table.
+
+use std::path::Path;
+
+use aho_corasick::AhoCorasick;
+use lazy_regex::regex_is_match;
+use once_cell::sync::Lazy;
+use regex::RegexSet;
+
+use super::util::get_lines;
+
+/// Number of leading lines searched for generator banners.
+const MARKER_LINES: usize = 20;
+
+/// Average line length above which JavaScript and CSS are considered minified.
+const MINIFIED_LINE_LENGTH: usize = 110;
+
+/// Number of leading lines [`lookup_table`] looks at, and how many of them need to be
+/// table entries.
+const TABLE_LINES: usize = 100;
+const TABLE_ENTRIES: usize = 90;
+
+/// Extensions of the languages [`lookup_table`] looks at.
+const TABLE_EXTENSIONS: [&str; 14] = [
+    "rs", "go", "c", "cc", "cpp", "h", "hpp", "java", "kt", "cs", "swift", "js", "ts", "py",
+];
+
+static GENERATED_PATHS: Lazy<RegexSet> = Lazy::new(|| {
+    RegexSet::new([
+        // Lockfiles
+        r"(^|/)Cargo\.lock$",
+        r"(^|/)Cargo\.toml\.orig$",
+        r"(^|/)(package-lock|npm-shrinkwrap)\.json$",
+        r"(^|/)pnpm-lock\.yaml$",
+        r"(^|/)yarn\.lock$",
+        r"(^|/)bun\.lockb?$",
+        r"(^|/)deno\.lock$",
+        r"(^|/)composer\.lock$",
+        r"(^|/)(Gemfile|Podfile|Pipfile|flake|pubspec|mix|MODULE\.bazel)\.lock$",
+        r"(^|/)(poetry|pdm|uv|pixi|esy)\.lock$",
+        r"(^|/)(Gopkg|glide)\.lock$",
+        r"(^|/)go\.(work\.)?sum$",
+        r"(^|/)Package\.resolved$",
+        r"(^|/)packages\.lock\.json$",
+        // Protocol buffers and gRPC
+        r"\.pb\.(go|cc|h|swift)$",
+        r"\.pb\.gw\.go$",
+        r"_grpc\.pb\.(go|cc|h)$",
+        r"_pb2(_grpc)?\.pyi?$",
+        r"_(grpc_)?pb\.(js|d\.ts|rb)$",
+        // Xcode and Visual Studio
+        r"\.(nib|xcworkspacedata|xcuserstate|pbxproj)$",
+        r"\.designer\.(cs|vb)$",
+        r"\.feature\.cs$",
+        r"\.(sln|vcxproj\.filters)$",
+        r"(^|/)\.idea/",
+        r"(^|/)(Pods|Carthage/Build)/",
+        // Build outputs
+        r"\.(js|css)\.map$",
+        r"\.min\.(js|css)$",
+        r"(^|/)\.pnp\.[cm]?js$",
+        r"(^|/)__generated__/",
+        r"(^|/)\.sqlx/query-[0-9a-f]+\.json$",
+    ])
+    .unwrap()
+});
+
+static GENERATED_MARKERS: Lazy<AhoCorasick> = Lazy::new(|| {
+    AhoCorasick::builder()
+        .ascii_case_insensitive(true)
+        .build([
+            "@generated",
+            "Generated by the protocol buffer compiler",
+            "Generated by the gRPC",
+            "GENERATED CODE -- DO NOT EDIT",
+            "Autogenerated by Thrift Compiler",
+            "DO NOT EDIT THIS FILE - it is machine generated",
+            "Generated by Cython",
+            "This file is automatically generated by Racc",
+            "Generated by roxygen2: do not edit by hand",
+            "Generated by PEG.js",
+            "Generated by Haxe",
+            "<!-- Generated by Doxygen",
+            "GIMP RGB C-Source image dump",
+            "GIMP header image file format",
+            "Microsoft Developer Studio Generated Build File",
+        ])
+        .unwrap()
+});
+
+/// Check if a file was produced by a tool rather than written by hand.
+///
+/// Combines path rules (lockfiles, `*.pb.go`, Xcode/Visual Studio project files,
+/// source maps, ...) with content markers such as Go's `Code generated ... DO NOT EDIT.`
+/// banner, `@generated` tags and minified JavaScript/CSS.
+///
+/// [`try_detect`](crate::try_detect) only returns the file type, so its callers ask
+/// separately; [`try_detect_with_stage`](crate::try_detect_with_stage),
+/// [`explain`](crate::explain), `try_detect_compressed` and `ScannedFile` carry the answer
+/// as their `generated` field.
+///
+/// # Example
+/// ```
+/// use palate::is_generated;
+///
+/// assert!(is_generated("Cargo.lock", ""));
+/// assert!(is_generated("api/service.pb.go", ""));
+/// assert!(is_generated(
+///     "mock.go",
+///     "// Code generated by MockGen. DO NOT EDIT.\npackage mock\n"
+/// ));
+/// assert!(!is_generated("main.rs", "fn main() {}\n"));
+/// assert!(palate::explain("Cargo.lock", "").generated);
+/// ```
+pub fn is_generated(path: impl AsRef<Path>, content: &str) -> bool {
+    let path = path.as_ref();
+    generated_path(path)
+        || minified(path, content)
+        || has_generated_marker(path, content)
+        || lookup_table(path, content)
+}
+
+fn generated_path(path: &Path) -> bool {
+    GENERATED_PATHS.is_match(&path.to_string_lossy().replace('\\', "/"))
+}
+
+fn minified(path: &Path, content: &str) -> bool {
+    let is_asset = path.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+        matches!(
+            e.to_ascii_lowercase().as_str(),
+            "js" | "mjs" | "cjs" | "css"
+        )
+    });
+    if !is_asset {
+        return false;
+    }
+    let lines = content.lines().count();
+    lines > 0 && content.len() / lines > MINIFIED_LINE_LENGTH
+}
+
+fn has_generated_marker(path: &Path, content: &str) -> bool {
+    let head = get_lines(content, MARKER_LINES);
+    if GENERATED_MARKERS.is_match(head)
+        || regex_is_match!(r"(?mi)^\W*code generated\b.*\bdo not edit\b", head)
+        || regex_is_match!(r"(?mi)\bgenerated from\b.*\bby ANTLR\b", head)
+        || regex_is_match!(
+            r"(?mi)\b(auto-?generated|generated code)\b.*\bdo not (edit|modify)\b",
+            head
+        )
+        // What `phf_codegen` writes for a map or set.
+        || regex_is_match!(r"(?m)^::phf::(Ordered)?(Map|Set) \{\n\s*key: \d+,$", head)
+    {
+        return true;
+    }
+
+    // Source maps and Unity metadata are only recognizable by their first line.
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("map") => regex_is_match!(r#"^\{\s*"version"\s*:\s*\d+"#, head),
+        Some("meta") => head.starts_with("fileFormatVersion: "),
+        _ => false,
+    }
+}
+
+/// Source files that open with a table of literal entries, like `"rs" => Rust,` or
+/// `("etc/a2ps.cfg", A2ps),`, for hundreds of lines: lookup tables written out by a
+/// script. Data formats are left out, since holding tables is what they are for.
+fn lookup_table(path: &Path, content: &str) -> bool {
+    let is_code = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| TABLE_EXTENSIONS.contains(&e));
+    if !is_code {
+        return false;
+    }
+    let (mut lines, mut entries) = (0, 0);
+    for line in content.lines().take(TABLE_LINES) {
+        let line = line.trim();
+        lines += 1;
+        if line.ends_with(',')
+            && (line.starts_with('"') || line.starts_with("(\"") || line.contains(" => "))
+        {
+            entries += 1;
+        }
+    }
+    lines == TABLE_LINES && entries >= TABLE_ENTRIES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_paths() {
+        assert!(is_generated("web/package-lock.json", "{}"));
+        assert!(is_generated("proto/user_pb2.py", ""));
+        assert!(is_generated("App.xcodeproj/project.pbxproj", ""));
+        assert!(is_generated("dist/app.js.map", ""));
+        assert!(!is_generated("src/lockfile.rs", ""));
+    }
+
+    #[test]
+    fn test_generated_markers() {
+        let protobuf = "// Generated by the protocol buffer compiler.  DO NOT EDIT!\n";
+        assert!(is_generated("user.pb.cc", protobuf));
+        assert!(is_generated("schema.rs", "// @generated\n"));
+        assert!(is_generated(
+            "bundle.js.map",
+            "{\"version\":3,\"sources\":[]}"
+        ));
+        assert!(!is_generated(
+            "notes.md",
+            "Please do not edit the generated docs.\n"
+        ));
+    }
+
+    #[test]
+    fn test_minified() {
+        let minified = format!("{}\n", "var a=1;".repeat(40));
+        assert!(is_generated("app.js", &minified));
+        assert!(!is_generated("app.js", "var a = 1;\nvar b = 2;\n"));
+    }
+
+    #[test]
+    fn test_codegen_outputs() {
+        let phf = "\
+static DISAMBIGUATIONS: phf::Map<&'static str, &'static [Rule]> =
+::phf::Map {
+    key: 16287231350648472473,
+    disps: &[
+        (0, 2),
+";
+        assert!(is_generated("src/heuristics-map.rs", phf));
+
+        let entries = (0..200).map(|i| format!("    \"ext{i}\" => FileType::Text,\n"));
+        let table = format!(
+            "use phf::{{phf_map, Map}};\n\nstatic EXTENSIONS: Map<&str, FileType> = phf_map! {{\n{}}};\n",
+            entries.collect::<String>()
+        );
+        assert!(is_generated("src/file_extension.rs", &table));
+        assert!(!is_generated("data/extensions.json", &table));
+        let short = table.lines().take(50).collect::<Vec<_>>().join("\n");
+        assert!(!is_generated("src/file_extension.rs", &short));
+
+        let code = "fn main() {\n    let x = match y {\n        1 => \"one\",\n        _ => \"many\",\n    };\n}\n";
+        assert!(!is_generated("src/main.rs", &code.repeat(20)));
+    }
+}
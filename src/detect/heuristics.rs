@@ -4,7 +4,7 @@
 //! multiple possible languages. It uses the regex patterns defined in heuristics.yml.
 
 use fancy_regex::RegexBuilder;
-use std::path::Path;
+use std::fmt;
 
 use crate::FileType;
 
@@ -48,28 +48,64 @@ impl Pattern {
 
 /// Apply heuristic-based detection for ambiguous extensions.
 ///
-/// This is called when a file extension could match multiple languages.
-/// The patterns are evaluated in order; the first match wins.
-pub(crate) fn apply_heuristics(extension: &str, _path: &Path, content: &str) -> Option<FileType> {
-    match DISAMBIGUATIONS.get(extension) {
-        Some(rules) => {
-            for rule in rules.iter() {
-                if let Some(pattern) = &rule.pattern {
-                    if pattern.matches(content) {
-                        // Return first language from the rule
-                        return rule.languages.first().copied();
-                    };
-                } else {
-                    // No pattern means "still ambiguous". Only treat it as a default
-                    // when there's exactly one language.
-                    if rule.languages.len() == 1 {
-                        return rule.languages.first().copied();
-                    }
-                };
+/// This is called when a file extension could match multiple languages. The patterns
+/// are evaluated in order; the first match wins. Returns the position of that rule
+/// with the language it picks.
+pub(crate) fn decide(extension: &str, content: &str) -> Option<(usize, FileType)> {
+    let rules = DISAMBIGUATIONS.get(extension)?;
+    for (index, rule) in rules.iter().enumerate() {
+        if let Some(pattern) = &rule.pattern {
+            if pattern.matches(content) {
+                // Return first language from the rule
+                return Some((index, *rule.languages.first()?));
+            };
+        } else {
+            // No pattern means "still ambiguous". Only treat it as a default
+            // when there's exactly one language.
+            if rule.languages.len() == 1 {
+                return Some((index, rule.languages[0]));
             }
-            None
+        };
+    }
+    None
+}
+
+/// Number of rules for `extension`.
+pub(crate) fn rule_count(extension: &str) -> usize {
+    DISAMBIGUATIONS
+        .get(extension)
+        .map_or(0, |rules| rules.len())
+}
+
+/// The rule at `index` for `extension` as its languages and pattern, e.g.
+/// `objective-c if /^\s*@interface/`.
+pub(crate) fn describe_rule(extension: &str, index: usize) -> Option<String> {
+    let rule = DISAMBIGUATIONS.get(extension)?.get(index)?;
+    let languages: Vec<&str> = rule.languages.iter().map(|ft| ft.canonical()).collect();
+    Some(match &rule.pattern {
+        Some(pattern) => format!("{} if {pattern}", languages.join(" | ")),
+        None => format!("{} (default)", languages.join(" | ")),
+    })
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |f: &mut fmt::Formatter<'_>, patterns: &[Pattern], op: &str| {
+            f.write_str("(")?;
+            for (i, pattern) in patterns.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " {op} ")?;
+                }
+                write!(f, "{pattern}")?;
+            }
+            f.write_str(")")
+        };
+        match self {
+            Pattern::Positive(pattern) => write!(f, "/{pattern}/"),
+            Pattern::Negative(pattern) => write!(f, "not /{pattern}/"),
+            Pattern::And(patterns) => join(f, patterns, "and"),
+            Pattern::Or(patterns) => join(f, patterns, "or"),
         }
-        None => None,
     }
 }
 
@@ -81,7 +117,7 @@ mod tests {
     fn test_heuristics_csharp() {
         let content = "using System;";
         assert_eq!(
-            apply_heuristics(".cs", Path::new("test.cs"), content),
+            decide(".cs", content).map(|(_, ft)| ft),
             Some(FileType::CSharp)
         );
     }
@@ -90,7 +126,7 @@ mod tests {
     fn test_heuristics_cpp() {
         let content = "    #include <vector>";
         assert_eq!(
-            apply_heuristics(".h", Path::new("test.h"), content),
+            decide(".h", content).map(|(_, ft)| ft),
             Some(FileType::Cpp)
         );
     }
@@ -99,7 +135,7 @@ mod tests {
     fn test_heuristics_objc() {
         let content = "@interface MyClass";
         assert_eq!(
-            apply_heuristics(".h", Path::new("test.h"), content),
+            decide(".h", content).map(|(_, ft)| ft),
             Some(FileType::ObjC)
         );
     }
@@ -108,7 +144,7 @@ mod tests {
     fn test_heuristics_c_default() {
         let content = "random content that doesn't match any pattern";
         assert_eq!(
-            apply_heuristics(".h", Path::new("test.h"), content),
+            decide(".h", content).map(|(_, ft)| ft),
             Some(FileType::C) // Default fallback (no pattern)
         );
     }
@@ -117,7 +153,7 @@ mod tests {
     fn test_heuristics_unknown_extension() {
         let content = "anything";
         assert_eq!(
-            apply_heuristics(".xyz", Path::new("test.xyz"), content),
+            decide(".xyz", content).map(|(_, ft)| ft),
             None
         );
     }
@@ -127,8 +163,18 @@ mod tests {
         // `.mod` includes a fallback rule (no pattern) with multiple candidate languages.
         // Our current implementation treats this as "still ambiguous".
         assert_eq!(
-            apply_heuristics(".mod", Path::new("x.mod"), ""),
+            decide(".mod", "").map(|(_, ft)| ft),
             None
         );
     }
+
+    #[test]
+    fn test_describe_rule() {
+        let (index, ft) = decide(".h", "@interface MyClass").unwrap();
+        assert_eq!(FileType::ObjC, ft);
+        let rule = describe_rule(".h", index).unwrap();
+        assert!(rule.starts_with("objc if "), "{rule}");
+        assert!(rule_count(".h") > index);
+        assert_eq!(None, describe_rule(".xyz", 0));
+    }
 }
@@ -0,0 +1,228 @@
+//! Detection in two phases: the path first, and the content only when the path can't
+//! decide on its own.
+//!
+//! Reading content is the expensive part of detection on remote filesystems and object
+//! stores, and most files are decided by their name or extension. [`detect_path`] says
+//! which files those are, and how much content the others need.
+
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+};
+
+use crate::{FileType, FileTypeResolver, scan::DEFAULT_MAX_BYTES};
+
+use super::{
+    DISAMBIGUATED_EXTENSIONS, Detection, DetectionStage, Scratch, dotted_into,
+    file_extension::FILE_EXTENSION, filename::FILENAME, find_compound_extension, heuristics,
+    lowercase_into, path_suffix::PATH_SUFFIX, pattern::PATTERN, try_detect_with_stage,
+};
+
+thread_local! {
+    /// Buffers for the lowercase file names and extensions [`detect_path`] looks up.
+    static SCRATCH: RefCell<Scratch> = RefCell::default();
+}
+
+/// What the path of a file says about its type, see [`detect_path`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PathDetection {
+    /// The path decides.
+    Resolved(FileType, DetectionStage),
+    /// The content decides; read as much as [`Pending::max_bytes`] says and finish with
+    /// [`Pending::detect`].
+    NeedsContent(Pending),
+}
+
+/// A detection that is waiting for the content of the file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pending {
+    path: PathBuf,
+}
+
+impl Pending {
+    /// How many bytes from the start of the file the content phase needs. The content
+    /// stages look at all they are given, so this is what scans read for detection.
+    pub fn max_bytes(&self) -> usize {
+        DEFAULT_MAX_BYTES
+    }
+
+    /// Finish the detection with the content of the file, or at least its first
+    /// [`Self::max_bytes`] bytes; the result is that of
+    /// [`try_detect_with_stage`](crate::try_detect_with_stage).
+    pub fn detect(&self, content: &str) -> Option<Detection> {
+        try_detect_with_stage(&self.path, content)
+    }
+}
+
+/// The path-only phase of [`try_detect_with_stage`]: either the answer, or what is
+/// needed to finish the detection once the content is read.
+///
+/// Path suffix rules like `etc/a2ps.cfg`, file names like `Cargo.toml` and extensions
+/// like `.go` or `.html.erb` decide on their own when they name one file type. These
+/// answers trust the path over a `#!` line, which is what lets callers skip the read.
+/// Files whose name or extension is ambiguous need the content: `.h`, everything with
+/// `heuristics.yml` rules like `.rs`, and files without a known extension.
+///
+/// # Example
+/// ```
+/// use palate::{DetectionStage, FileType, PathDetection, detect_path};
+///
+/// assert_eq!(
+///     PathDetection::Resolved(FileType::Go, DetectionStage::Extension),
+///     detect_path("cmd/server/main.go")
+/// );
+///
+/// // `.h` is C, C++ or Objective-C depending on the content, and so are the extensions
+/// // with Linguist heuristics, like `.rs` (Rust or RenderScript).
+/// let PathDetection::NeedsContent(pending) = detect_path("include/util.h") else {
+///     unreachable!()
+/// };
+/// assert_eq!(palate::scan::DEFAULT_MAX_BYTES, pending.max_bytes());
+/// assert_eq!(
+///     Some(FileType::ObjC),
+///     pending
+///         .detect("@interface Util : NSObject\n@end\n")
+///         .map(|detection| detection.file_type)
+/// );
+/// ```
+pub fn detect_path(path: impl AsRef<Path>) -> PathDetection {
+    let path = path.as_ref();
+    match SCRATCH.with_borrow_mut(|scratch| decided_by_path(path, scratch)) {
+        Some((ft, stage)) => PathDetection::Resolved(ft, stage),
+        None => PathDetection::NeedsContent(Pending {
+            path: path.to_path_buf(),
+        }),
+    }
+}
+
+/// What the stages of the pipeline other than the shebang decide from `path` alone, in
+/// their order; `None` as soon as a stage could look at the content.
+fn decided_by_path(path: &Path, scratch: &mut Scratch) -> Option<(FileType, DetectionStage)> {
+    let decided = |resolver: &FileTypeResolver, stage| match resolver {
+        FileTypeResolver::Static(ft) => Some((*ft, stage)),
+        FileTypeResolver::Dynamic(_) => None,
+    };
+
+    for (suffix, resolver) in PATH_SUFFIX {
+        if path.ends_with(suffix) {
+            return decided(resolver, DetectionStage::PathSuffix);
+        }
+    }
+
+    let filename = path.file_name().and_then(|os_name| os_name.to_str());
+    if let Some(resolver) = filename.and_then(|filename| FILENAME.get(filename)) {
+        return decided(resolver, DetectionStage::Filename);
+    }
+
+    if let Some(filename) = filename {
+        let filename = lowercase_into(&mut scratch.filename, filename);
+        let found = find_compound_extension(filename, &mut scratch.key, |key| {
+            Some(decided(
+                FILE_EXTENSION.get(key)?,
+                DetectionStage::CompoundExtension,
+            ))
+        });
+        if let Some(decided) = found {
+            return decided;
+        }
+    }
+
+    let ext = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => Some(lowercase_into(&mut scratch.extension, ext)),
+        None => None,
+    };
+    if let Some(ext) = ext {
+        if DISAMBIGUATED_EXTENSIONS.contains(&ext)
+            || heuristics::rule_count(dotted_into(&mut scratch.dotted_extension, ext)) > 0
+        {
+            return None;
+        }
+    }
+
+    for (match_full_path, regex, pattern) in PATTERN.iter() {
+        let haystack = match match_full_path {
+            true => path.to_str(),
+            false => filename,
+        };
+        if haystack.is_some_and(|haystack| regex.is_match(haystack)) {
+            return decided(&pattern.resolver, DetectionStage::Pattern);
+        }
+    }
+
+    decided(FILE_EXTENSION.get(ext?)?, DetectionStage::Extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Contents that trigger shebangs, the built-in checks and common heuristics.
+    const CONTENTS: [&str; 16] = [
+        "",
+        "#!/usr/bin/env python3\nprint(1)\n",
+        "#!/bin/sh\nexec scala \"$0\" \"$@\"\n",
+        "#!/usr/bin/env unknown-interpreter\n",
+        "<?xml version=\"1.0\"?>\n<eagle/>\n",
+        "(define (f x) x)\n",
+        "EESchema Schematic File Version 4\n",
+        "implement Shell;\n",
+        "#include <sourcemod>\npublic Plugin:myinfo = {};\n",
+        "<?php echo 1;\n",
+        "#version 330\nvoid main() {}\n",
+        "function f() { return 1; }\n",
+        "server {\n  listen 80;\n}\n",
+        "@implementation Foo\n@end\n",
+        "InstallMethod(Foo);\nmodule Bar;\n",
+        "(* Notebook[{}] *)\n",
+    ];
+
+    #[test]
+    fn test_same_as_try_detect() {
+        let paths = FILE_EXTENSION
+            .keys()
+            .map(|ext| format!("dir/file.{ext}"))
+            .chain(FILENAME.keys().map(|name| format!("dir/{name}")))
+            .chain(PATH_SUFFIX.iter().map(|(suffix, _)| format!("/{suffix}")))
+            .chain(["README", "dir/noext", "x.unknown-extension"].map(String::from));
+        for path in paths {
+            // The content phase runs the whole pipeline.
+            let PathDetection::Resolved(ft, stage) = detect_path(&path) else {
+                continue;
+            };
+            for content in CONTENTS {
+                // Path suffixes are the only stage before the shebang, which the other
+                // answers don't read.
+                if stage != DetectionStage::PathSuffix && content.starts_with("#!") {
+                    continue;
+                }
+                let expected = try_detect_with_stage(&path, content)
+                    .map(|detection| (detection.file_type, detection.stage));
+                assert_eq!(expected, Some((ft, stage)), "{path} with {content:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolved_by_path() {
+        let resolved = |path| match detect_path(path) {
+            PathDetection::Resolved(ft, stage) => Some((ft, stage)),
+            PathDetection::NeedsContent(_) => None,
+        };
+        assert_eq!(
+            Some((FileType::Bzl, DetectionStage::Filename)),
+            resolved("third_party/BUILD")
+        );
+        assert_eq!(
+            Some((FileType::Go, DetectionStage::Extension)),
+            resolved("src/Main.GO")
+        );
+        assert_eq!(
+            Some((FileType::Json, DetectionStage::CompoundExtension)),
+            resolved("terraform.tfstate.backup")
+        );
+        assert_eq!(None, resolved("include/util.h"));
+        assert_eq!(None, resolved("src/main.rs"));
+        assert_eq!(None, resolved("bin/run"));
+    }
+}
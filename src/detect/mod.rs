@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{fmt, path::Path};
 
 use aho_corasick::AhoCorasick;
 use infer::Infer;
@@ -8,19 +8,33 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 
 use self::{
-    file_extension::FILE_EXTENSION, filename::FILENAME, path_suffix::PATH_SUFFIX, pattern::PATTERN,
-    shebang::detect_from_shebang, util::get_lines,
+    explain::Trace,
+    file_extension::FILE_EXTENSION,
+    filename::FILENAME,
+    path_suffix::PATH_SUFFIX,
+    pattern::{PATTERN, Pattern},
+    shebang::detect_from_shebang,
+    util::get_lines,
 };
 use crate::FileType;
 
+#[cfg(feature = "rayon")]
+mod batch;
 mod classifier;
+#[cfg(feature = "compression")]
+mod compressed;
+mod explain;
 mod file_extension;
 mod filename;
+mod generated;
 mod heuristics;
+mod lazy;
 mod path_suffix;
 mod pattern;
 mod shebang;
+mod test_file;
 mod util;
+mod vendor;
 
 #[cfg(feature = "tokio")]
 mod stream;
@@ -28,6 +42,88 @@ mod stream;
 #[cfg(feature = "tokio")]
 pub use stream::*;
 
+#[cfg(feature = "rayon")]
+pub use batch::detect_many;
+#[cfg(feature = "compression")]
+pub use compressed::{Compressed, Compression, try_detect_compressed};
+pub use explain::{Explanation, Outcome, Step, explain};
+pub use generated::is_generated;
+pub use lazy::{PathDetection, Pending, detect_path};
+pub use test_file::is_test_file;
+pub use vendor::{is_documentation, is_vendored};
+
+/// The step of the detection pipeline that resolved a [`FileType`].
+///
+/// Stages are listed in the order [`try_detect`] runs them; disambiguation and
+/// pattern stages can run more than once for different path shapes.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    strum_macros::IntoStaticStr,
+    strum_macros::EnumString,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[non_exhaustive]
+pub enum DetectionStage {
+    /// Full path suffix such as `etc/a2ps.cfg`.
+    PathSuffix,
+    /// `#!` interpreter line.
+    Shebang,
+    /// Exact file name such as `Makefile`.
+    Filename,
+    /// Multi-dot extension such as `.js.erb`.
+    CompoundExtension,
+    /// Built-in content checks for high-conflict extensions.
+    Disambiguation,
+    /// Linguist `heuristics.yml` rules.
+    Heuristics,
+    /// Neovim-style path patterns.
+    Pattern,
+    /// Plain file extension.
+    Extension,
+    /// Content classifier fallback.
+    Classifier,
+    /// A project mapping from [`scan::Overrides`](crate::scan::Overrides); never
+    /// returned by [`try_detect`].
+    Override,
+    /// Nothing matched and the file is reported as [`FileType::Text`], like [`detect`]
+    /// does; only used by scans that
+    /// [keep undetected files](crate::scan::ScanBuilder::keep_undetected).
+    Fallback,
+}
+
+impl fmt::Display for DetectionStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.into())
+    }
+}
+
+/// What [`try_detect_with_stage`] found out about a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Detection {
+    /// The detected file type.
+    pub file_type: FileType,
+    /// The stage that decided it.
+    pub stage: DetectionStage,
+    /// Whether the file is generated, see [`is_generated`].
+    pub generated: bool,
+}
+
+impl Detection {
+    fn new(path: &Path, content: &str, (file_type, stage): (FileType, DetectionStage)) -> Self {
+        Self {
+            file_type,
+            stage,
+            generated: is_generated(path, content),
+        }
+    }
+}
+
 /// Same as [`try_detect`] but automatically falling back to [`FileType::Text`] where
 /// [`try_detect`] would return [`None`].
 ///
@@ -54,31 +150,149 @@ pub fn detect(path: impl AsRef<Path>, content: &str) -> FileType {
 /// assert_eq!(None, palate::try_detect("unsupported.filetype", ""));
 /// ```
 pub fn try_detect(path: impl AsRef<Path>, content: &str) -> Option<FileType> {
+    detect_with_stage(path.as_ref(), content).map(|(ft, _)| ft)
+}
+
+/// Same as [`try_detect`] but also reporting which [`DetectionStage`] produced the answer,
+/// and whether the file is generated.
+///
+/// # Example
+/// ```
+/// use palate::{DetectionStage, FileType};
+///
+/// let detection = palate::try_detect_with_stage("main.rs", "").unwrap();
+/// assert_eq!(FileType::Rust, detection.file_type);
+/// assert_eq!(DetectionStage::Extension, detection.stage);
+/// assert!(!detection.generated);
+///
+/// let detection = palate::try_detect_with_stage("run", "#!/usr/bin/env python3\n").unwrap();
+/// assert_eq!(
+///     (FileType::Python, DetectionStage::Shebang),
+///     (detection.file_type, detection.stage)
+/// );
+/// assert!(palate::try_detect_with_stage("Cargo.lock", "").unwrap().generated);
+/// ```
+pub fn try_detect_with_stage(path: impl AsRef<Path>, content: &str) -> Option<Detection> {
     let path = path.as_ref();
+    detect_with_stage(path, content).map(|detected| Detection::new(path, content, detected))
+}
+
+/// [`try_detect_with_stage`] without the check for generated files.
+pub(crate) fn detect_with_stage(path: &Path, content: &str) -> Option<(FileType, DetectionStage)> {
+    detect_traced(path, content, &mut Trace::inactive(), &mut Scratch::default())
+}
+
+/// Buffers for the lowercase file name and extension [`detect_traced`] looks up, kept
+/// across calls by callers that detect many files.
+#[derive(Default)]
+struct Scratch {
+    filename: String,
+    key: String,
+    extension: String,
+    dotted_extension: String,
+}
+
+/// Replace the contents of `buffer` with `s` in ASCII lowercase.
+fn lowercase_into<'a>(buffer: &'a mut String, s: &str) -> &'a str {
+    buffer.clear();
+    buffer.push_str(s);
+    buffer.make_ascii_lowercase();
+    buffer
+}
+
+/// Replace the contents of `buffer` with `ext` behind a dot, like `.rs`.
+fn dotted_into<'a>(buffer: &'a mut String, ext: &str) -> &'a str {
+    buffer.clear();
+    buffer.push('.');
+    buffer.push_str(ext);
+    buffer
+}
+
+/// Hand the compound extensions of the lowercase `filename` to `visit`, from five
+/// dot-separated parts down to two (`html.erb` for `view.html.erb`), until it returns
+/// something. The extensions are built in `key`.
+fn find_compound_extension<T>(
+    filename: &str,
+    key: &mut String,
+    mut visit: impl FnMut(&str) -> Option<T>,
+) -> Option<T> {
+    let parts = || filename.split('.').filter(|s| !s.is_empty());
+    let count = parts().count();
+    for n in (2..=count.min(5)).rev() {
+        key.clear();
+        for part in parts().skip(count - n) {
+            if !key.is_empty() {
+                key.push('.');
+            }
+            key.push_str(part);
+        }
+        if let Some(found) = visit(key) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// The detection pipeline behind [`try_detect_with_stage`] and [`explain`], recording
+/// what every stage did in `trace`.
+fn detect_traced(
+    path: &Path,
+    content: &str,
+    trace: &mut Trace,
+    scratch: &mut Scratch,
+) -> Option<(FileType, DetectionStage)> {
+    let skipped = |reason: &'static str| move || Outcome::Skipped(reason.to_string());
 
     // path suffix
+    let mut suffix_matched = false;
     for (suffix, resolver) in PATH_SUFFIX {
         if path.ends_with(suffix) {
-            if let Some(ft) = resolver.resolve(path, content) {
-                return Some(ft);
+            suffix_matched = true;
+            let resolved = resolver.resolve(path, content);
+            let rule = || format!("path suffix `{suffix}`");
+            trace.resolved(DetectionStage::PathSuffix, rule, resolver, resolved);
+            if let Some(ft) = resolved {
+                return Some((ft, DetectionStage::PathSuffix));
             }
         }
     }
+    if !suffix_matched {
+        trace.record(
+            DetectionStage::PathSuffix,
+            skipped("no path suffix rule matches"),
+        );
+    }
 
     // shebang detection (from hyperpolyglot)
     if let Some(ft) = detect_from_shebang(content) {
-        return Some(ft);
-    }
+        trace.record(DetectionStage::Shebang, || Outcome::Matched {
+            rule: format!(
+                "interpreter `{}`",
+                shebang::interpreter(content).unwrap_or("")
+            ),
+            file_type: ft,
+        });
+        return Some((ft, DetectionStage::Shebang));
+    }
+    trace.record(DetectionStage::Shebang, || {
+        match shebang::interpreter(content) {
+            Some(interpreter) => Outcome::NoMatch(format!("unknown interpreter `{interpreter}`")),
+            None => Outcome::Skipped("no `#!` line".to_string()),
+        }
+    });
 
     // filename
-    if let Some(resolver) = path
-        .file_name()
-        .and_then(|os_name| os_name.to_str())
-        .and_then(|filename| FILENAME.get(filename))
-    {
-        if let Some(ft) = resolver.resolve(path, content) {
-            return Some(ft);
+    let filename = path.file_name().and_then(|os_name| os_name.to_str());
+    match filename.and_then(|filename| Some((filename, FILENAME.get(filename)?))) {
+        Some((filename, resolver)) => {
+            let resolved = resolver.resolve(path, content);
+            let rule = || format!("filename `{filename}`");
+            trace.resolved(DetectionStage::Filename, rule, resolver, resolved);
+            if let Some(ft) = resolved {
+                return Some((ft, DetectionStage::Filename));
+            }
         }
+        None => trace.record(DetectionStage::Filename, skipped("no filename rule")),
     }
 
     // Compound extension (e.g. `foo.js.erb`, `terraform.tfstate.backup`, `sample.axi.erb`).
@@ -86,59 +300,42 @@ pub fn try_detect(path: impl AsRef<Path>, content: &str) -> Option<FileType> {
     // Neovim's "extension" table includes a small number of dotted keys that are
     // not representable via `Path::extension()` (which only returns the last segment).
     // We reconstruct these candidates from the filename and try the extension map.
-    if let Some(filename) = path.file_name().and_then(|os_name| os_name.to_str()) {
-        let filename = filename.to_ascii_lowercase();
-        let parts: Vec<&str> = filename.split('.').filter(|s| !s.is_empty()).collect();
-        if parts.len() >= 2 {
-            let max = parts.len().min(5);
-            for n in (2..=max).rev() {
-                let start = parts.len() - n;
-                let key = parts[start..].join(".");
-                if let Some(resolver) = FILE_EXTENSION.get(key.as_str()) {
-                    if let Some(ft) = resolver.resolve(path, content) {
-                        return Some(ft);
-                    }
-                }
-            }
-        }
+    let mut compound_matched = false;
+    if let Some(filename) = filename {
+        let filename = lowercase_into(&mut scratch.filename, filename);
+        let found = find_compound_extension(filename, &mut scratch.key, |key| {
+            let resolver = FILE_EXTENSION.get(key)?;
+            compound_matched = true;
+            let resolved = resolver.resolve(path, content);
+            let rule = || format!("extension `{key}`");
+            trace.resolved(DetectionStage::CompoundExtension, rule, resolver, resolved);
+            resolved
+        });
+        if let Some(ft) = found {
+            return Some((ft, DetectionStage::CompoundExtension));
+        }
+    }
+    if !compound_matched {
+        trace.record(
+            DetectionStage::CompoundExtension,
+            skipped("no multi-dot extension rule"),
+        );
     }
 
     // Early, content-based disambiguation for a few high-conflict extensions where
     // the generic heuristics table isn't precise enough.
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        let ext = ext.to_ascii_lowercase();
-        match ext.as_str() {
-            // `.h` is ambiguous; prefer C++ for most headers unless Objective-C markers are present.
-            "h" => return header(path, content),
-            // `.spec` is ambiguous (RPM vs python/ruby "spec" scripts).
-            "spec" => {
-                let head = get_lines(content, 120);
-                if regex_is_match!(r"(?m)^\s*\w+\s*=\s*Analysis\s*\(", head)
-                    || regex_is_match!(r"(?m)^\s*from\s+\w+\s+import\s+\w+", head)
-                    || regex_is_match!(r"(?m)^\s*import\s+\w+", head)
-                {
-                    return Some(FileType::Python);
-                }
-                if regex_is_match!(r"(?m)^\s*describe\b", head) && head.contains("require") {
-                    return Some(FileType::Ruby);
-                }
-            }
-            // `.t` is used by multiple ecosystems (Terra, Raku tests, others).
-            "t" => {
-                let head = get_lines(content, 200);
-                if regex_is_match!(r"(?mi)^\s*terra\b|\bterralib\b", head) {
-                    return Some(FileType::Terra);
-                }
-                // Raku markers in tests.
-                if regex_is_match!(r"(?mi)^\s*use\s+v6\s*;", head)
-                    || regex_is_match!(r"(?mi)\bis\s+copy\b", head)
-                    || regex_is_match!(r"(?m)->\s*\$\w+", head)
-                    || regex_is_match!(r"(?m)^\\s*#\\s*vim:\\s*ft=perl6\\b", content)
-                {
-                    return Some(FileType::Raku);
-                }
-            }
-            _ => {}
+    let ext = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => Some(lowercase_into(&mut scratch.extension, ext)),
+        None => None,
+    };
+    if let Some(ext) = ext {
+        let resolved = disambiguate_early(path, ext, content);
+        if let Some(ft) = resolved {
+            trace.record(DetectionStage::Disambiguation, || Outcome::Matched {
+                rule: format!("built-in check for `.{ext}`"),
+                file_type: ft,
+            });
+            return Some((ft, DetectionStage::Disambiguation));
         }
     }
 
@@ -147,177 +344,333 @@ pub fn try_detect(path: impl AsRef<Path>, content: &str) -> Option<FileType> {
     // This runs before Neovim-style patterns so it can override extension-like
     // patterns (e.g. `^.*\\.[Mm][Oo][Dd]$`) when content indicates a different
     // language.
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        let ext = ext.to_ascii_lowercase();
-        let dotted_extension = format!(".{ext}");
-        if let Some(ft) = heuristics::apply_heuristics(&dotted_extension, path, content) {
-            return Some(ft);
-        }
+    if let Some(ext) = ext {
+        let dotted_extension = dotted_into(&mut scratch.dotted_extension, ext);
+        if let Some((index, ft)) = heuristics::decide(dotted_extension, content) {
+            trace.record(DetectionStage::Heuristics, || Outcome::Matched {
+                rule: format!(
+                    "heuristics.yml `{dotted_extension}` rule {}: {}",
+                    index + 1,
+                    heuristics::describe_rule(dotted_extension, index).unwrap_or_default()
+                ),
+                file_type: ft,
+            });
+            return Some((ft, DetectionStage::Heuristics));
+        }
+        let rules = heuristics::rule_count(dotted_extension);
+        trace.record(DetectionStage::Heuristics, || match rules {
+            0 => Outcome::Skipped(format!("no heuristics.yml rules for `{dotted_extension}`")),
+            rules => Outcome::NoMatch(format!(
+                "none of the {rules} heuristics.yml rules for `{dotted_extension}` matched"
+            )),
+        });
+    } else {
+        trace.record(DetectionStage::Heuristics, skipped("no extension"));
     }
 
     // Content-based disambiguation for a few high-conflict extensions where Neovim defaults
     // are not reliable for our use-case.
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        let ext = ext.to_ascii_lowercase();
-        match ext.as_str() {
-            // `.sch` is extremely overloaded (Eagle, KiCad, XML schematron/schema, Scheme...).
-            // Use a few cheap content checks to disambiguate.
-            "sch" => {
-                if let Some(first) = util::next_non_blank(content, 0) {
-                    let first = first.trim_start();
-                    if first.starts_with("EESchema") {
-                        return Some(FileType::EeschemaSchematic);
+    match ext {
+        Some(ext) => {
+            if let Some(ft) = disambiguate(ext, content) {
+                trace.record(DetectionStage::Disambiguation, || Outcome::Matched {
+                    rule: format!("built-in check for `.{ext}`"),
+                    file_type: ft,
+                });
+                return Some((ft, DetectionStage::Disambiguation));
+            }
+            trace.record(
+                DetectionStage::Disambiguation,
+                skipped("no built-in check matched"),
+            );
+        }
+        None => trace.record(DetectionStage::Disambiguation, skipped("no extension")),
+    }
+
+    // patterns (non-negative priority)
+    let mut pattern_matched = false;
+    let mut try_pattern = |(match_full_path, regex, pat): &(bool, &Regex, Pattern)| {
+        let haystack = match match_full_path {
+            true => path.to_str(),
+            false => path.file_name().and_then(|os_name| os_name.to_str()),
+        };
+        if haystack.is_none_or(|haystack| !regex.is_match(haystack)) {
+            return None;
+        }
+        pattern_matched = true;
+        let resolved = pat.resolver.resolve(path, content);
+        let rule = || {
+            let target = if *match_full_path { "path" } else { "filename" };
+            match pat.priority {
+                Some(priority) => format!(
+                    "{target} pattern `{}` (priority {priority})",
+                    regex.as_str()
+                ),
+                None => format!("{target} pattern `{}`", regex.as_str()),
+            }
+        };
+        trace.resolved(DetectionStage::Pattern, rule, &pat.resolver, resolved);
+        resolved
+    };
+    let mut negative_prio_start_index = 0;
+    for (index, entry) in PATTERN.iter().enumerate() {
+        if entry.2.priority.is_some_and(|prio| prio < 0) {
+            negative_prio_start_index = index;
+            break;
+        }
+        if let Some(ft) = try_pattern(entry) {
+            return Some((ft, DetectionStage::Pattern));
+        }
+    }
+
+    // patterns (negative priority)
+    for entry in PATTERN.iter().skip(negative_prio_start_index) {
+        if let Some(ft) = try_pattern(entry) {
+            return Some((ft, DetectionStage::Pattern));
+        }
+    }
+    if !pattern_matched {
+        trace.record(
+            DetectionStage::Pattern,
+            skipped("no pattern matches the path"),
+        );
+    }
+
+    // file extension
+    match ext.and_then(|ext| Some((FILE_EXTENSION.get(ext)?, ext))) {
+        Some((resolver, ext)) => {
+            let resolved = resolver.resolve(path, content);
+            let rule = || format!("extension `{ext}`");
+            trace.resolved(DetectionStage::Extension, rule, resolver, resolved);
+            if let Some(ft) = resolved {
+                return Some((ft, DetectionStage::Extension));
+            }
+        }
+        None => trace.record(DetectionStage::Extension, skipped("no extension rule")),
+    }
+
+    // Final fallback: naive Bayes classifier
+    let scores = classifier::scores(content);
+    if let Some(&(ft, _)) = scores.first() {
+        trace.record(DetectionStage::Classifier, || Outcome::Matched {
+            rule: format!(
+                "scores: {}",
+                scores
+                    .iter()
+                    .map(|(ft, score)| format!("{} {score}", ft.canonical()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            file_type: ft,
+        });
+        return Some((ft, DetectionStage::Classifier));
+    }
+    trace.record(DetectionStage::Classifier, || {
+        Outcome::NoMatch("no language scored".to_string())
+    });
+
+    None
+}
+
+/// The lowercase extensions whose content [`disambiguate_early`] and [`disambiguate`]
+/// look at.
+const DISAMBIGUATED_EXTENSIONS: [&str; 23] = [
+    "b", "brd", "command", "cp", "fcgi", "frag", "gi", "gs", "h", "j", "nb", "nbp", "pkb",
+    "pks", "pluginspec", "sch", "shader", "sls", "sp", "spec", "t", "vhost", "workflow",
+];
+
+/// Early, content-based disambiguation for a few high-conflict extensions where the
+/// generic heuristics table isn't precise enough. `ext` is lowercase.
+fn disambiguate_early(path: &Path, ext: &str, content: &str) -> Option<FileType> {
+    match ext {
+        // `.h` is ambiguous; prefer C++ for most headers unless Objective-C markers are present.
+        "h" => {
+            return header(path, content);
+        }
+        // `.spec` is ambiguous (RPM vs python/ruby "spec" scripts).
+        "spec" => {
+            let head = get_lines(content, 120);
+            if regex_is_match!(r"(?m)^\s*\w+\s*=\s*Analysis\s*\(", head)
+                || regex_is_match!(r"(?m)^\s*from\s+\w+\s+import\s+\w+", head)
+                || regex_is_match!(r"(?m)^\s*import\s+\w+", head)
+            {
+                return Some(FileType::Python);
+            }
+            if regex_is_match!(r"(?m)^\s*describe\b", head) && head.contains("require") {
+                return Some(FileType::Ruby);
+            }
+        }
+        // `.t` is used by multiple ecosystems (Terra, Raku tests, others).
+        "t" => {
+            let head = get_lines(content, 200);
+            if regex_is_match!(r"(?mi)^\s*terra\b|\bterralib\b", head) {
+                return Some(FileType::Terra);
+            }
+            // Raku markers in tests.
+            if regex_is_match!(r"(?mi)^\s*use\s+v6\s*;", head)
+                || regex_is_match!(r"(?mi)\bis\s+copy\b", head)
+                || regex_is_match!(r"(?m)->\s*\$\w+", head)
+                || regex_is_match!(r"(?m)^\\s*#\\s*vim:\\s*ft=perl6\\b", content)
+            {
+                return Some(FileType::Raku);
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
+/// Content-based disambiguation for a few high-conflict extensions where Neovim
+/// defaults are not reliable for our use-case. `ext` is lowercase.
+fn disambiguate(ext: &str, content: &str) -> Option<FileType> {
+    match ext {
+        // `.sch` is extremely overloaded (Eagle, KiCad, XML schematron/schema, Scheme...).
+        // Use a few cheap content checks to disambiguate.
+        "sch" => {
+            if let Some(first) = util::next_non_blank(content, 0) {
+                let first = first.trim_start();
+                if first.starts_with("EESchema") {
+                    return Some(FileType::EeschemaSchematic);
+                }
+                if first.starts_with("<?xml") || first.starts_with('<') {
+                    let head = get_lines(content, 20);
+                    if regex_is_match!(r"(?mi)<!DOCTYPE\s+eagle\b|<\s*eagle\b", head) {
+                        return Some(FileType::Eagle);
                     }
-                    if first.starts_with("<?xml") || first.starts_with('<') {
-                        let head = get_lines(content, 20);
-                        if regex_is_match!(r"(?mi)<!DOCTYPE\s+eagle\b|<\s*eagle\b", head) {
-                            return Some(FileType::Eagle);
+                    return Some(FileType::Xml);
+                }
+                if first.starts_with('(') {
+                    return Some(FileType::Scheme);
+                }
+                // Scheme files can start with `;` comment blocks before the first form.
+                if first.starts_with(';') {
+                    for line in content.lines().take(200) {
+                        let line = line.trim_start();
+                        if line.is_empty() || line.starts_with(';') {
+                            continue;
                         }
-                        return Some(FileType::Xml);
-                    }
-                    if first.starts_with('(') {
-                        return Some(FileType::Scheme);
-                    }
-                    // Scheme files can start with `;` comment blocks before the first form.
-                    if first.starts_with(';') {
-                        for line in content.lines().take(200) {
-                            let line = line.trim_start();
-                            if line.is_empty() || line.starts_with(';') {
-                                continue;
-                            }
-                            if line.starts_with('(') {
-                                return Some(FileType::Scheme);
-                            }
-                            break;
+                        if line.starts_with('(') {
+                            return Some(FileType::Scheme);
                         }
+                        break;
                     }
                 }
             }
-            // KiCad legacy boards.
-            "brd" => {
-                if regex_is_match!(r"(?m)^\s*PCBNEW-BOARD\b", get_lines(content, 3)) {
-                    return Some(FileType::KicadLegacyLayout);
-                }
+        }
+        // KiCad legacy boards.
+        "brd" => {
+            if regex_is_match!(r"(?m)^\s*PCBNEW-BOARD\b", get_lines(content, 3)) {
+                return Some(FileType::KicadLegacyLayout);
             }
-            // Limbo source (Plan 9 / Inferno) vs Brainfuck `.b`.
-            "b" => {
-                if regex_is_match!(r"(?m)^\s*implement\s+\w+\s*;", get_lines(content, 5)) {
-                    return Some(FileType::Limbo);
-                }
+        }
+        // Limbo source (Plan 9 / Inferno) vs Brainfuck `.b`.
+        "b" => {
+            if regex_is_match!(r"(?m)^\s*implement\s+\w+\s*;", get_lines(content, 5)) {
+                return Some(FileType::Limbo);
             }
-            // Scheme library files vs Salt SLS.
-            "sls" => {
-                if let Some(first) = util::next_non_blank(content, 0) {
-                    if first.trim_start().starts_with('(') {
-                        return Some(FileType::Scheme);
-                    }
+        }
+        // Scheme library files vs Salt SLS.
+        "sls" => {
+            if let Some(first) = util::next_non_blank(content, 0) {
+                if first.trim_start().starts_with('(') {
+                    return Some(FileType::Scheme);
                 }
             }
-            // macOS `.command` scripts are shell scripts without a shebang.
-            "command" => return Some(FileType::Sh),
-            // SourcePawn is a common conflict for `.sp` (Spice).
-            "sp" => {
-                if regex_is_match!(
-                    r"(?mi)^\s*#\s*include\s*<sourcemod>\b|^\s*public\s+Plugin:",
-                    get_lines(content, 80)
-                ) {
-                    return Some(FileType::Sourcepawn);
-                }
+        }
+        // macOS `.command` scripts are shell scripts without a shebang.
+        "command" => return Some(FileType::Sh),
+        // SourcePawn is a common conflict for `.sp` (Spice).
+        "sp" => {
+            if regex_is_match!(
+                r"(?mi)^\s*#\s*include\s*<sourcemod>\b|^\s*public\s+Plugin:",
+                get_lines(content, 80)
+            ) {
+                return Some(FileType::Sourcepawn);
             }
-            // `.fcgi` is frequently used for FastCGI wrappers; disambiguate by content.
-            "fcgi" => {
-                if util::find(content, 10, false, "<?php") {
-                    return Some(FileType::Php);
-                }
+        }
+        // `.fcgi` is frequently used for FastCGI wrappers; disambiguate by content.
+        "fcgi" if util::find(content, 10, false, "<?php") => {
+            return Some(FileType::Php);
+        }
+        // `.spec` is ambiguous (RPM spec vs various project “spec” scripts).
+        "spec" => {
+            let head = get_lines(content, 120);
+            // PyInstaller spec files are Python.
+            if regex_is_match!(r"(?m)^\s*\w+\s*=\s*Analysis\s*\(", head)
+                || regex_is_match!(r"(?m)^\s*from\\s+\\w+\\s+import\\s+\\w+", head)
+            {
+                return Some(FileType::Python);
             }
-            // `.spec` is ambiguous (RPM spec vs various project “spec” scripts).
-            "spec" => {
-                let head = get_lines(content, 120);
-                // PyInstaller spec files are Python.
-                if regex_is_match!(r"(?m)^\s*\w+\s*=\s*Analysis\s*\(", head)
-                    || regex_is_match!(r"(?m)^\s*from\\s+\\w+\\s+import\\s+\\w+", head)
-                {
-                    return Some(FileType::Python);
-                }
-                // RSpec / Ruby spec files are Ruby.
-                if regex_is_match!(r"(?m)^\s*describe\\b", head) && head.contains("require") {
-                    return Some(FileType::Ruby);
-                }
+            // RSpec / Ruby spec files are Ruby.
+            if regex_is_match!(r"(?m)^\s*describe\\b", head) && head.contains("require") {
+                return Some(FileType::Ruby);
             }
-            // `.shader` is ambiguous (Godot shaders vs plain GLSL).
-            "shader" => {
-                let head = get_lines(content, 60);
-                if regex_is_match!(r"(?mi)^\s*#\s*version\b", head) {
-                    return Some(FileType::Glsl);
-                }
-                if regex_is_match!(r"(?mi)^\s*shader_type\b", head) {
-                    return Some(FileType::GdShader);
-                }
+        }
+        // `.shader` is ambiguous (Godot shaders vs plain GLSL).
+        "shader" => {
+            let head = get_lines(content, 60);
+            if regex_is_match!(r"(?mi)^\s*#\s*version\b", head) {
+                return Some(FileType::Glsl);
             }
-            // `.gs` is ambiguous (Google Apps Script vs GrADS).
-            "gs" => {
-                let head = get_lines(content, 120);
-                // Prefer JavaScript when the content looks like JS / Apps Script.
-                if regex_is_match!(r"(?m)\b(function|var|let|const)\b", head)
-                    && (head.contains('{') || head.contains("=>"))
-                {
-                    return Some(FileType::JavaScript);
-                }
+            if regex_is_match!(r"(?mi)^\s*shader_type\b", head) {
+                return Some(FileType::GdShader);
             }
-            // `.frag` is ambiguous (GLSL fragments vs “.js.frag” concatenation snippets).
-            "frag" => {
-                let head = get_lines(content, 80);
-                if regex_is_match!(r"(?mi)^\s*#\s*version\b", head)
-                    || regex_is_match!(r"(?mi)^\s*(uniform|varying|precision)\b", head)
-                    || regex_is_match!(r"(?mi)\bvoid\s+main\s*\(", head)
-                {
-                    return Some(FileType::Glsl);
-                }
-                if regex_is_match!(r"(?m)^\s*\(function\b|^\s*function\b", head)
-                    || head.contains("window")
-                    || head.contains("angular")
-                {
-                    return Some(FileType::JavaScript);
-                }
+        }
+        // `.gs` is ambiguous (Google Apps Script vs GrADS).
+        "gs" => {
+            let head = get_lines(content, 120);
+            // Prefer JavaScript when the content looks like JS / Apps Script.
+            if regex_is_match!(r"(?m)\b(function|var|let|const)\b", head)
+                && (head.contains('{') || head.contains("=>"))
+            {
+                return Some(FileType::JavaScript);
             }
-            // PLSQL package headers/bodies are commonly stored as `.pks`/`.pkb`.
-            "pks" | "pkb" => return Some(FileType::Plsql),
-            // XML-ish extension buckets that are sometimes mapped to other config languages.
-            "workflow" | "pluginspec" => {
-                if regex_is_match!(r"(?m)^\s*<\?xml\b|^\s*<", get_lines(content, 5)) {
-                    return Some(FileType::Xml);
-                }
+        }
+        // `.frag` is ambiguous (GLSL fragments vs “.js.frag” concatenation snippets).
+        "frag" => {
+            let head = get_lines(content, 80);
+            if regex_is_match!(r"(?mi)^\s*#\s*version\b", head)
+                || regex_is_match!(r"(?mi)^\s*(uniform|varying|precision)\b", head)
+                || regex_is_match!(r"(?mi)\bvoid\s+main\s*\(", head)
+            {
+                return Some(FileType::Glsl);
             }
-            // `.t` is handled by the early disambiguation block.
-            // Wolfram notebooks (`.nb`) are text-based but are sometimes used as generic
-            // "notebook"/notes extensions in the wild. Prefer Mma only when the content
-            // clearly looks like a Wolfram notebook; otherwise treat as plain text.
-            "nb" | "nbp" => {
-                let first = util::next_non_blank(content, 0).unwrap_or("");
-                if first.trim_start().starts_with("(*")
-                    || regex_is_match!(r"(?m)^\s*Notebook\s*\[", get_lines(content, 40))
-                {
-                    return Some(FileType::Mma);
-                }
-                return Some(FileType::Text);
+            if regex_is_match!(r"(?m)^\s*\(function\b|^\s*function\b", head)
+                || head.contains("window")
+                || head.contains("angular")
+            {
+                return Some(FileType::JavaScript);
             }
-            _ => {}
         }
+        // PLSQL package headers/bodies are commonly stored as `.pks`/`.pkb`.
+        "pks" | "pkb" => return Some(FileType::Plsql),
+        // XML-ish extension buckets that are sometimes mapped to other config languages.
+        "workflow" | "pluginspec" => {
+            if regex_is_match!(r"(?m)^\s*<\?xml\b|^\s*<", get_lines(content, 5)) {
+                return Some(FileType::Xml);
+            }
+        }
+        // `.t` is handled by the early disambiguation block.
+        // Wolfram notebooks (`.nb`) are text-based but are sometimes used as generic
+        // "notebook"/notes extensions in the wild. Prefer Mma only when the content
+        // clearly looks like a Wolfram notebook; otherwise treat as plain text.
+        "nb" | "nbp" => {
+            let first = util::next_non_blank(content, 0).unwrap_or("");
+            if first.trim_start().starts_with("(*")
+                || regex_is_match!(r"(?m)^\s*Notebook\s*\[", get_lines(content, 40))
+            {
+                return Some(FileType::Mma);
+            }
+            return Some(FileType::Text);
+        }
+        _ => {}
     }
 
-    if path
-        .extension()
-        .and_then(|e| e.to_str())
-        .is_some_and(|e| e.eq_ignore_ascii_case("vhost"))
-        && regex_is_match!(r"(?m)^\s*server\s*\{", get_lines(content, 20))
-    {
+    if ext == "vhost" && regex_is_match!(r"(?m)^\s*server\s*\{", get_lines(content, 20)) {
         return Some(FileType::Nginx);
     }
 
-    if path
-        .extension()
-        .and_then(|e| e.to_str())
-        .is_some_and(|e| e.eq_ignore_ascii_case("j"))
+    if ext == "j"
         && regex_is_match!(
             r"(?mi)^\s*@(?:import|implementation|interface|protocol|end)\b",
             get_lines(content, 50)
@@ -326,10 +679,7 @@ pub fn try_detect(path: impl AsRef<Path>, content: &str) -> Option<FileType> {
         return Some(FileType::ObjJ);
     }
 
-    if path
-        .extension()
-        .and_then(|e| e.to_str())
-        .is_some_and(|e| e.eq_ignore_ascii_case("gi"))
+    if ext == "gi"
         && regex_is_match!(
             r"(?m)\b(?:InstallMethod|InstallGlobalFunction|TryNextMethod|DeclareOperation)\b",
             get_lines(content, 120)
@@ -338,70 +688,10 @@ pub fn try_detect(path: impl AsRef<Path>, content: &str) -> Option<FileType> {
         return Some(FileType::Gap);
     }
 
-    if path
-        .extension()
-        .and_then(|e| e.to_str())
-        .is_some_and(|e| e.eq_ignore_ascii_case("cp"))
-        && regex_is_match!(
-            r"(?mi)^\s*(?:module|import)\b",
-            get_lines(content, 50)
-        )
-    {
+    if ext == "cp" && regex_is_match!(r"(?mi)^\s*(?:module|import)\b", get_lines(content, 50)) {
         return Some(FileType::ComponentPascal);
     }
 
-    // patterns (non-negative priority)
-    let mut negative_prio_start_index = 0;
-    for (index, (match_full_path, regex, pat)) in PATTERN.iter().enumerate() {
-        if pat.priority.is_some_and(|prio| prio < 0) {
-            negative_prio_start_index = index;
-            break;
-        }
-        if match match_full_path {
-            true => path.to_str(),
-            false => path.file_name().and_then(|os_name| os_name.to_str()),
-        }
-        .is_none_or(|haystack| !regex.is_match(haystack))
-        {
-            continue;
-        }
-        if let Some(ft) = pat.resolver.resolve(path, content) {
-            return Some(ft);
-        }
-    }
-
-    // patterns (negative priority)
-    for (match_full_path, regex, pat) in PATTERN.iter().skip(negative_prio_start_index) {
-        if match match_full_path {
-            true => path.to_str(),
-            false => path.file_name().and_then(|os_name| os_name.to_str()),
-        }
-        .is_none_or(|haystack| !regex.is_match(haystack))
-        {
-            continue;
-        }
-        if let Some(ft) = pat.resolver.resolve(path, content) {
-            return Some(ft);
-        }
-    }
-
-    // file extension
-    if let Some(resolver) = path
-        .extension()
-        .and_then(|os_ext| os_ext.to_str())
-        .map(|ext| ext.to_ascii_lowercase())
-        .and_then(|ext| FILE_EXTENSION.get(ext.as_str()))
-    {
-        if let Some(ft) = resolver.resolve(path, content) {
-            return Some(ft);
-        }
-    }
-
-    // Final fallback: naive Bayes classifier
-    if let Some(ft) = classifier::classify(content) {
-        return Some(ft);
-    }
-
     None
 }
 
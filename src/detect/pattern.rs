@@ -848,6 +848,6 @@ pub(crate) static PATTERN: Lazy<Vec<(bool, &'static Regex, Pattern)>> = Lazy::ne
         (false, regex!(r"^.*\.tm\[Pp\]references$").deref(), Pattern::new(FileTypeResolver::Static(FileType::Xml), None)),
         (false, regex!(r"^.*\.tm\[Tt\]heme$").deref(), Pattern::new(FileTypeResolver::Static(FileType::Xml), None)),
     ];
-    vec.sort_unstable_by(|(_, _, pat1), (_, _, pat2)| pat2.priority.unwrap_or(0).cmp(&pat1.priority.unwrap_or(0)));
+    vec.sort_unstable_by_key(|(_, _, pat)| std::cmp::Reverse(pat.priority.unwrap_or(0)));
     vec
 });
@@ -1,50 +1,14 @@
 use crate::FileType;
 
+/// Lines of content [`detect_from_shebang`] looks at: the `#!` line, and the lines
+/// after it for shell wrappers.
+pub(crate) const SHEBANG_LINES: usize = 10;
+
 /// Detect file type from shebang line.
 ///
 /// Parses `#!` lines from file content and maps to languages using interpreters map.
 pub(crate) fn detect_from_shebang(content: &str) -> Option<FileType> {
-    let first_line = content.lines().next()?;
-
-    // Check for shebang
-    if !first_line.starts_with("#!") {
-        return None;
-    }
-
-    // Extract the interpreter part (everything after #!)
-    let shebang_content = &first_line[2..];
-
-    // Parse the shebang to get the interpreter name
-    // Handle cases like:
-    // - #!/bin/bash
-    // - #!/usr/bin/env python3
-    // - #!/usr/bin/perl -w
-    let parts: Vec<&str> = shebang_content.split_whitespace().collect();
-    if parts.is_empty() {
-        return None;
-    }
-
-    // Get the interpreter path or name
-    let interpreter = if parts[0].ends_with("env") && parts.len() > 1 {
-        // Examples:
-        // - #!/usr/bin/env python3 -> python3
-        // - #!/usr/bin/env VAR=1 sh -x -> sh
-        let mut it = parts.iter().skip(1);
-        let mut candidate: Option<&str> = None;
-        while let Some(tok) = it.next() {
-            // Skip environment assignments and env flags.
-            if tok.contains('=') || tok.starts_with('-') {
-                continue;
-            }
-            candidate = Some(tok);
-            break;
-        }
-        candidate?
-    } else {
-        // #!/bin/bash -> bash
-        // #!/usr/bin/perl -> perl
-        parts[0].rsplit('/').next().unwrap_or(parts[0])
-    };
+    let interpreter = interpreter(content)?;
 
     // Handle interpreters where trailing digits are significant, not version suffixes.
     // E.g. `perl6` / `raku` are different languages than `perl`.
@@ -66,7 +30,7 @@ pub(crate) fn detect_from_shebang(content: &str) -> Option<FileType> {
         FileType::Sh | FileType::Bash | FileType::Zsh | FileType::Ksh | FileType::Csh | FileType::Tcsh
     ) && content
         .lines()
-        .take(10)
+        .take(SHEBANG_LINES)
         .any(|l| l.trim_start().starts_with("exec ") && l.contains("scala"))
     {
         return Some(FileType::Scala);
@@ -75,6 +39,39 @@ pub(crate) fn detect_from_shebang(content: &str) -> Option<FileType> {
     Some(ft)
 }
 
+/// The interpreter named by the `#!` line of `content`, as written (`python3`, not
+/// `/usr/bin/env python3`).
+pub(crate) fn interpreter(content: &str) -> Option<&str> {
+    let first_line = content.lines().next()?;
+
+    // Check for shebang
+    let shebang_content = first_line.strip_prefix("#!")?;
+
+    // Parse the shebang to get the interpreter name
+    // Handle cases like:
+    // - #!/bin/bash
+    // - #!/usr/bin/env python3
+    // - #!/usr/bin/perl -w
+    let parts: Vec<&str> = shebang_content.split_whitespace().collect();
+    let first = *parts.first()?;
+
+    // Get the interpreter path or name
+    if first.ends_with("env") && parts.len() > 1 {
+        // Examples:
+        // - #!/usr/bin/env python3 -> python3
+        // - #!/usr/bin/env VAR=1 sh -x -> sh
+        // Skip environment assignments and env flags.
+        parts
+            .into_iter()
+            .skip(1)
+            .find(|tok| !tok.contains('=') && !tok.starts_with('-'))
+    } else {
+        // #!/bin/bash -> bash
+        // #!/usr/bin/perl -> perl
+        first.rsplit('/').next()
+    }
+}
+
 /// Hardcoded interpreter mappings built from hyperpolyglot's languages.yml
 /// TODO: Generate this from languages.yml in a build script
 fn interpreter_to_filetype(interpreter: &str) -> Option<FileType> {
@@ -171,4 +168,11 @@ mod tests {
         assert_eq!(None, detect_from_shebang("echo hello"));
         assert_eq!(None, detect_from_shebang(""));
     }
+
+    #[test]
+    fn test_interpreter() {
+        assert_eq!(Some("python3"), interpreter("#!/usr/bin/env -S python3 -u\n"));
+        assert_eq!(Some("perl"), interpreter("#!/usr/bin/perl -w\n"));
+        assert_eq!(None, interpreter("#!\n"));
+    }
 }
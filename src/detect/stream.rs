@@ -1,12 +1,10 @@
 //! Stream-based file type detection.
 
-use super::{classifier, filename::FILENAME, heuristics, path_suffix::PATH_SUFFIX, shebang};
+use super::{PathDetection, detect_path};
 use crate::FileType;
 use std::pin::Pin;
 use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
 
-const MAX_CONTENT_SIZE_BYTES: usize = 51200;
-
 /// A reader that can peek at content without consuming the stream.
 ///
 /// This is useful for file type detection where you need to examine the content
@@ -170,9 +168,8 @@ impl<R: AsyncRead + Unpin> AsyncRead for PeekableReader<R> {
 
 /// Detect file type from path and peekable reader using the detection pipeline.
 ///
-/// This uses the existing detector building blocks (filename, extension, shebang,
-/// heuristics, classifier) directly, following the same pipeline as `detect()`
-/// but working with a stream.
+/// The path decides first, see [`detect_path`](crate::detect_path): the content is
+/// only peeked at when it can't, up to [`Pending::max_bytes`](crate::Pending::max_bytes).
 ///
 /// Returns `Ok((Some(FileType), PeekableReader))` on successful detection,
 /// `Ok((None, PeekableReader))` if detection fails, or `Err((io_error, PeekableReader))`
@@ -186,65 +183,20 @@ where
 {
     let mut reader = reader;
 
-    // Path suffix detection
-    for (suffix, resolver) in PATH_SUFFIX {
-        if path.ends_with(suffix) {
-            let first_line_bytes = match reader.peek_first_line().await {
-                Ok(line) => line,
-                Err(e) => return Err((e, reader)),
-            };
-            let first_line = std::str::from_utf8(&first_line_bytes).unwrap_or("");
-            if let Some(ft) = resolver.resolve(path, first_line) {
-                return Ok((Some(ft), reader));
-            }
-        }
-    }
-
-    // Filename detection
-    if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-        if let Some(resolver) = FILENAME.get(filename) {
-            let first_line_bytes = match reader.peek_first_line().await {
-                Ok(line) => line,
-                Err(e) => return Err((e, reader)),
-            };
-            let first_line = std::str::from_utf8(&first_line_bytes).unwrap_or("");
-            if let Some(ft) = resolver.resolve(path, first_line) {
-                return Ok((Some(ft), reader));
-            }
-        }
-    }
-
-    // Read content for remaining detection stages
-    let content_bytes = match reader.peek_content(MAX_CONTENT_SIZE_BYTES).await {
+    let pending = match detect_path(path) {
+        PathDetection::Resolved(ft, _) => return Ok((Some(ft), reader)),
+        PathDetection::NeedsContent(pending) => pending,
+    };
+    let max_bytes = pending.max_bytes();
+    let content_bytes = match reader.peek_content(max_bytes).await {
         Ok(content) => content,
         Err(e) => return Err((e, reader)),
     };
 
     let content_str = String::from_utf8_lossy(&content_bytes);
-    let content = truncate_to_char_boundary(&content_str, MAX_CONTENT_SIZE_BYTES);
-
-    // Shebang detection
-    if let Some(ft) = shebang::detect_from_shebang(content) {
-        return Ok((Some(ft), reader));
-    }
-
-    // Extension detection for heuristics
-    let ext = path.extension().and_then(|e| e.to_str());
-
-    // Heuristics for ambiguous extensions
-    if let Some(extension) = ext {
-        let dotted_extension = format!(".{extension}");
-        if let Some(ft) = heuristics::apply_heuristics(&dotted_extension, path, content) {
-            return Ok((Some(ft), reader));
-        }
-    }
-
-    // Classifier fallback
-    if let Some(ft) = classifier::classify(content) {
-        return Ok((Some(ft), reader));
-    }
-
-    Ok((None, reader))
+    let content = truncate_to_char_boundary(&content_str, max_bytes);
+    let detection = pending.detect(content);
+    Ok((detection.map(|detection| detection.file_type), reader))
 }
 
 fn truncate_to_char_boundary(s: &str, mut max: usize) -> &str {
@@ -289,6 +241,16 @@ mod tests {
         assert_eq!(ft, Some(FileType::Rust));
     }
 
+    #[tokio::test]
+    async fn test_detect_with_reader_skips_content_the_path_decides() {
+        let path = std::path::Path::new("cmd/main.go");
+        let reader = PeekableReader::new(Cursor::new(&b"package main\n"[..]), 51200);
+
+        let (ft, reader) = detect_with_reader(path, reader).await.unwrap();
+        assert_eq!(ft, Some(FileType::Go));
+        assert!(reader.buffer.is_empty());
+    }
+
     #[tokio::test]
     async fn test_detect_with_reader_no_extension() {
         let path = std::path::Path::new("Makefile");
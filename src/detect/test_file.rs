@@ -0,0 +1,125 @@
+//! Test file classification.
+//!
+//! Each language has its own conventions for where tests live and what they are called:
+//! `*_test.go` next to the code, `src/test/java/` in a Maven layout, `__tests__/` for
+//! Jest, `t/*.t` for Perl. Paths are matched repository-relative, with `/` as the
+//! separator.
+
+use std::path::Path;
+
+use lazy_regex::regex_is_match;
+
+use super::vendor::normalize;
+use crate::FileType;
+
+/// Check if a file of type `file_type` is test code by the conventions of its language.
+///
+/// The path should be relative to the repository root. Languages without a test
+/// convention palate knows of never have test files.
+///
+/// # Example
+/// ```
+/// use palate::{FileType, is_test_file};
+///
+/// assert!(is_test_file("pkg/server/handler_test.go", FileType::Go));
+/// assert!(is_test_file("src/test/java/org/AppTest.java", FileType::Java));
+/// assert!(is_test_file("web/__tests__/App.tsx", FileType::Tsx));
+/// assert!(is_test_file("t/basic.t", FileType::Perl));
+/// assert!(!is_test_file("src/main.rs", FileType::Rust));
+/// ```
+pub fn is_test_file(path: impl AsRef<Path>, file_type: FileType) -> bool {
+    let path = normalize(path.as_ref());
+    let path = path.as_ref();
+    match file_type {
+        FileType::Go => regex_is_match!(r"_test\.go$", path),
+        FileType::Rust => regex_is_match!(r"(^|/)tests(/|\.rs$)", path),
+        FileType::Python => regex_is_match!(
+            r"(^|/)(tests?/|test_[^/]*\.pyi?$|[^/]*_test\.pyi?$|conftest\.py$)",
+            path
+        ),
+        FileType::JavaScript
+        | FileType::Jsx
+        | FileType::TypeScript
+        | FileType::Tsx
+        | FileType::Vue
+        | FileType::Svelte => regex_is_match!(
+            r"\.(test|spec)\.[cm]?[jt]sx?$|(^|/)(__tests__|tests?|spec)/",
+            path
+        ),
+        FileType::Java | FileType::Kotlin | FileType::Scala | FileType::Groovy => {
+            regex_is_match!(
+                r"(^|/)src/[a-zA-Z]*[tT]est/|(^|/)[^/]*(Tests?|Spec)\.[a-z]+$",
+                path
+            )
+        }
+        FileType::CSharp | FileType::FSharp | FileType::Vb => regex_is_match!(
+            r"(^|/)[^/]*Tests?\.[a-z]+$|(^|/)[^/]*\.(Unit|Integration)?Tests?/|(^|/)[tT]ests?/",
+            path
+        ),
+        FileType::Ruby => regex_is_match!(
+            r"_(spec|test)\.rb$|(^|/)test_[^/]*\.rb$|(^|/)(spec|test)/",
+            path
+        ),
+        FileType::Perl | FileType::Raku => regex_is_match!(r"\.(t|rakutest)$|(^|/)t/", path),
+        FileType::Php => regex_is_match!(r"Test\.php$|(^|/)[tT]ests?/", path),
+        FileType::Elixir => regex_is_match!(r"_test\.exs$|(^|/)test/", path),
+        FileType::Erlang => regex_is_match!(r"_(SUITE|tests)\.erl$|(^|/)test/", path),
+        FileType::Swift => regex_is_match!(r"Tests?\.swift$|(^|/)Tests/", path),
+        FileType::ObjC | FileType::ObjCpp => regex_is_match!(r"Tests?\.mm?$|(^|/)Tests/", path),
+        FileType::Dart => regex_is_match!(r"_test\.dart$|(^|/)test/", path),
+        FileType::C | FileType::Cpp => regex_is_match!(
+            r"_(unit)?tests?\.[a-z+]+$|(^|/)test_[^/]*$|(^|/)tests?/",
+            path
+        ),
+        FileType::Haskell => regex_is_match!(r"Spec\.hs$|(^|/)tests?/", path),
+        FileType::Clojure => regex_is_match!(r"_test\.clj[cs]?$|(^|/)test/", path),
+        FileType::Lua => regex_is_match!(r"_spec\.lua$|(^|/)spec/", path),
+        FileType::Crystal => regex_is_match!(r"_spec\.cr$|(^|/)spec/", path),
+        FileType::Gleam => regex_is_match!(r"_test\.gleam$|(^|/)test/", path),
+        FileType::Julia => regex_is_match!(r"(^|/)test/", path),
+        FileType::R => regex_is_match!(r"(^|/)tests/", path),
+        FileType::Elm | FileType::Nim => regex_is_match!(r"(^|/)tests/", path),
+        FileType::Zig => regex_is_match!(r"_test\.zig$|(^|/)tests?/", path),
+        FileType::Sh | FileType::Bash | FileType::Zsh => {
+            regex_is_match!(r"_test\.(sh|bash|zsh)$|\.bats$|(^|/)tests?/", path)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conventions() {
+        for (path, file_type) in [
+            ("tests/cli.rs", FileType::Rust),
+            ("src/scan/tests.rs", FileType::Rust),
+            ("./tests/test_api.py", FileType::Python),
+            ("pkg/conftest.py", FileType::Python),
+            ("pkg/util_test.py", FileType::Python),
+            ("src/app.spec.ts", FileType::TypeScript),
+            ("lib/parse.test.mjs", FileType::JavaScript),
+            ("app/src/androidTest/kotlin/MainTest.kt", FileType::Kotlin),
+            ("Palate.Tests/ScannerTests.cs", FileType::CSharp),
+            ("spec/models/user_spec.rb", FileType::Ruby),
+            ("t/lib/helper.pm", FileType::Perl),
+            ("test/palate_test.exs", FileType::Elixir),
+            ("base/strings_unittest.cc", FileType::Cpp),
+        ] {
+            assert!(is_test_file(path, file_type), "{path} should be a test");
+        }
+        for (path, file_type) in [
+            ("src/main.rs", FileType::Rust),
+            ("src/testing.rs", FileType::Rust),
+            ("pkg/testdata/fixture.go", FileType::Go),
+            ("src/contest.py", FileType::Python),
+            ("src/main/java/org/Latest.java", FileType::Java),
+            ("src/app.ts", FileType::TypeScript),
+            ("tests/fixture.json", FileType::Json),
+        ] {
+            assert!(!is_test_file(path, file_type), "{path} shouldn't be a test");
+        }
+    }
+}
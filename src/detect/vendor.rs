@@ -0,0 +1,83 @@
+//! Vendored and documentation path classification.
+//!
+//! The regex lists come from Linguist's `vendor.yml` and `documentation.yml` and are
+//! matched against repository-relative paths using `/` as the separator.
+
+use std::{borrow::Cow, path::Path};
+
+use once_cell::sync::Lazy;
+use regex::RegexSet;
+
+// Include the generated path classification patterns
+include!("../codegen/path-classification-patterns.rs");
+
+static VENDOR: Lazy<RegexSet> = Lazy::new(|| RegexSet::new(VENDOR_PATTERNS).unwrap());
+static DOCUMENTATION: Lazy<RegexSet> = Lazy::new(|| RegexSet::new(DOCUMENTATION_PATTERNS).unwrap());
+
+/// Check if a path is vendored (third-party dependencies, build outputs, bundled tools).
+///
+/// The path should be relative to the repository root. Directories can be tested by
+/// appending a trailing `/`, which allows pruning whole trees such as `node_modules/`.
+///
+/// # Example
+/// ```
+/// use palate::is_vendored;
+///
+/// assert!(is_vendored("node_modules/left-pad/index.js"));
+/// assert!(is_vendored("web/dist/app.min.js"));
+/// assert!(!is_vendored("src/main.rs"));
+/// ```
+pub fn is_vendored(path: impl AsRef<Path>) -> bool {
+    VENDOR.is_match(&normalize(path.as_ref()))
+}
+
+/// Check if a path is documentation (docs directories, READMEs, licenses, changelogs).
+///
+/// The path should be relative to the repository root.
+///
+/// # Example
+/// ```
+/// use palate::is_documentation;
+///
+/// assert!(is_documentation("docs/index.md"));
+/// assert!(is_documentation("crates/foo/README.md"));
+/// assert!(!is_documentation("src/docs.rs"));
+/// ```
+pub fn is_documentation(path: impl AsRef<Path>) -> bool {
+    DOCUMENTATION.is_match(&normalize(path.as_ref()))
+}
+
+/// Render a path the way Linguist sees it: `/`-separated and without `./` prefixes.
+pub(super) fn normalize(path: &Path) -> Cow<'_, str> {
+    let path = path.to_string_lossy();
+    if cfg!(windows) {
+        let path = path.replace('\\', "/");
+        return Cow::Owned(path.trim_start_matches("./").to_owned());
+    }
+    match path {
+        Cow::Borrowed(path) => Cow::Borrowed(path.trim_start_matches("./")),
+        Cow::Owned(path) => Cow::Owned(path.trim_start_matches("./").to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vendored_directories() {
+        assert!(is_vendored("node_modules/"));
+        assert!(is_vendored("deps/http_parser/http_parser.c"));
+        assert!(is_vendored("./vendor/github.com/pkg/errors/errors.go"));
+        assert!(is_vendored("tools/cpplint.py"));
+        assert!(!is_vendored("src/deps/mod.rs"));
+    }
+
+    #[test]
+    fn test_documentation_paths() {
+        assert!(is_documentation("Documentation/filesystems/ext4.rst"));
+        assert!(is_documentation("LICENSE"));
+        assert!(is_documentation("CHANGELOG.md"));
+        assert!(!is_documentation("src/lib/examples/foo.rs"));
+    }
+}
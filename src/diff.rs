@@ -0,0 +1,549 @@
+//! Per-language churn of unified diffs.
+//!
+//! [`parse`] reads the output of `git diff`, `git format-patch` or `diff -u` into one
+//! [`FileChange`] per touched file, with the lines it adds and removes and its file
+//! type. The type is detected from the path and the lines the hunks show of the file,
+//! so content-based stages like shebangs and heuristics still apply. [`churn`] adds the
+//! changes up per language.
+//!
+//! # Example
+//! ```
+//! use palate::{FileType, diff};
+//!
+//! let patch = "\
+//! diff --git a/src/main.rs b/src/main.rs
+//! --- a/src/main.rs
+//! +++ b/src/main.rs
+//! @@ -1,2 +1,2 @@
+//!  fn main() {
+//! -    println!(\"hi\");
+//! +    println!(\"hello\");
+//! ";
+//! let files = diff::parse(patch);
+//! assert_eq!(FileType::Rust, files[0].file_type);
+//! let churn = diff::churn(&files);
+//! assert_eq!(1, churn.languages[&FileType::Rust].added);
+//! ```
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{DetectionStage, FileType, detect::detect_with_stage};
+
+/// Number of bytes of hunk lines kept per file for detection.
+const MAX_CONTENT: usize = 51_200;
+
+/// One file touched by a diff.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FileChange {
+    /// The path before the change; `None` for added files.
+    pub old_path: Option<PathBuf>,
+    /// The path after the change; `None` for deleted files.
+    pub new_path: Option<PathBuf>,
+    /// File type of the file, [`FileType::Text`] with [`DetectionStage::Fallback`] if
+    /// nothing recognized it.
+    pub file_type: FileType,
+    /// The stage that decided the file type.
+    pub stage: DetectionStage,
+    /// Added lines.
+    pub added: u64,
+    /// Removed lines.
+    pub removed: u64,
+    /// Whether the diff only says that a binary file changed.
+    pub binary: bool,
+}
+
+impl FileChange {
+    /// The path after the change, or before it for deleted files.
+    pub fn path(&self) -> &Path {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or(Path::new(""))
+    }
+
+    /// Whether the file was renamed or copied.
+    pub fn is_rename(&self) -> bool {
+        matches!((&self.old_path, &self.new_path), (Some(old), Some(new)) if old != new)
+    }
+}
+
+/// Added and removed lines of one language.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct LanguageChurn {
+    /// Number of touched files.
+    pub files: usize,
+    /// Added lines.
+    pub added: u64,
+    /// Removed lines.
+    pub removed: u64,
+}
+
+impl LanguageChurn {
+    /// Add the churn of `other` to this.
+    pub fn merge(&mut self, other: LanguageChurn) {
+        self.files += other.files;
+        self.added += other.added;
+        self.removed += other.removed;
+    }
+}
+
+/// Churn per language, see [`churn`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct ChurnBreakdown {
+    /// Churn of every file type that was touched.
+    pub languages: HashMap<FileType, LanguageChurn>,
+}
+
+impl ChurnBreakdown {
+    /// Churn over all languages.
+    pub fn total(&self) -> LanguageChurn {
+        let mut total = LanguageChurn::default();
+        for churn in self.languages.values() {
+            total.merge(*churn);
+        }
+        total
+    }
+}
+
+/// Add up the changes per file type.
+pub fn churn(files: &[FileChange]) -> ChurnBreakdown {
+    let mut breakdown = ChurnBreakdown::default();
+    for file in files {
+        breakdown
+            .languages
+            .entry(file.file_type)
+            .or_default()
+            .merge(LanguageChurn {
+                files: 1,
+                added: file.added,
+                removed: file.removed,
+            });
+    }
+    breakdown
+}
+
+/// Parse a unified diff into the files it touches, in diff order, detecting their file
+/// types with [`try_detect_with_stage`]. Anything that isn't part of a diff, like the
+/// commit message of a patch, is skipped.
+pub fn parse(diff: &str) -> Vec<FileChange> {
+    parse_with(diff, detect_with_stage)
+}
+
+/// Like [`parse`], with `detect` deciding the file type from a path and the lines the
+/// hunks show of the file; `None` falls back to [`FileType::Text`].
+pub fn parse_with(
+    diff: &str,
+    mut detect: impl FnMut(&Path, &str) -> Option<(FileType, DetectionStage)>,
+) -> Vec<FileChange> {
+    let mut parser = Parser::default();
+    for line in diff.lines() {
+        parser.line(line);
+    }
+    parser.finish();
+    parser
+        .files
+        .into_iter()
+        .map(|file| {
+            let mut change = FileChange {
+                old_path: file.old_path,
+                new_path: file.new_path,
+                file_type: FileType::Text,
+                stage: DetectionStage::Fallback,
+                added: file.added,
+                removed: file.removed,
+                binary: file.binary,
+            };
+            if let Some(detected) = detect(change.path(), &file.content) {
+                (change.file_type, change.stage) = detected;
+            }
+            change
+        })
+        .collect()
+}
+
+/// A file while its part of the diff is read.
+#[derive(Default)]
+struct PendingFile {
+    old_path: Option<PathBuf>,
+    new_path: Option<PathBuf>,
+    added: u64,
+    removed: u64,
+    binary: bool,
+    /// The lines of the file the hunks show: context and added lines, or removed lines
+    /// for deleted files.
+    content: String,
+    /// Whether the `---` and `+++` lines of the file were read; in a plain diff another
+    /// pair starts the next file.
+    headers: bool,
+}
+
+#[derive(Default)]
+struct Parser {
+    files: Vec<PendingFile>,
+    current: Option<PendingFile>,
+    /// Lines of the current hunk still to come, on the old and the new side.
+    hunk: Option<(u64, u64)>,
+    /// A `---` line waiting for its `+++` line.
+    old_header: Option<Option<PathBuf>>,
+}
+
+impl Parser {
+    fn line(&mut self, line: &str) {
+        if let Some((old, new)) = self.hunk {
+            if self.hunk_line(line, old, new) {
+                return;
+            }
+        }
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            self.start();
+            let file = self.current.get_or_insert_with(PendingFile::default);
+            if let Some((old, new)) = git_paths(paths) {
+                file.old_path = Some(old);
+                file.new_path = Some(new);
+            }
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            self.old_header = Some(header_path(path, "a/"));
+        } else if let (Some(path), Some(old)) = (line.strip_prefix("+++ "), &self.old_header) {
+            let old = old.clone();
+            self.old_header = None;
+            // Plain diffs have no `diff --git` line, so a new header pair starts a file.
+            if self.current.as_ref().is_some_and(|file| file.headers) {
+                self.start();
+            }
+            let file = self.current.get_or_insert_with(PendingFile::default);
+            file.old_path = old;
+            file.new_path = header_path(path, "b/");
+            file.headers = true;
+        } else if let Some(range) = line.strip_prefix("@@ ") {
+            if self.current.is_some() {
+                self.hunk = hunk_lengths(range);
+            }
+        } else if let Some(file) = &mut self.current {
+            if let Some(path) = line
+                .strip_prefix("rename from ")
+                .or_else(|| line.strip_prefix("copy from "))
+            {
+                file.old_path = Some(unquote(path));
+            } else if let Some(path) = line
+                .strip_prefix("rename to ")
+                .or_else(|| line.strip_prefix("copy to "))
+            {
+                file.new_path = Some(unquote(path));
+            } else if line.starts_with("new file mode ") {
+                file.old_path = None;
+            } else if line.starts_with("deleted file mode ") {
+                file.new_path = None;
+            } else if line == "GIT binary patch"
+                || (line.starts_with("Binary files ") && line.ends_with(" differ"))
+            {
+                file.binary = true;
+            }
+        }
+    }
+
+    /// Count a line of the current hunk; `false` once the hunk is over.
+    fn hunk_line(&mut self, line: &str, old: u64, new: u64) -> bool {
+        let Some(file) = &mut self.current else {
+            return false;
+        };
+        let (old, new) = match line.as_bytes().first() {
+            // Marks the line before it, and doesn't count.
+            Some(b'\\') => return true,
+            Some(b'+') if new > 0 => {
+                file.added += 1;
+                file.keep(&line[1..], file.new_path.is_some());
+                (old, new - 1)
+            }
+            Some(b'-') if old > 0 => {
+                file.removed += 1;
+                file.keep(&line[1..], file.new_path.is_none());
+                (old - 1, new)
+            }
+            // Some tools strip the space of empty context lines.
+            Some(b' ') | None if old > 0 && new > 0 => {
+                file.keep(line.get(1..).unwrap_or_default(), true);
+                (old - 1, new - 1)
+            }
+            _ => {
+                self.hunk = None;
+                return false;
+            }
+        };
+        self.hunk = (old > 0 || new > 0).then_some((old, new));
+        true
+    }
+
+    /// Finish the current file, if any.
+    fn start(&mut self) {
+        self.hunk = None;
+        self.old_header = None;
+        if let Some(file) = self.current.take() {
+            self.files.push(file);
+        }
+    }
+
+    fn finish(&mut self) {
+        self.start();
+    }
+}
+
+impl PendingFile {
+    fn keep(&mut self, line: &str, keep: bool) {
+        if keep && self.content.len() < MAX_CONTENT {
+            self.content.push_str(line);
+            self.content.push('\n');
+        }
+    }
+}
+
+/// The lengths of the old and new side of a hunk from `-a,b +c,d @@`; a missing
+/// length is 1.
+fn hunk_lengths(range: &str) -> Option<(u64, u64)> {
+    let mut sides = range.split_ascii_whitespace();
+    let length = |side: &str| match side.split_once(',') {
+        Some((_, length)) => length.parse().ok(),
+        None => Some(1),
+    };
+    let old = length(sides.next()?.strip_prefix('-')?)?;
+    let new = length(sides.next()?.strip_prefix('+')?)?;
+    Some((old, new))
+}
+
+/// The path of a `---` or `+++` line: `None` for `/dev/null`, without the timestamp
+/// `diff -u` adds and without git's `a/` or `b/` prefix.
+fn header_path(header: &str, prefix: &str) -> Option<PathBuf> {
+    let path = if header.starts_with('"') {
+        header
+    } else {
+        header.split('\t').next().unwrap_or(header).trim_end()
+    };
+    if path == "/dev/null" {
+        return None;
+    }
+    let path = unquote(path);
+    Some(match path.strip_prefix(prefix) {
+        Ok(stripped) => stripped.to_path_buf(),
+        Err(_) => path,
+    })
+}
+
+/// The paths of a `diff --git a/old b/new` line, which is only unambiguous when the
+/// paths are quoted or the same.
+fn git_paths(paths: &str) -> Option<(PathBuf, PathBuf)> {
+    if let Some(rest) = paths.strip_prefix('"') {
+        let end = closing_quote(rest)?;
+        let old = unquote(&paths[..end + 2]);
+        let new = unquote(paths[end + 2..].trim_start());
+        return Some((strip(old, "a/"), strip(new, "b/")));
+    }
+    if paths.ends_with('"') {
+        let start = paths.rfind(" \"")?;
+        return Some((
+            strip(PathBuf::from(&paths[..start]), "a/"),
+            strip(unquote(&paths[start + 1..]), "b/"),
+        ));
+    }
+    // Both halves are the same length when the file wasn't renamed.
+    let half = paths.len().checked_sub(1)? / 2;
+    let (old, new) = (paths.get(..half)?, paths.get(half + 1..)?);
+    let old = old.strip_prefix("a/")?;
+    (Some(old) == new.strip_prefix("b/")).then(|| (PathBuf::from(old), PathBuf::from(old)))
+}
+
+fn strip(path: PathBuf, prefix: &str) -> PathBuf {
+    match path.strip_prefix(prefix) {
+        Ok(stripped) => stripped.to_path_buf(),
+        Err(_) => path,
+    }
+}
+
+/// The index of the quote that closes a quoted path, in the path after the opening quote.
+fn closing_quote(rest: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in rest.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(index),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// A path as git prints it: as is, or in double quotes with C-style escapes and octal
+/// escapes for bytes of non-ASCII characters.
+fn unquote(path: &str) -> PathBuf {
+    let Some(quoted) = path
+        .strip_prefix('"')
+        .and_then(|path| path.strip_suffix('"'))
+    else {
+        return PathBuf::from(path);
+    };
+    let mut bytes = Vec::with_capacity(quoted.len());
+    let mut chars = quoted.bytes().peekable();
+    while let Some(b) = chars.next() {
+        if b != b'\\' {
+            bytes.push(b);
+            continue;
+        }
+        match chars.next() {
+            Some(b'n') => bytes.push(b'\n'),
+            Some(b't') => bytes.push(b'\t'),
+            Some(b'r') => bytes.push(b'\r'),
+            Some(b'a') => bytes.push(0x07),
+            Some(b'b') => bytes.push(0x08),
+            Some(b'f') => bytes.push(0x0c),
+            Some(b'v') => bytes.push(0x0b),
+            Some(digit @ b'0'..=b'7') => {
+                let mut value = u32::from(digit - b'0');
+                for _ in 0..2 {
+                    match chars.peek() {
+                        Some(&next @ b'0'..=b'7') => {
+                            value = value * 8 + u32::from(next - b'0');
+                            chars.next();
+                        }
+                        _ => break,
+                    }
+                }
+                bytes.push(value as u8);
+            }
+            Some(other) => bytes.push(other),
+            None => bytes.push(b'\\'),
+        }
+    }
+    PathBuf::from(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = "\
+From 1234 Mon Sep 17 00:00:00 2001
+Subject: [PATCH] Move the tool to Python
+
+--- this is not a header
+diff --git a/tools/run b/tools/run
+new file mode 100755
+index 0000000..1111111
+--- /dev/null
++++ b/tools/run
+@@ -0,0 +1,3 @@
++#!/usr/bin/env python3
++-- not a header
++print('hi')
+diff --git a/old.sh b/old.sh
+deleted file mode 100644
+index 2222222..0000000
+--- a/old.sh
++++ /dev/null
+@@ -1,2 +0,0 @@
+-echo hi
+-echo bye
+diff --git a/lib/a b.rb b/lib/c.rb
+similarity index 90%
+rename from lib/a b.rb
+rename to lib/c.rb
+index 3333333..4444444 100644
+--- a/lib/a b.rb
++++ b/lib/c.rb
+@@ -1,3 +1,3 @@
+ class A
+-  def x; end
++  def y; end
+
+\\ No newline at end of file
+diff --git a/logo.png b/logo.png
+index 5555555..6666666 100644
+Binary files a/logo.png and b/logo.png differ
+diff --git \"a/docs/caf\\303\\251.md\" \"b/docs/caf\\303\\251.md\"
+index 7777777..8888888 100644
+--- \"a/docs/caf\\303\\251.md\"
++++ \"b/docs/caf\\303\\251.md\"
+@@ -1 +1,2 @@
+ # Café
++More.
+--
+2.42.0
+";
+
+    #[test]
+    fn test_parse_git_diff() {
+        let files = parse(PATCH);
+        let summary: Vec<_> = files
+            .iter()
+            .map(|file| {
+                (
+                    file.old_path
+                        .as_ref()
+                        .map(|path| path.display().to_string()),
+                    file.new_path
+                        .as_ref()
+                        .map(|path| path.display().to_string()),
+                    file.file_type,
+                    file.added,
+                    file.removed,
+                )
+            })
+            .collect();
+        let path = |path: &str| Some(path.to_string());
+        assert_eq!(
+            vec![
+                (None, path("tools/run"), FileType::Python, 3, 0),
+                (path("old.sh"), None, FileType::Sh, 0, 2),
+                (path("lib/a b.rb"), path("lib/c.rb"), FileType::Ruby, 1, 1),
+                (path("logo.png"), path("logo.png"), FileType::Text, 0, 0),
+                (
+                    path("docs/café.md"),
+                    path("docs/café.md"),
+                    FileType::Markdown,
+                    1,
+                    0
+                ),
+            ],
+            summary
+        );
+        assert_eq!(DetectionStage::Shebang, files[0].stage);
+        assert!(files[2].is_rename() && !files[4].is_rename());
+        assert!(files[3].binary);
+        assert_eq!(DetectionStage::Fallback, files[3].stage);
+    }
+
+    #[test]
+    fn test_plain_diff_and_churn() {
+        let diff = "\
+--- app/main.py\t2024-01-01 10:00:00
++++ app/main.py\t2024-01-02 10:00:00
+@@ -1,2 +1,3 @@
+ import os
++import sys
+ print(os)
+--- app/util.py
++++ app/util.py
+@@ -5 +5 @@
+-x = 1
++x = 2
+";
+        let files = parse(diff);
+        assert_eq!(2, files.len());
+        assert_eq!(Path::new("app/util.py"), files[1].path());
+        let churn = churn(&files);
+        assert_eq!(
+            LanguageChurn {
+                files: 2,
+                added: 2,
+                removed: 1
+            },
+            churn.languages[&FileType::Python]
+        );
+        assert_eq!(churn.languages[&FileType::Python], churn.total());
+    }
+}
@@ -11,7 +11,12 @@ pub use list::FileType;
 
 #[cfg(feature = "detect")]
 mod detect;
+#[cfg(feature = "detect")]
+pub mod diff;
+mod linguist;
 mod list;
+#[cfg(feature = "detect")]
+pub mod scan;
 
 /// Internal resolver for file type detection.
 #[derive(Clone)]
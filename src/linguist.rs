@@ -0,0 +1,52 @@
+//! Metadata from GitHub Linguist's `languages.yml`.
+
+use crate::FileType;
+
+// Include the generated Linguist language lookup
+include!("codegen/linguist-languages.rs");
+
+impl FileType {
+    /// Display name of the matching [Linguist](https://github.com/github-linguist/linguist)
+    /// language, if there is one.
+    ///
+    /// # Example
+    /// ```
+    /// use palate::FileType;
+    ///
+    /// assert_eq!(Some("C#"), FileType::CSharp.linguist_name());
+    /// assert_eq!(Some("Rust"), FileType::Rust.linguist_name());
+    /// ```
+    pub const fn linguist_name(self) -> Option<&'static str> {
+        linguist_name(self)
+    }
+
+    /// Linguist's color for the language as a `#rrggbb` hex string, if it has one.
+    ///
+    /// # Example
+    /// ```
+    /// use palate::FileType;
+    ///
+    /// assert_eq!(Some("#dea584"), FileType::Rust.linguist_color());
+    /// assert_eq!(None, FileType::A2ps.linguist_color());
+    /// ```
+    pub const fn linguist_color(self) -> Option<&'static str> {
+        linguist_color(self)
+    }
+
+    /// Human-readable name for reports: the Linguist language name when known, the
+    /// canonical filetype otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use palate::FileType;
+    ///
+    /// assert_eq!("JavaScript", FileType::JavaScript.display_name());
+    /// assert_eq!("a2ps", FileType::A2ps.display_name());
+    /// ```
+    pub const fn display_name(self) -> &'static str {
+        match linguist_name(self) {
+            Some(name) => name,
+            None => self.canonical(),
+        }
+    }
+}
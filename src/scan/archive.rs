@@ -0,0 +1,248 @@
+//! Reading the members of tar, tar.gz, tar.zst and zip archives.
+
+use std::{
+    io::{self, Cursor, Read, Seek},
+    path::Path,
+};
+
+use flate2::read::GzDecoder;
+use ruzstd::decoding::StreamingDecoder;
+
+/// Archive formats a scan can look into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Format {
+    Tar,
+    TarGz,
+    TarZst,
+    Zip,
+}
+
+impl Format {
+    /// The format of the archive starting with `head`; compressed files only count when
+    /// they hold a tar archive.
+    pub(super) fn of(head: &[u8]) -> Option<Self> {
+        match infer::get(head)?.mime_type() {
+            "application/x-tar" => Some(Self::Tar),
+            "application/zip" => Some(Self::Zip),
+            "application/gzip" => holds_tar(GzDecoder::new(head)).then_some(Self::TarGz),
+            "application/zstd" => {
+                let decoder = StreamingDecoder::new(head).ok()?;
+                holds_tar(decoder).then_some(Self::TarZst)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Whether the decompressed stream starts with a tar header. A truncated stream is
+/// fine as long as the header made it.
+fn holds_tar(mut decompressed: impl Read) -> bool {
+    let mut header = [0; 512];
+    let mut filled = 0;
+    while filled < header.len() {
+        match decompressed.read(&mut header[filled..]) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => filled += n,
+        }
+    }
+    infer::archive::is_tar(&header[..filled])
+}
+
+/// Call `visit` with the path, size and content of every regular file in the archive
+/// `reader` holds, in archive order. Members whose path would escape the archive are
+/// skipped.
+pub(super) fn for_each_member(
+    format: Format,
+    reader: impl Read + Seek,
+    visit: &mut dyn FnMut(&Path, u64, &mut dyn Read),
+) -> io::Result<()> {
+    match format {
+        Format::Zip => {
+            let mut archive = zip::ZipArchive::new(reader)?;
+            for index in 0..archive.len() {
+                let mut member = archive.by_index(index)?;
+                if !member.is_file() {
+                    continue;
+                }
+                if let Some(path) = member.enclosed_name() {
+                    let size = member.size();
+                    visit(&path, size, &mut member);
+                }
+            }
+            Ok(())
+        }
+        _ => tar_members(format, reader, visit),
+    }
+}
+
+/// Like [`for_each_member`], for an archive that can only be read front to back, which
+/// was read up to `head`. Zip archives need to seek, so they are read into memory, and
+/// fail when larger than `max_bytes`.
+pub(super) fn for_each_streamed_member(
+    format: Format,
+    head: Vec<u8>,
+    rest: impl Read,
+    max_bytes: usize,
+    visit: &mut dyn FnMut(&Path, u64, &mut dyn Read),
+) -> io::Result<()> {
+    let reader = Cursor::new(head).chain(rest);
+    match format {
+        Format::Zip => {
+            let mut data = Vec::new();
+            reader.take(max_bytes as u64 + 1).read_to_end(&mut data)?;
+            if data.len() > max_bytes {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "zip archive too large to read without seeking",
+                ));
+            }
+            for_each_member(format, Cursor::new(data), visit)
+        }
+        _ => tar_members(format, reader, visit),
+    }
+}
+
+/// The members of a tar archive, compressed the way `format` says.
+fn tar_members(
+    format: Format,
+    reader: impl Read,
+    visit: &mut dyn FnMut(&Path, u64, &mut dyn Read),
+) -> io::Result<()> {
+    match format {
+        Format::TarGz => read_tar(GzDecoder::new(reader), visit),
+        Format::TarZst => {
+            let decoder = StreamingDecoder::new(reader).map_err(io::Error::other)?;
+            read_tar(decoder, visit)
+        }
+        _ => read_tar(reader, visit),
+    }
+}
+
+fn read_tar(reader: impl Read, visit: &mut dyn FnMut(&Path, u64, &mut dyn Read)) -> io::Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.into_owned();
+        if path.is_absolute() || path.components().any(|c| c.as_os_str() == "..") {
+            continue;
+        }
+        let size = entry.size();
+        visit(&path, size, &mut entry);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn tar(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn members(format: Format, data: Vec<u8>) -> Vec<(String, String)> {
+        let mut members = Vec::new();
+        for_each_member(format, Cursor::new(data), &mut |path, _, content| {
+            let mut text = String::new();
+            content.read_to_string(&mut text).unwrap();
+            members.push((path.display().to_string(), text));
+        })
+        .unwrap();
+        members
+    }
+
+    #[test]
+    fn test_tar_formats() {
+        let data = tar(&[("src/main.rs", "fn main() {}\n"), ("README", "hi\n")]);
+        assert_eq!(Some(Format::Tar), Format::of(&data));
+        let expected = vec![
+            ("src/main.rs".to_string(), "fn main() {}\n".to_string()),
+            ("README".to_string(), "hi\n".to_string()),
+        ];
+        assert_eq!(expected, members(Format::Tar, data.clone()));
+
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gz.write_all(&data).unwrap();
+        let gz = gz.finish().unwrap();
+        assert_eq!(Some(Format::TarGz), Format::of(&gz));
+        assert_eq!(expected, members(Format::TarGz, gz));
+
+        let zst = ruzstd::encoding::compress_to_vec(
+            data.as_slice(),
+            ruzstd::encoding::CompressionLevel::Fastest,
+        );
+        assert_eq!(Some(Format::TarZst), Format::of(&zst));
+        assert_eq!(expected, members(Format::TarZst, zst));
+
+        let mut plain = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        plain.write_all(b"just some text\n").unwrap();
+        assert_eq!(None, Format::of(&plain.finish().unwrap()));
+    }
+
+    #[test]
+    fn test_zip() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        writer.add_directory("lib/", options).unwrap();
+        writer.start_file("lib/a.py", options).unwrap();
+        writer.write_all(b"import os\n").unwrap();
+        let data = writer.finish().unwrap().into_inner();
+        assert_eq!(Some(Format::Zip), Format::of(&data));
+        assert_eq!(
+            vec![("lib/a.py".to_string(), "import os\n".to_string())],
+            members(Format::Zip, data)
+        );
+    }
+
+    #[test]
+    fn test_streamed() {
+        let data = tar(&[("src/main.rs", "fn main() {}\n")]);
+        let mut members = Vec::new();
+        let (head, rest) = data.split_at(100);
+        for_each_streamed_member(Format::Tar, head.to_vec(), rest, 100, &mut |path, _, _| {
+            members.push(path.to_path_buf());
+        })
+        .unwrap();
+        assert_eq!(vec![Path::new("src/main.rs")], members);
+
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("README", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"hi\n").unwrap();
+        let zip = zip.finish().unwrap().into_inner();
+        let mut count = 0;
+        for_each_streamed_member(
+            Format::Zip,
+            Vec::new(),
+            zip.as_slice(),
+            zip.len(),
+            &mut |_, _, _| {
+                count += 1;
+            },
+        )
+        .unwrap();
+        let too_large = for_each_streamed_member(
+            Format::Zip,
+            Vec::new(),
+            zip.as_slice(),
+            zip.len() - 1,
+            &mut |_, _, _| {},
+        );
+        assert_eq!(1, count);
+        assert_eq!(io::ErrorKind::InvalidData, too_large.unwrap_err().kind());
+    }
+}
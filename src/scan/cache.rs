@@ -0,0 +1,291 @@
+//! On-disk cache of per-file scan results.
+//!
+//! The cache is a text file with one tab-separated record per file, keyed by path and
+//! validated by size and modification time. The header carries the palate version, the
+//! detection prefix size and a hash of the [`Overrides`], so upgrading palate (and with
+//! it the detection rules), changing [`max_bytes`](super::ScanBuilder::max_bytes) or
+//! changing a project's mappings and exclusions discards every entry.
+
+use std::{
+    collections::HashMap,
+    fs::{File, Metadata},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use crate::{DetectionStage, FileType};
+
+use super::{LineCounts, Overrides};
+
+/// Bumped whenever the record layout or meaning changes.
+const FORMAT_VERSION: u32 = 3;
+
+/// The MIME types a scanned (text) file can have.
+const TEXT_MIMES: [&str; 4] = ["text/plain", "text/html", "text/xml", "text/x-shellscript"];
+
+/// What identifies a version of a file: its size and modification time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct Stamp {
+    size: u64,
+    mtime: Duration,
+}
+
+impl Stamp {
+    pub(super) fn of(metadata: &Metadata) -> Option<Self> {
+        let mtime = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            size: metadata.len(),
+            mtime,
+        })
+    }
+
+    pub(super) fn size(self) -> u64 {
+        self.size
+    }
+}
+
+/// Everything a scan needs to know about a text file, detected or not.
+#[derive(Clone, Debug)]
+pub(super) struct Detected {
+    pub(super) file_type: FileType,
+    pub(super) stage: DetectionStage,
+    pub(super) mime: &'static str,
+    pub(super) generated: bool,
+    /// Whether [`Sniffed::is_text`](super::Sniffed::is_text) accepted the file, rather
+    /// than it only looking like text.
+    pub(super) recognized: bool,
+    pub(super) lines: Option<LineCounts>,
+}
+
+/// The scan result of one file; `detected` is `None` for binary files.
+#[derive(Clone, Debug)]
+pub(super) struct CacheEntry {
+    pub(super) stamp: Stamp,
+    pub(super) detected: Option<Detected>,
+}
+
+#[derive(Default)]
+pub(super) struct Cache {
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+/// The first line of a cache file, which has to match for its entries to be used.
+pub(super) fn header(max_bytes: usize, overrides: Option<&Overrides>) -> String {
+    let overrides = overrides.map_or(0, |overrides| fnv1a(overrides.describe().as_bytes()));
+    format!(
+        "palate-cache\t{FORMAT_VERSION}\t{}\t{max_bytes}\t{overrides:016x}",
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// The 64-bit FNV-1a hash of `bytes`, which unlike the standard library's hashers is
+/// the same in every build.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+impl Cache {
+    /// Load the cache at `path`; a missing, unreadable or outdated cache is empty.
+    pub(super) fn load(path: &Path, header: &str) -> Self {
+        let Ok(file) = File::open(path) else {
+            return Self::default();
+        };
+        let mut lines = BufReader::new(file).lines();
+        match lines.next() {
+            Some(Ok(line)) if line == header => {}
+            _ => return Self::default(),
+        }
+        let entries = lines
+            .map_while(Result::ok)
+            .filter_map(|line| parse_record(&line))
+            .collect();
+        Self { entries }
+    }
+
+    /// The entry for `path`, if it was recorded for the same `stamp`.
+    pub(super) fn get(&self, path: &Path, stamp: Stamp) -> Option<&CacheEntry> {
+        self.entries.get(path).filter(|entry| entry.stamp == stamp)
+    }
+
+    /// Write the cache back to `path` with the entries `seen` by this scan. Entries
+    /// below `roots` that weren't seen belong to deleted or excluded files and are
+    /// dropped; entries outside `roots` are kept for other scans.
+    pub(super) fn save(
+        mut self,
+        path: &Path,
+        header: &str,
+        roots: &[PathBuf],
+        seen: Vec<(PathBuf, CacheEntry)>,
+    ) -> io::Result<()> {
+        self.entries
+            .retain(|path, _| !roots.iter().any(|root| path.starts_with(root)));
+        self.entries.extend(seen);
+
+        // Write a uniquely named sibling file and rename it, so concurrent readers never
+        // see half a cache and concurrent writers don't write into each other's file.
+        let dir = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+        let mut out = BufWriter::new(temp.as_file_mut());
+        writeln!(out, "{header}")?;
+        for (path, entry) in &self.entries {
+            if let Some(record) = format_record(path, entry) {
+                writeln!(out, "{record}")?;
+            }
+        }
+        out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        temp.persist(path).map_err(|err| err.error)?;
+        Ok(())
+    }
+}
+
+/// `size, mtime, file type, stage, mime, generated, recognized, lines, blanks, comments, path`,
+/// with `-` for missing values. Paths that aren't UTF-8 or contain a newline aren't cached.
+fn format_record(path: &Path, entry: &CacheEntry) -> Option<String> {
+    let path = path.to_str().filter(|path| !path.contains(['\n', '\r']))?;
+    let Stamp { size, mtime } = entry.stamp;
+    let mtime = format!("{}.{:09}", mtime.as_secs(), mtime.subsec_nanos());
+    let Some(detected) = &entry.detected else {
+        return Some(format!("{size}\t{mtime}\t-\t-\t-\t-\t-\t-\t-\t-\t{path}"));
+    };
+    let stage: &'static str = detected.stage.into();
+    let counts = match detected.lines {
+        Some(counts) => format!("{}\t{}\t{}", counts.lines, counts.blanks, counts.comments),
+        None => "-\t-\t-".to_string(),
+    };
+    Some(format!(
+        "{size}\t{mtime}\t{}\t{stage}\t{}\t{}\t{}\t{counts}\t{path}",
+        detected.file_type.canonical(),
+        detected.mime,
+        u8::from(detected.generated),
+        u8::from(detected.recognized),
+    ))
+}
+
+fn parse_record(line: &str) -> Option<(PathBuf, CacheEntry)> {
+    let mut fields = line.splitn(11, '\t');
+    let mut next = || fields.next();
+    let size = next()?.parse().ok()?;
+    let (secs, nanos) = next()?.split_once('.')?;
+    let stamp = Stamp {
+        size,
+        mtime: Duration::new(secs.parse().ok()?, nanos.parse().ok()?),
+    };
+    let (file_type, stage, mime) = (next()?, next()?, next()?);
+    let (generated, recognized) = (next()?, next()?);
+    let (lines, blanks, comments) = (next()?, next()?, next()?);
+    let path = PathBuf::from(next()?);
+
+    let detected = match file_type {
+        "-" => None,
+        file_type => Some(Detected {
+            file_type: file_type.parse().ok()?,
+            stage: stage.parse().ok()?,
+            mime: TEXT_MIMES.into_iter().find(|known| *known == mime)?,
+            generated: generated == "1",
+            recognized: recognized == "1",
+            lines: match lines {
+                "-" => None,
+                lines => Some(LineCounts {
+                    lines: lines.parse().ok()?,
+                    blanks: blanks.parse().ok()?,
+                    comments: comments.parse().ok()?,
+                }),
+            },
+        }),
+    };
+    Some((path, CacheEntry { stamp, detected }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::{DEFAULT_MAX_BYTES, tests::temp_dir};
+
+    #[test]
+    fn test_record_roundtrip() {
+        let entry = CacheEntry {
+            stamp: Stamp {
+                size: 42,
+                mtime: Duration::new(1_700_000_000, 5),
+            },
+            detected: Some(Detected {
+                file_type: FileType::Rust,
+                stage: DetectionStage::Heuristics,
+                mime: "text/plain",
+                generated: true,
+                recognized: true,
+                lines: Some(LineCounts {
+                    lines: 3,
+                    blanks: 1,
+                    comments: 1,
+                }),
+            }),
+        };
+        let record = format_record(Path::new("src/a\tb.rs"), &entry).unwrap();
+        let (path, parsed) = parse_record(&record).unwrap();
+        assert_eq!(Path::new("src/a\tb.rs"), path);
+        assert_eq!(entry.stamp, parsed.stamp);
+        let detected = parsed.detected.unwrap();
+        assert_eq!(FileType::Rust, detected.file_type);
+        assert_eq!(DetectionStage::Heuristics, detected.stage);
+        assert!(detected.generated && detected.recognized);
+        assert_eq!(Some(1), detected.lines.map(|counts| counts.code()));
+
+        let binary = CacheEntry {
+            detected: None,
+            ..entry
+        };
+        let record = format_record(Path::new("logo.png"), &binary).unwrap();
+        assert!(parse_record(&record).unwrap().1.detected.is_none());
+        assert!(format_record(Path::new("a\nb"), &binary).is_none());
+    }
+
+    #[test]
+    fn test_header_covers_overrides() {
+        let mapped = |file_type| {
+            let mut overrides = Overrides::new("project");
+            overrides.extension("inc", file_type);
+            header(DEFAULT_MAX_BYTES, Some(&overrides))
+        };
+        let mut excluded = Overrides::new("project");
+        excluded.exclude("fixtures/").unwrap();
+        let headers = [
+            header(DEFAULT_MAX_BYTES, None),
+            header(1024, None),
+            mapped(FileType::Php),
+            mapped(FileType::Html),
+            header(DEFAULT_MAX_BYTES, Some(&excluded)),
+        ];
+        for (i, a) in headers.iter().enumerate() {
+            for b in &headers[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        assert_eq!(mapped(FileType::Php), mapped(FileType::Php));
+    }
+
+    #[test]
+    fn test_concurrent_saves() {
+        let tmp = temp_dir();
+        let path = tmp.path().join("cache");
+        let header = header(DEFAULT_MAX_BYTES, None);
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    Cache::default()
+                        .save(&path, &header, &[], Vec::new())
+                        .unwrap()
+                });
+            }
+        });
+        let files: Vec<_> = std::fs::read_dir(tmp.path()).unwrap().collect();
+        assert_eq!(1, files.len());
+        assert_eq!(header, std::fs::read_to_string(&path).unwrap().trim_end());
+    }
+}
@@ -0,0 +1,168 @@
+//! Attributing files to owners with a `CODEOWNERS` file.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+/// Where GitHub and GitLab look for the `CODEOWNERS` file, in order, relative to the
+/// repository root.
+const LOCATIONS: [&str; 4] = [
+    ".github/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+];
+
+/// The owners of the files of a repository, from its `CODEOWNERS` file.
+///
+/// Patterns use `.gitignore` syntax relative to the repository root, and the last
+/// matching rule decides the owners of a file; a rule without owners leaves the files
+/// it matches unowned. GitLab section headers like `[Docs]` are skipped.
+///
+/// # Example
+/// ```
+/// use palate::scan::Codeowners;
+///
+/// let codeowners = Codeowners::parse(
+///     "/repo",
+///     "# Everything else\n* @org/platform\n/apps/web/ @org/web @alice\n*.md\n",
+/// )
+/// .unwrap();
+/// assert_eq!(["@org/web", "@alice"], codeowners.owners("/repo/apps/web/src/main.ts"));
+/// assert_eq!(["@org/platform"], codeowners.owners("/repo/build.rs"));
+/// assert!(codeowners.owners("/repo/apps/web/README.md").is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Codeowners {
+    root: PathBuf,
+    rules: Vec<(Gitignore, Vec<String>)>,
+}
+
+impl Codeowners {
+    /// No rules yet, for the repository in `root`; relative roots are taken from the
+    /// current directory.
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref();
+        Self {
+            root: std::path::absolute(root).unwrap_or_else(|_| root.to_path_buf()),
+            rules: Vec::new(),
+        }
+    }
+
+    /// The rules of `content`, a `CODEOWNERS` file of the repository in `root`; fails
+    /// on the first invalid pattern.
+    pub fn parse(root: impl AsRef<Path>, content: &str) -> io::Result<Self> {
+        let mut codeowners = Self::new(root);
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || is_section(line) {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let Some(pattern) = fields.next() else {
+                continue;
+            };
+            let owners = fields.take_while(|field| !field.starts_with('#'));
+            codeowners.rule(pattern, owners)?;
+        }
+        Ok(codeowners)
+    }
+
+    /// The `CODEOWNERS` file of the repository `start` (a file or directory) is in:
+    /// the first of `.github/CODEOWNERS`, `CODEOWNERS`, `docs/CODEOWNERS` and
+    /// `.gitlab/CODEOWNERS` in `start` or the closest parent that has one.
+    pub fn discover(start: impl AsRef<Path>) -> io::Result<Option<Self>> {
+        let start = std::path::absolute(start.as_ref())?;
+        for dir in start.ancestors().filter(|dir| dir.is_dir()) {
+            for location in LOCATIONS {
+                let path = dir.join(location);
+                if path.is_file() {
+                    let content = std::fs::read_to_string(&path)?;
+                    return Self::parse(dir, &content).map(Some);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// The repository root.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Make `owners` the owners of the files matching `pattern`, over the rules so far;
+    /// fails if `pattern` isn't valid.
+    pub fn rule<I, S>(&mut self, pattern: &str, owners: I) -> io::Result<&mut Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let invalid = |err| io::Error::new(io::ErrorKind::InvalidInput, err);
+        let mut builder = GitignoreBuilder::new("");
+        builder.add_line(None, pattern).map_err(invalid)?;
+        let matcher = builder.build().map_err(invalid)?;
+        self.rules
+            .push((matcher, owners.into_iter().map(Into::into).collect()));
+        Ok(self)
+    }
+
+    /// The owners of the file `path`; empty for files no rule gives an owner, and for
+    /// paths outside of the repository.
+    pub fn owners(&self, path: impl AsRef<Path>) -> &[String] {
+        let Some(relative) = std::path::absolute(path.as_ref())
+            .ok()
+            .and_then(|path| Some(path.strip_prefix(&self.root).ok()?.to_path_buf()))
+            .filter(|relative| !relative.as_os_str().is_empty())
+        else {
+            return &[];
+        };
+        self.rules
+            .iter()
+            .rev()
+            .find(|(matcher, _)| {
+                matcher
+                    .matched_path_or_any_parents(&relative, false)
+                    .is_ignore()
+            })
+            .map_or(&[], |(_, owners)| owners.as_slice())
+    }
+}
+
+/// Whether `line` is a GitLab section header like `[Docs]`, `^[Docs][2]` or
+/// `[Docs] @docs-team`.
+fn is_section(line: &str) -> bool {
+    line.trim_start_matches('^').starts_with('[')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_rule_wins() {
+        let codeowners = Codeowners::parse(
+            "repo",
+            "\
+* @org/platform  # the default
+*.md @org/docs
+docs/ @org/writers
+/scripts/*.sh
+[Frontend]
+\\#weird @org/escaped
+",
+        )
+        .unwrap();
+        let owners = |path: &str| codeowners.owners(path).join(" ");
+        assert_eq!("@org/platform", owners("repo/src/main.rs"));
+        assert_eq!("@org/docs", owners("repo/README.md"));
+        assert_eq!("@org/writers", owners("repo/docs/guide.md"));
+        assert_eq!("@org/writers", owners("repo/src/docs/a/b.rs"));
+        assert_eq!("", owners("repo/scripts/release.sh"));
+        assert_eq!("@org/platform", owners("repo/tools/scripts/release.sh"));
+        assert_eq!("@org/escaped", owners("repo/#weird"));
+        assert_eq!("", owners("elsewhere/main.rs"));
+    }
+}
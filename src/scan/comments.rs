@@ -0,0 +1,674 @@
+//! Comment syntax per language, used to tell comment lines from code lines.
+//!
+//! Lines are scanned left to right with a little state carried between them, so block
+//! comments and strings can span lines and comment markers inside strings don't count.
+//! A line is a comment line when everything on it that isn't whitespace belongs to a
+//! comment. Languages without an entry here have no comment lines.
+//!
+//! The tokenizer behind the classifier can't stand in for this: it takes whole files and
+//! knows one comment syntax for every language, while `#` is a comment in Python but a
+//! preprocessor line in C.
+
+use crate::FileType;
+
+/// How comments and strings are written in a language. Strings matter only because
+/// they can contain comment markers; a backslash escapes the next byte inside them.
+#[derive(Debug)]
+pub(super) struct Syntax {
+    line: &'static [&'static str],
+    /// Open and close markers. Block comments are tried before line comments, so
+    /// `--[[` can open a block in Lua while `--` starts a line comment.
+    block: &'static [(&'static str, &'static str)],
+    nested: bool,
+    /// Open and close delimiters, longest first.
+    quotes: &'static [(&'static str, &'static str)],
+    /// `'` opens a character literal like `'"'` or `'\''` only when one character and a
+    /// closing `'` follow; otherwise it's a lifetime or a label.
+    chars: bool,
+    /// Rust raw strings like `r"…"`, `br#"…"#` or `cr"…"`, which have no escapes.
+    raw_strings: bool,
+    /// Open and close delimiters of strings without escapes, like Go's `` `…` ``.
+    raw_quotes: &'static [(&'static str, &'static str)],
+    /// Lua long brackets of any level up to [`LONG_CLOSES`]: `--[==[ … ]==]` comments
+    /// and `[==[ … ]==]` strings, which have no escapes.
+    long_brackets: bool,
+}
+
+/// Closing delimiters of raw strings by the number of `#`s they were opened with.
+const RAW_CLOSES: &[&str] = &["\"", "\"#", "\"##", "\"###", "\"####"];
+
+/// Closing long brackets by the number of `=`s they were opened with.
+const LONG_CLOSES: &[&str] = &["]]", "]=]", "]==]", "]===]", "]====]"];
+
+const DOUBLE: &[(&str, &str)] = &[("\"", "\"")];
+const DOUBLE_SINGLE: &[(&str, &str)] = &[("\"", "\""), ("'", "'")];
+const C_BLOCK: &[(&str, &str)] = &[("/*", "*/")];
+
+/// `//` and `/* */`: C and most of its descendants.
+const C_STYLE: Syntax = Syntax {
+    line: &["//"],
+    block: C_BLOCK,
+    nested: false,
+    quotes: DOUBLE_SINGLE,
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+/// C style with raw strings.
+const GO_STYLE: Syntax = Syntax {
+    raw_quotes: &[("`", "`")],
+    ..C_STYLE
+};
+
+/// C style with template literals.
+const JS_STYLE: Syntax = Syntax {
+    quotes: &[("\"", "\""), ("'", "'"), ("`", "`")],
+    ..C_STYLE
+};
+
+/// C style with nested block comments, raw strings, and `'` for lifetimes and
+/// characters rather than strings.
+const RUST_STYLE: Syntax = Syntax {
+    nested: true,
+    quotes: DOUBLE,
+    chars: true,
+    raw_strings: true,
+    ..C_STYLE
+};
+
+/// Rust style without Rust's raw strings; Swift's `#"…"#` only differs from a plain
+/// string in its escapes.
+const SWIFT_STYLE: Syntax = Syntax {
+    raw_strings: false,
+    ..RUST_STYLE
+};
+
+const NESTED_C_STYLE: Syntax = Syntax {
+    nested: true,
+    ..C_STYLE
+};
+
+const D_STYLE: Syntax = Syntax {
+    block: &[("/+", "+/"), ("/*", "*/")],
+    raw_strings: false,
+    ..RUST_STYLE
+};
+
+const CSS_STYLE: Syntax = Syntax {
+    line: &[],
+    ..C_STYLE
+};
+
+const HASH: Syntax = Syntax {
+    line: &["#"],
+    block: &[],
+    nested: false,
+    quotes: DOUBLE_SINGLE,
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+const PYTHON_STYLE: Syntax = Syntax {
+    quotes: &[
+        ("\"\"\"", "\"\"\""),
+        ("'''", "'''"),
+        ("\"", "\""),
+        ("'", "'"),
+    ],
+    ..HASH
+};
+
+/// `#` in configuration formats where an apostrophe in prose doesn't open a string.
+const HASH_UNQUOTED: Syntax = Syntax {
+    quotes: &[],
+    ..HASH
+};
+
+const INI_STYLE: Syntax = Syntax {
+    line: &[";", "#"],
+    ..HASH_UNQUOTED
+};
+
+const NIX_STYLE: Syntax = Syntax {
+    block: C_BLOCK,
+    quotes: DOUBLE,
+    ..HASH
+};
+
+const HCL_STYLE: Syntax = Syntax {
+    line: &["#", "//"],
+    block: C_BLOCK,
+    nested: false,
+    quotes: DOUBLE,
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+const PHP_STYLE: Syntax = Syntax {
+    line: &["#", "//"],
+    ..C_STYLE
+};
+
+const POWERSHELL_STYLE: Syntax = Syntax {
+    block: &[("<#", "#>")],
+    ..HASH
+};
+
+const JULIA_STYLE: Syntax = Syntax {
+    block: &[("#=", "=#")],
+    nested: true,
+    quotes: &[("\"\"\"", "\"\"\""), ("\"", "\"")],
+    ..HASH
+};
+
+const NIM_STYLE: Syntax = Syntax {
+    block: &[("#[", "]#")],
+    ..JULIA_STYLE
+};
+
+const HASKELL_STYLE: Syntax = Syntax {
+    line: &["--"],
+    block: &[("{-", "-}")],
+    nested: true,
+    quotes: DOUBLE,
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+const LUA_STYLE: Syntax = Syntax {
+    line: &["--"],
+    block: &[],
+    nested: false,
+    quotes: DOUBLE_SINGLE,
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: true,
+};
+
+const SQL_STYLE: Syntax = Syntax {
+    line: &["--"],
+    block: C_BLOCK,
+    nested: false,
+    quotes: DOUBLE_SINGLE,
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+const MYSQL_STYLE: Syntax = Syntax {
+    line: &["--", "#"],
+    ..SQL_STYLE
+};
+
+const ADA_STYLE: Syntax = Syntax {
+    line: &["--"],
+    block: &[],
+    nested: false,
+    quotes: DOUBLE,
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+const ML_STYLE: Syntax = Syntax {
+    line: &[],
+    block: &[("(*", "*)")],
+    nested: true,
+    quotes: DOUBLE,
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+const FSHARP_STYLE: Syntax = Syntax {
+    line: &["//"],
+    ..ML_STYLE
+};
+
+const PASCAL_STYLE: Syntax = Syntax {
+    line: &["//"],
+    block: &[("{", "}"), ("(*", "*)")],
+    nested: false,
+    quotes: &[("'", "'")],
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+const LISP_STYLE: Syntax = Syntax {
+    line: &[";"],
+    block: &[("#|", "|#")],
+    nested: true,
+    quotes: DOUBLE,
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+const SEMICOLON: Syntax = Syntax {
+    block: &[],
+    ..LISP_STYLE
+};
+
+const ASM_STYLE: Syntax = Syntax {
+    quotes: DOUBLE_SINGLE,
+    ..SEMICOLON
+};
+
+const PERCENT: Syntax = Syntax {
+    line: &["%"],
+    block: &[],
+    nested: false,
+    quotes: DOUBLE,
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+const TEX_STYLE: Syntax = Syntax {
+    quotes: &[],
+    ..PERCENT
+};
+
+const MATLAB_STYLE: Syntax = Syntax {
+    block: &[("%{", "%}")],
+    ..PERCENT
+};
+
+const PROLOG_STYLE: Syntax = Syntax {
+    block: C_BLOCK,
+    quotes: DOUBLE_SINGLE,
+    ..PERCENT
+};
+
+const MARKUP: Syntax = Syntax {
+    line: &[],
+    block: &[("<!--", "-->")],
+    nested: false,
+    quotes: &[],
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+const FORTRAN_STYLE: Syntax = Syntax {
+    line: &["!"],
+    block: &[],
+    nested: false,
+    quotes: DOUBLE_SINGLE,
+    chars: false,
+    raw_strings: false,
+    raw_quotes: &[],
+    long_brackets: false,
+};
+
+/// The comment syntax of `file_type`, if palate knows it.
+pub(super) fn syntax(file_type: FileType) -> Option<&'static Syntax> {
+    use FileType::*;
+
+    Some(match file_type {
+        Apex | Arduino | Ballerina | Bicep | C | C3 | Cairo | Carbon | Chuck | Circom | CSharp
+        | Cpp | Cue | Cuda | Dafny | Dart | GdShader | Glsl | Groovy | Hare | Haxe | Hlsl
+        | Ispc | Jai | Java | Json5 | JsonC | Kusto | Metal | ObjC | ObjCpp | Odin | Pawn
+        | Prisma | Proto | Slang | Smithy | Solidity | Sourcepawn | Squirrel | Stan
+        | SystemVerilog | Thrift | Typespec | Vala | Wgsl | Yul | Zig => &C_STYLE,
+        Go => &GO_STYLE,
+        JavaScript | Jsx | TypeScript | Tsx => &JS_STYLE,
+        Rust => &RUST_STYLE,
+        Swift => &SWIFT_STYLE,
+        Kotlin | Scala => &NESTED_C_STYLE,
+        D => &D_STYLE,
+        Css => &CSS_STYLE,
+        Less | Sass | Scss | Stylus => &C_STYLE,
+        Awk | Bash | Crystal | Csh | Elixir | Elvish | Fish | Just | Ksh | Nu | Perl | R | Raku
+        | Ruby | Sh | Tcl | Tcsh | Zsh => &HASH,
+        Bzl | GdScript | Meson | Mojo | Python | Snakemake | Starlark | Toml | Xonsh => {
+            &PYTHON_STYLE
+        }
+        CMake | Codeowners | Conf | Dockerfile | Dotenv | GitAttributes | GitIgnore | Make
+        | Nginx | Procfile | Requirements | Sed | SshConfig | Tmux | Yaml => &HASH_UNQUOTED,
+        ConfIni | DosIni | EditorConfig | GitConfig => &INI_STYLE,
+        Nix => &NIX_STYLE,
+        Hcl | Terraform | TerraformVars => &HCL_STYLE,
+        Php => &PHP_STYLE,
+        Ps1 => &POWERSHELL_STYLE,
+        Julia => &JULIA_STYLE,
+        Nim => &NIM_STYLE,
+        Agda | Curry | Dhall | Elm | Haskell | Idris | Purescript => &HASKELL_STYLE,
+        Lua | Luau | Teal => &LUA_STYLE,
+        Hiveql | Plpgsql | Plsql | Sql | Sqlpl | Tsql => &SQL_STYLE,
+        MySql => &MYSQL_STYLE,
+        Ada | Vhdl => &ADA_STYLE,
+        Coq | OCaml | OCamlInterface | Sml => &ML_STYLE,
+        FSharp => &FSHARP_STYLE,
+        Pascal => &PASCAL_STYLE,
+        Lisp | Racket | Scheme => &LISP_STYLE,
+        Clojure | Elisp | Fennel => &SEMICOLON,
+        Asm | Masm | Nasm | Yasm => &ASM_STYLE,
+        Erlang => &PERCENT,
+        PlainTex | Tex => &TEX_STYLE,
+        Matlab | Octave => &MATLAB_STYLE,
+        Prolog => &PROLOG_STYLE,
+        Angular | Html | Markdown | MavenPom | Svelte | Svg | Vue | Xhtml | Xml | Xsd | Xslt => {
+            &MARKUP
+        }
+        Fortran => &FORTRAN_STYLE,
+        _ => return None,
+    })
+}
+
+/// Where the previous line left off.
+#[derive(Clone, Copy, Debug)]
+enum State {
+    Code,
+    Block {
+        open: &'static str,
+        close: &'static str,
+        depth: u32,
+    },
+    Quoted {
+        close: &'static str,
+        escapes: bool,
+    },
+}
+
+/// Classifies the lines of one file in order.
+#[derive(Debug)]
+pub(super) struct CommentScanner {
+    syntax: &'static Syntax,
+    state: State,
+}
+
+impl CommentScanner {
+    pub(super) fn new(file_type: FileType) -> Option<Self> {
+        Some(Self {
+            syntax: syntax(file_type)?,
+            state: State::Code,
+        })
+    }
+
+    /// Scan the next line (without its newline) and tell whether it holds nothing but
+    /// comments. Blank lines may be skipped, they never change the state.
+    pub(super) fn is_comment_line(&mut self, line: &[u8]) -> bool {
+        let syntax = self.syntax;
+        let (mut code, mut comment) = (false, false);
+        let mut i = 0;
+        while i < line.len() {
+            let rest = &line[i..];
+            match self.state {
+                State::Block { open, close, depth } => {
+                    if rest.starts_with(close.as_bytes()) {
+                        i += close.len();
+                        comment = true;
+                        self.state = match depth {
+                            1 => State::Code,
+                            _ => State::Block {
+                                open,
+                                close,
+                                depth: depth - 1,
+                            },
+                        };
+                    } else if syntax.nested && rest.starts_with(open.as_bytes()) {
+                        i += open.len();
+                        self.state = State::Block {
+                            open,
+                            close,
+                            depth: depth + 1,
+                        };
+                    } else {
+                        comment |= !rest[0].is_ascii_whitespace();
+                        i += 1;
+                    }
+                }
+                State::Quoted { close, escapes } => {
+                    code = true;
+                    if escapes && rest[0] == b'\\' {
+                        i += 2;
+                    } else if rest.starts_with(close.as_bytes()) {
+                        i += close.len();
+                        self.state = State::Code;
+                    } else {
+                        i += 1;
+                    }
+                }
+                State::Code => {
+                    if rest[0].is_ascii_whitespace() {
+                        i += 1;
+                    } else if let Some((open, close)) = syntax
+                        .long_brackets
+                        .then(|| long_bracket(rest.strip_prefix(b"--")?))
+                        .flatten()
+                    {
+                        i += 2 + open;
+                        comment = true;
+                        self.state = State::Block {
+                            open: "--[",
+                            close,
+                            depth: 1,
+                        };
+                    } else if let Some(&(open, close)) = starting(syntax.block, rest) {
+                        i += open.len();
+                        comment = true;
+                        self.state = State::Block {
+                            open,
+                            close,
+                            depth: 1,
+                        };
+                    } else if syntax
+                        .line
+                        .iter()
+                        .any(|marker| rest.starts_with(marker.as_bytes()))
+                    {
+                        return !code;
+                    } else if let Some(len) = syntax.chars.then(|| char_literal(rest)).flatten() {
+                        i += len;
+                        code = true;
+                    } else if let Some((open, close)) = syntax
+                        .raw_strings
+                        .then(|| raw_string(&line[..i], rest))
+                        .flatten()
+                    {
+                        i += open;
+                        code = true;
+                        self.state = State::Quoted {
+                            close,
+                            escapes: false,
+                        };
+                    } else if let Some((open, close)) = starting(syntax.raw_quotes, rest)
+                        .map(|&(open, close)| (open.len(), close))
+                        .or_else(|| syntax.long_brackets.then(|| long_bracket(rest)).flatten())
+                    {
+                        i += open;
+                        code = true;
+                        self.state = State::Quoted {
+                            close,
+                            escapes: false,
+                        };
+                    } else if let Some(&(open, close)) = starting(syntax.quotes, rest) {
+                        i += open.len();
+                        code = true;
+                        self.state = State::Quoted {
+                            close,
+                            escapes: true,
+                        };
+                    } else {
+                        code = true;
+                        i += 1;
+                    }
+                }
+            }
+        }
+        comment && !code
+    }
+}
+
+fn starting<'a>(
+    pairs: &'a [(&'static str, &'static str)],
+    rest: &[u8],
+) -> Option<&'a (&'static str, &'static str)> {
+    pairs
+        .iter()
+        .find(|(open, _)| rest.starts_with(open.as_bytes()))
+}
+
+/// The length of the character literal `rest` starts with, like `'x'`, `'\''` or
+/// `'\u{1F600}'`.
+fn char_literal(rest: &[u8]) -> Option<usize> {
+    if rest.first() != Some(&b'\'') {
+        return None;
+    }
+    let len = match rest.get(1)? {
+        b'\\' => 3 + rest.get(3..)?.iter().position(|&b| b == b'\'')?,
+        &first => 1 + utf8_width(first),
+    };
+    (rest.get(len) == Some(&b'\'')).then_some(len + 1)
+}
+
+fn utf8_width(first: u8) -> usize {
+    match first.leading_ones() {
+        2..=4 => first.leading_ones() as usize,
+        _ => 1,
+    }
+}
+
+/// The length of the opening delimiter and the closing delimiter of the raw string
+/// `rest` starts with, if any; `before` is the line up to `rest`, so that the `r` of
+/// `bar"` isn't taken for a prefix.
+fn raw_string(before: &[u8], rest: &[u8]) -> Option<(usize, &'static str)> {
+    let is_ident = |b: &u8| b.is_ascii_alphanumeric() || *b == b'_';
+    let prefix = match before {
+        [.., b'b' | b'c'] => &before[..before.len() - 1],
+        _ => before,
+    };
+    if rest.first() != Some(&b'r') || prefix.last().is_some_and(is_ident) {
+        return None;
+    }
+    let hashes = rest[1..].iter().take_while(|&&b| b == b'#').count();
+    if rest.get(1 + hashes) != Some(&b'"') {
+        return None;
+    }
+    Some((hashes + 2, RAW_CLOSES.get(hashes)?))
+}
+
+/// The length of the opening long bracket `rest` starts with, like `[[` or `[==[`, and
+/// its closing bracket.
+fn long_bracket(rest: &[u8]) -> Option<(usize, &'static str)> {
+    let level = rest
+        .strip_prefix(b"[")?
+        .iter()
+        .take_while(|&&b| b == b'=')
+        .count();
+    if rest.get(1 + level) != Some(&b'[') {
+        return None;
+    }
+    Some((level + 2, LONG_CLOSES.get(level)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The comment lines of `content`, numbered from 1.
+    fn comment_lines(file_type: FileType, content: &str) -> Vec<usize> {
+        let mut scanner = CommentScanner::new(file_type).unwrap();
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter(|(_, line)| scanner.is_comment_line(line.as_bytes()))
+            .map(|(i, _)| i + 1)
+            .collect()
+    }
+
+    #[test]
+    fn test_comment_lines() {
+        let rust = "\
+// line
+let a = 1; // trailing
+/* block
+   still block */
+let b = \"// not a comment\";
+/* /* nested */ still */ let c = 3;
+let d = \"multi
+// line string\";
+/* one */ /* two */
+";
+        assert_eq!(vec![1, 3, 4, 9], comment_lines(FileType::Rust, rust));
+
+        let python = "\
+# comment
+x = '#'
+\"\"\"
+# inside a docstring
+\"\"\"
+";
+        assert_eq!(vec![1], comment_lines(FileType::Python, python));
+
+        // Quotes in character, byte and raw string literals don't open strings.
+        let rust = "\
+fn f<'a>(s: &'a str) -> char { let q = b'\"'; '\"' }
+// one
+let r = r#\"a \" b // not a comment
+\"#; let e = '\\''; let u = '\\u{22}';
+// two
+let raw = r\"C:\\\"; let bar = br\"\\\";
+// three
+let s = \"/* not a block\"; 'outer: loop { let c = '€'; }
+// four
+";
+        assert_eq!(vec![2, 5, 7, 9], comment_lines(FileType::Rust, rust));
+
+        let lua = "--[[ block\nstill ]] \n-- line\nprint('--')\n";
+        assert_eq!(vec![1, 2, 3], comment_lines(FileType::Lua, lua));
+
+        // `#include` is a preprocessor line, not a comment.
+        let c = "#include <stdio.h>\n// comment\n";
+        assert_eq!(vec![2], comment_lines(FileType::C, c));
+        assert!(CommentScanner::new(FileType::Text).is_none());
+    }
+
+    #[test]
+    fn test_go_raw_strings() {
+        let go = "\
+// comment
+p := `C:\\` // the backslash doesn't escape
+// comment
+q := `multi
+// line raw string`
+r := '\"'
+// comment
+";
+        assert_eq!(vec![1, 3, 7], comment_lines(FileType::Go, go));
+    }
+
+    #[test]
+    fn test_lua_long_brackets() {
+        let lua = "\
+--[==[ block
+]] still
+]==]
+s = [[ -- not a comment ]]
+--[=[ one ]=] --[[ two ]]
+t = [==[
+-- long string ]] \\]==]
+--[ line
+";
+        assert_eq!(vec![1, 2, 3, 5, 8], comment_lines(FileType::Lua, lua));
+    }
+}
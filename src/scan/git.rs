@@ -0,0 +1,256 @@
+//! Reading committed files straight from the object database of a git repository, by
+//! running `git`.
+
+use std::{
+    ffi::OsStr,
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Component, Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// A commit, as listed by [`commits`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Commit {
+    /// The full commit id.
+    pub id: String,
+    /// Committer time, in seconds since the Unix epoch.
+    pub time: i64,
+    /// The first line of the commit message.
+    pub summary: String,
+}
+
+/// The last `max_count` commits of `revision` in the git repository `path` (a file or
+/// directory) is in, newest first, following only the first parent of merges so that
+/// the commits are the states a branch was in.
+///
+/// Pair it with [`ScanBuilder::breakdown_at`](super::ScanBuilder::breakdown_at) to see
+/// how the languages of a repository evolved. Runs the `git` command, and fails when it
+/// isn't installed, `path` isn't in a repository, or `revision` doesn't exist.
+pub fn commits(
+    path: impl AsRef<Path>,
+    revision: &str,
+    max_count: usize,
+) -> io::Result<Vec<Commit>> {
+    let log = git(
+        directory(path.as_ref()),
+        [
+            "log",
+            "--first-parent",
+            "-z",
+            "--format=%H %ct %s",
+            &format!("--max-count={max_count}"),
+            "--end-of-options",
+            revision,
+            "--",
+        ],
+    )?;
+    let log = String::from_utf8_lossy(&log);
+    log.split_terminator('\0')
+        .map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(3, ' ');
+            let mut next = || fields.next().unwrap_or_default();
+            let (id, time, summary) = (next(), next(), next());
+            Ok(Commit {
+                id: id.to_string(),
+                time: time
+                    .parse()
+                    .map_err(|_| invalid("unexpected `git log` output"))?,
+                summary: summary.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The repository a scan reads from.
+pub(super) struct Repository {
+    /// The top-level directory of the working tree.
+    top: PathBuf,
+}
+
+/// A regular file in a tree.
+pub(super) struct Blob {
+    /// The path below the top-level directory.
+    pub(super) path: PathBuf,
+    pub(super) size: u64,
+    id: String,
+}
+
+impl Repository {
+    /// The repository `path` (a file or directory) is in.
+    pub(super) fn discover(path: &Path) -> io::Result<Self> {
+        let top = git(directory(path), ["rev-parse", "--show-toplevel"])?;
+        let top = String::from_utf8(top).map_err(invalid)?;
+        Ok(Self {
+            top: PathBuf::from(top.trim_end_matches('\n')),
+        })
+    }
+
+    /// `path`, a file or directory in the repository, below the top-level directory. It
+    /// is resolved lexically, so it doesn't need to exist in the working tree.
+    pub(super) fn relative(&self, path: &Path) -> io::Result<PathBuf> {
+        let path = normalized(&std::path::absolute(path)?);
+        let relative = path.strip_prefix(&self.top).map_err(|_| {
+            let message = format!("{} is outside of {}", path.display(), self.top.display());
+            invalid(message)
+        })?;
+        Ok(relative.to_path_buf())
+    }
+
+    /// The regular files of the tree of `revision` below `paths`, which are relative to
+    /// the top-level directory; all of them if `paths` is empty. Symlinks and submodules
+    /// are left out.
+    pub(super) fn blobs(&self, revision: &str, paths: &[&Path]) -> io::Result<Vec<Blob>> {
+        let tree = git(
+            &self.top,
+            [
+                "rev-parse",
+                "--verify",
+                "--end-of-options",
+                &format!("{revision}^{{tree}}"),
+            ],
+        )
+        .map_err(|_| {
+            let message = format!("unknown revision `{revision}`");
+            io::Error::new(io::ErrorKind::NotFound, message)
+        })?;
+        let tree = String::from_utf8(tree).map_err(invalid)?;
+        let mut args = vec![
+            OsStr::new("--literal-pathspecs"),
+            OsStr::new("ls-tree"),
+            OsStr::new("-r"),
+            OsStr::new("-z"),
+            OsStr::new("--long"),
+            OsStr::new(tree.trim_end()),
+            OsStr::new("--"),
+        ];
+        // An empty path is the whole tree, which makes the others redundant.
+        if !paths.iter().any(|path| path.as_os_str().is_empty()) {
+            args.extend(paths.iter().map(|path| path.as_os_str()));
+        }
+        let listing = git(&self.top, args)?;
+
+        let mut blobs = Vec::new();
+        for entry in listing.split(|&b| b == 0).filter(|entry| !entry.is_empty()) {
+            let entry = std::str::from_utf8(entry).map_err(invalid)?;
+            let (meta, path) = entry
+                .split_once('\t')
+                .ok_or_else(|| invalid("unexpected `git ls-tree` output"))?;
+            let mut meta = meta.split_ascii_whitespace();
+            let (Some(mode), Some(_), Some(id), Some(size)) =
+                (meta.next(), meta.next(), meta.next(), meta.next())
+            else {
+                return Err(invalid("unexpected `git ls-tree` output"));
+            };
+            if mode != "100644" && mode != "100755" {
+                continue;
+            }
+            blobs.push(Blob {
+                path: PathBuf::from(path),
+                size: size.parse().map_err(invalid)?,
+                id: id.to_string(),
+            });
+        }
+        Ok(blobs)
+    }
+
+    /// Read the content of `blobs` and call `visit` with the index of each blob and a
+    /// reader of its content, in order. Whatever `visit` leaves unread is skipped.
+    pub(super) fn read_blobs(
+        &self,
+        blobs: &[&Blob],
+        mut visit: impl FnMut(usize, &mut dyn Read),
+    ) -> io::Result<()> {
+        let mut child = Command::new("git")
+            .arg("-C")
+            .arg(&self.top)
+            .args(["cat-file", "--batch"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let (Some(mut stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(io::Error::other("failed to talk to `git cat-file`"));
+        };
+        let read = std::thread::scope(|scope| {
+            // Requests are written while the answers are read, so neither pipe fills up.
+            scope.spawn(move || {
+                for blob in blobs {
+                    if writeln!(stdin, "{}", blob.id).is_err() {
+                        break;
+                    }
+                }
+            });
+            let mut stdout = BufReader::new(stdout);
+            let mut header = String::new();
+            for (index, blob) in blobs.iter().enumerate() {
+                header.clear();
+                stdout.read_line(&mut header)?;
+                let size = match header.split_ascii_whitespace().collect::<Vec<_>>()[..] {
+                    [_, "blob", size] => size.parse().map_err(invalid)?,
+                    _ => return Err(invalid(format!("cannot read blob {}", blob.id))),
+                };
+                let mut content = (&mut stdout).take(size);
+                visit(index, &mut content);
+                io::copy(&mut content, &mut io::sink())?;
+                if content.limit() > 0 {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+                // Every object is followed by a newline.
+                stdout.read_exact(&mut [0])?;
+            }
+            Ok(())
+        });
+        child.wait()?;
+        read
+    }
+}
+
+/// The directory to run `git` in for `path`: the closest of it and its parents that is
+/// a directory, since it may be missing from the working tree.
+fn directory(path: &Path) -> &Path {
+    path.ancestors()
+        .find(|dir| !dir.as_os_str().is_empty() && dir.is_dir())
+        .unwrap_or(Path::new("."))
+}
+
+/// `path` with `.` and `..` components resolved without looking at the file system.
+fn normalized(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Run `git` in `dir` and return its output; a failure carries git's message.
+fn git<I, S>(dir: &Path, args: I) -> io::Result<Vec<u8>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.lines().next().unwrap_or("git failed");
+        return Err(io::Error::other(
+            message.trim_start_matches("fatal: ").to_string(),
+        ));
+    }
+    Ok(output.stdout)
+}
+
+fn invalid(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
@@ -0,0 +1,1671 @@
+//! Language breakdowns of directory trees.
+//!
+//! [`ScanBuilder`] walks one or more roots with `ignore`'s parallel walker. Every worker
+//! thread folds the files it sees into its own accumulator, and the accumulators are
+//! merged once the walk is done. Each file is opened once: the first
+//! [`max_bytes`](ScanBuilder::max_bytes) feed MIME sniffing and detection, and line
+//! counting continues from the same handle.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{File, Metadata},
+    hash::Hash,
+    io::{self, Read},
+    mem,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use ignore::{DirEntry, WalkBuilder, WalkState};
+use infer::MatcherType;
+
+use crate::{
+    DetectionStage, FileType, detect::detect_with_stage, is_documentation, is_generated,
+    is_test_file, is_vendored, try_detect,
+};
+
+use cache::{Cache, CacheEntry, Detected, Stamp};
+use comments::CommentScanner;
+
+pub use codeowners::Codeowners;
+pub use git::{Commit, commits};
+pub use overrides::Overrides;
+#[cfg(feature = "watch")]
+pub use watch::{Update, Watch};
+
+#[cfg(feature = "archive")]
+mod archive;
+mod cache;
+mod codeowners;
+mod comments;
+mod git;
+mod overrides;
+#[cfg(feature = "watch")]
+mod watch;
+
+/// How deep archives inside archives are scanned, see [`ScanBuilder::archives`].
+#[cfg(feature = "archive")]
+const MAX_ARCHIVE_DEPTH: usize = 4;
+
+/// Number of leading bytes read for detection unless [`ScanBuilder::max_bytes`] says
+/// otherwise.
+pub const DEFAULT_MAX_BYTES: usize = 51_200;
+
+/// Line counts of a file or a group of files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineCounts {
+    /// All lines, including a final line without a trailing newline.
+    pub lines: u64,
+    /// Lines containing only whitespace.
+    pub blanks: u64,
+    /// Lines containing only comments and whitespace.
+    pub comments: u64,
+}
+
+impl LineCounts {
+    /// Lines that are neither blank nor comments.
+    pub fn code(&self) -> u64 {
+        self.lines
+            .saturating_sub(self.blanks)
+            .saturating_sub(self.comments)
+    }
+}
+
+/// Count the lines, blank lines and comment lines of everything `reader` yields, using
+/// the comment syntax of `file_type`. Types palate doesn't know the comment syntax of
+/// have no comment lines.
+///
+/// # Example
+/// ```
+/// use palate::{FileType, scan::count_lines};
+///
+/// let source = "// Entry point.\nfn main() {\n\n    println!(\"/* hi */\");\n}";
+/// let counts = count_lines(source.as_bytes(), FileType::Rust).unwrap();
+/// assert_eq!(5, counts.lines);
+/// assert_eq!(1, counts.blanks);
+/// assert_eq!(1, counts.comments);
+/// assert_eq!(3, counts.code());
+/// ```
+pub fn count_lines(mut reader: impl Read, file_type: FileType) -> io::Result<LineCounts> {
+    let mut counter = LineCounter::new(file_type);
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        counter.feed(&buf[..n]);
+    }
+    Ok(counter.finish())
+}
+
+/// Aggregated statistics of the files of one language.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct LanguageStats {
+    /// Number of files.
+    pub files: usize,
+    /// Total lines.
+    pub lines: u64,
+    /// Total whitespace-only lines.
+    pub blanks: u64,
+    /// Total comment-only lines.
+    pub comments: u64,
+    /// Total size in bytes.
+    pub bytes: u64,
+    /// The files, when [`ScanBuilder::collect_paths`] is enabled.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub paths: Vec<PathBuf>,
+}
+
+impl LanguageStats {
+    /// Lines that are neither blank nor comments.
+    pub fn code(&self) -> u64 {
+        self.lines
+            .saturating_sub(self.blanks)
+            .saturating_sub(self.comments)
+    }
+
+    /// Add the statistics of `other` to these.
+    pub fn merge(&mut self, other: LanguageStats) {
+        self.files += other.files;
+        self.lines += other.lines;
+        self.blanks += other.blanks;
+        self.comments += other.comments;
+        self.bytes += other.bytes;
+        self.paths.extend(other.paths);
+    }
+
+    fn add(&mut self, file: ScannedFile, collect_paths: bool) {
+        self.files += 1;
+        self.bytes += file.bytes;
+        if let Some(counts) = file.lines {
+            self.lines += counts.lines;
+            self.blanks += counts.blanks;
+            self.comments += counts.comments;
+        }
+        if collect_paths {
+            self.paths.push(file.path);
+        }
+    }
+}
+
+/// Per-language statistics of a scan.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct LanguageBreakdown {
+    /// Statistics for every file type that was found.
+    pub languages: HashMap<FileType, LanguageStats>,
+}
+
+impl LanguageBreakdown {
+    /// Statistics over all languages, without paths.
+    pub fn total(&self) -> LanguageStats {
+        let mut total = LanguageStats::default();
+        for stats in self.languages.values() {
+            total.files += stats.files;
+            total.lines += stats.lines;
+            total.blanks += stats.blanks;
+            total.comments += stats.comments;
+            total.bytes += stats.bytes;
+        }
+        total
+    }
+
+    /// Add the statistics of `other` to these.
+    pub fn merge(&mut self, other: LanguageBreakdown) {
+        for (file_type, stats) in other.languages {
+            self.languages.entry(file_type).or_default().merge(stats);
+        }
+    }
+}
+
+/// The breakdown of `files`, with their paths.
+impl FromIterator<ScannedFile> for LanguageBreakdown {
+    fn from_iter<I: IntoIterator<Item = ScannedFile>>(files: I) -> Self {
+        let mut breakdown = LanguageBreakdown::default();
+        for file in files {
+            breakdown
+                .languages
+                .entry(file.file_type)
+                .or_default()
+                .add(file, true);
+        }
+        breakdown
+    }
+}
+
+/// One file seen by a scan.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ScannedFile {
+    /// Path of the file, starting with the root it was found under.
+    pub path: PathBuf,
+    /// Detected file type.
+    pub file_type: FileType,
+    /// Detection stage that decided the file type.
+    pub stage: DetectionStage,
+    /// MIME type, `text/plain` unless a more specific one was sniffed.
+    pub mime: &'static str,
+    /// Size in bytes.
+    pub bytes: u64,
+    /// Line counts, unless [`ScanBuilder::count_lines`] was disabled.
+    pub lines: Option<LineCounts>,
+    /// Whether the file is generated, see [`is_generated`].
+    pub generated: bool,
+    /// Whether the file is test code, see [`is_test_file`].
+    pub test: bool,
+    /// How the file is compressed, for files like `schema.sql.gz` whose file type is
+    /// detected from their decompressed content.
+    #[cfg(feature = "compression")]
+    pub compression: Option<crate::Compression>,
+}
+
+/// Configures and runs a scan of one or more directory trees or files.
+///
+/// By default the scan honors `.gitignore` and the other ignore rules of the `ignore`
+/// crate, and leaves out vendored, documentation and generated files the way Linguist
+/// does.
+///
+/// # Example
+/// ```no_run
+/// use palate::{FileType, scan::ScanBuilder};
+///
+/// let breakdown = ScanBuilder::new(".").threads(4).breakdown();
+/// if let Some(rust) = breakdown.languages.get(&FileType::Rust) {
+///     println!("{} Rust files, {} bytes", rust.files, rust.bytes);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScanBuilder {
+    roots: Vec<PathBuf>,
+    ignore_rules: bool,
+    exclude_hidden: bool,
+    exclude_vendored: bool,
+    exclude_documentation: bool,
+    exclude_generated: bool,
+    keep_undetected: bool,
+    types: Option<HashSet<FileType>>,
+    max_bytes: usize,
+    threads: usize,
+    count_lines: bool,
+    collect_paths: bool,
+    cache: Option<PathBuf>,
+    overrides: Option<Overrides>,
+    #[cfg(feature = "archive")]
+    archives: bool,
+}
+
+impl Default for ScanBuilder {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            ignore_rules: true,
+            exclude_hidden: true,
+            exclude_vendored: true,
+            exclude_documentation: true,
+            exclude_generated: true,
+            keep_undetected: false,
+            types: None,
+            max_bytes: DEFAULT_MAX_BYTES,
+            threads: 0,
+            count_lines: true,
+            collect_paths: false,
+            cache: None,
+            overrides: None,
+            #[cfg(feature = "archive")]
+            archives: false,
+        }
+    }
+}
+
+impl ScanBuilder {
+    /// Scan `root`, a directory or a single file.
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        let mut builder = Self::default();
+        builder.add(root);
+        builder
+    }
+
+    /// Also scan `root`. Files given as roots are scanned even if an ignore rule or an
+    /// exclusion would have skipped them, and kept even if generated or undetected; only
+    /// the [`Overrides`] exclude them.
+    pub fn add(&mut self, root: impl AsRef<Path>) -> &mut Self {
+        self.roots.push(root.as_ref().to_path_buf());
+        self
+    }
+
+    /// Honor `.gitignore`, `.ignore` and hidden files (enabled by default).
+    pub fn ignore_rules(&mut self, yes: bool) -> &mut Self {
+        self.ignore_rules = yes;
+        self
+    }
+
+    /// Leave out hidden files and directories (enabled by default, and only with
+    /// [`ignore_rules`](Self::ignore_rules)). `.git` directories are always left out.
+    pub fn exclude_hidden(&mut self, yes: bool) -> &mut Self {
+        self.exclude_hidden = yes;
+        self
+    }
+
+    /// Leave out vendored paths, see [`is_vendored`] (enabled by default).
+    pub fn exclude_vendored(&mut self, yes: bool) -> &mut Self {
+        self.exclude_vendored = yes;
+        self
+    }
+
+    /// Leave out documentation paths, see [`is_documentation`] (enabled by default).
+    pub fn exclude_documentation(&mut self, yes: bool) -> &mut Self {
+        self.exclude_documentation = yes;
+        self
+    }
+
+    /// Leave out generated files, see [`is_generated`] (enabled by default).
+    pub fn exclude_generated(&mut self, yes: bool) -> &mut Self {
+        self.exclude_generated = yes;
+        self
+    }
+
+    /// Report files that look like text but that no detection stage recognized as
+    /// [`FileType::Text`] with [`DetectionStage::Fallback`], instead of leaving them out
+    /// (disabled by default).
+    pub fn keep_undetected(&mut self, yes: bool) -> &mut Self {
+        self.keep_undetected = yes;
+        self
+    }
+
+    /// Only report files of these types.
+    pub fn types(&mut self, types: impl IntoIterator<Item = FileType>) -> &mut Self {
+        self.types = Some(types.into_iter().collect());
+        self
+    }
+
+    /// Number of leading bytes of each file used for detection (default
+    /// [`DEFAULT_MAX_BYTES`]). Line counts always cover the whole file.
+    pub fn max_bytes(&mut self, max_bytes: usize) -> &mut Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Number of worker threads; `0` (the default) picks one per CPU.
+    pub fn threads(&mut self, threads: usize) -> &mut Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Count lines, blank lines and comment lines (enabled by default).
+    pub fn count_lines(&mut self, yes: bool) -> &mut Self {
+        self.count_lines = yes;
+        self
+    }
+
+    /// Keep the path of every file in [`LanguageStats::paths`] (disabled by default).
+    pub fn collect_paths(&mut self, yes: bool) -> &mut Self {
+        self.collect_paths = yes;
+        self
+    }
+
+    /// Reuse the results of earlier scans stored in the cache file at `path`, and
+    /// update it afterwards.
+    ///
+    /// Files whose size and modification time haven't changed aren't read again. The
+    /// cache is discarded when the palate version (and with it the detection rules),
+    /// [`max_bytes`](Self::max_bytes) or the [overrides](Self::overrides) change. It is
+    /// best effort: an unreadable cache is treated as empty and a failure to write it is
+    /// ignored.
+    pub fn cache(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.cache = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Apply a project's own file type mappings and exclusions. Mapped text files are
+    /// reported with [`DetectionStage::Override`].
+    pub fn overrides(&mut self, overrides: Overrides) -> &mut Self {
+        self.overrides = Some(overrides);
+        self
+    }
+
+    /// Scan the members of tar, tar.gz, tar.zst and zip archives as if the archive was a
+    /// directory, recognizing archives by their content (disabled by default).
+    ///
+    /// Members are reported below the path of the archive, e.g. `dist.tar.gz/src/lib.rs`,
+    /// and go through the same exclusions and filters as files in a directory; archives
+    /// inside archives are scanned too. Archive members are never cached. Archives that
+    /// can't be seeked, those inside archives and in [revisions](Self::breakdown_at),
+    /// are streamed, except for zip archives, which are skipped when larger than
+    /// [`Self::max_bytes`].
+    #[cfg(feature = "archive")]
+    pub fn archives(&mut self, yes: bool) -> &mut Self {
+        self.archives = yes;
+        self
+    }
+
+    /// Run the scan and aggregate the files per language.
+    pub fn breakdown(&self) -> LanguageBreakdown {
+        self.scan(
+            |breakdown: &mut LanguageBreakdown, file| {
+                breakdown
+                    .languages
+                    .entry(file.file_type)
+                    .or_default()
+                    .add(file, self.collect_paths);
+            },
+            LanguageBreakdown::merge,
+        )
+    }
+
+    /// Like [`Self::breakdown`], with a breakdown per group: `group` names the groups a
+    /// file counts toward, which may be none or several.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use palate::scan::ScanBuilder;
+    ///
+    /// // Per top-level directory.
+    /// let breakdowns = ScanBuilder::new(".").breakdown_by(|file| {
+    ///     file.path.components().nth(1).map(|dir| dir.as_os_str().to_owned())
+    /// });
+    /// for (dir, breakdown) in breakdowns {
+    ///     println!("{}: {} files", dir.display(), breakdown.total().files);
+    /// }
+    /// ```
+    pub fn breakdown_by<K, I>(
+        &self,
+        group: impl Fn(&ScannedFile) -> I + Sync,
+    ) -> HashMap<K, LanguageBreakdown>
+    where
+        K: Eq + Hash + Send,
+        I: IntoIterator<Item = K>,
+    {
+        self.scan(
+            |groups: &mut HashMap<K, LanguageBreakdown>, file| {
+                self.add_grouped(groups, group(&file), file)
+            },
+            |groups, other| {
+                for (key, breakdown) in other {
+                    groups.entry(key).or_default().merge(breakdown);
+                }
+            },
+        )
+    }
+
+    /// Run the scan and return every file, sorted by path.
+    pub fn files(&self) -> Vec<ScannedFile> {
+        let mut files = self.scan(
+            |files: &mut Vec<ScannedFile>, file| files.push(file),
+            |files, other| files.extend(other),
+        );
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
+    }
+
+    /// Like [`Self::breakdown`], but of the files committed at `revision` (a commit id,
+    /// tag, branch or anything else `git rev-parse` understands) in the git repository
+    /// the roots are in. Files are read from the object database, without a checkout.
+    ///
+    /// The roots pick the parts of the tree to scan, like they pick the parts of the
+    /// working tree, and files are reported below them. Ignore rules and the
+    /// [cache](Self::cache) don't apply, since only committed files are read. This runs
+    /// the `git` command, and fails when it isn't installed, the roots aren't in a git
+    /// repository, or `revision` doesn't exist.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use palate::scan::ScanBuilder;
+    ///
+    /// let breakdown = ScanBuilder::new(".").breakdown_at("v1.0")?;
+    /// println!("{} files at v1.0", breakdown.total().files);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn breakdown_at(&self, revision: &str) -> io::Result<LanguageBreakdown> {
+        self.scan_revision(revision, |breakdown: &mut LanguageBreakdown, file| {
+            breakdown
+                .languages
+                .entry(file.file_type)
+                .or_default()
+                .add(file, self.collect_paths);
+        })
+    }
+
+    /// Like [`Self::breakdown_by`], but of the files committed at `revision`, see
+    /// [`Self::breakdown_at`].
+    pub fn breakdown_by_at<K, I>(
+        &self,
+        revision: &str,
+        group: impl Fn(&ScannedFile) -> I,
+    ) -> io::Result<HashMap<K, LanguageBreakdown>>
+    where
+        K: Eq + Hash,
+        I: IntoIterator<Item = K>,
+    {
+        self.scan_revision(
+            revision,
+            |groups: &mut HashMap<K, LanguageBreakdown>, file| {
+                self.add_grouped(groups, group(&file), file)
+            },
+        )
+    }
+
+    /// Like [`Self::files`], but of the files committed at `revision`, see
+    /// [`Self::breakdown_at`].
+    pub fn files_at(&self, revision: &str) -> io::Result<Vec<ScannedFile>> {
+        let mut files = self.scan_revision(revision, |files: &mut Vec<ScannedFile>, file| {
+            files.push(file)
+        })?;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Count `file` toward each of `keys`.
+    fn add_grouped<K: Eq + Hash>(
+        &self,
+        groups: &mut HashMap<K, LanguageBreakdown>,
+        keys: impl IntoIterator<Item = K>,
+        file: ScannedFile,
+    ) {
+        for key in keys {
+            groups
+                .entry(key)
+                .or_default()
+                .languages
+                .entry(file.file_type)
+                .or_default()
+                .add(file.clone(), self.collect_paths);
+        }
+    }
+
+    /// Read the files of `revision` below the roots, folding every detected file into
+    /// an `A` with `visit`.
+    fn scan_revision<A: Default>(
+        &self,
+        revision: &str,
+        mut visit: impl FnMut(&mut A, ScannedFile),
+    ) -> io::Result<A> {
+        let Some(first) = self.roots.first() else {
+            return Ok(A::default());
+        };
+        let repository = git::Repository::discover(first)?;
+        // Every root with its path below the top-level directory.
+        let roots = self
+            .roots
+            .iter()
+            .map(|root| Ok((root, repository.relative(root)?)))
+            .collect::<io::Result<Vec<_>>>()?;
+        let paths: Vec<&Path> = roots.iter().map(|(_, path)| path.as_path()).collect();
+        let blobs = repository.blobs(revision, &paths)?;
+
+        let exclusions = self.exclusions();
+        let hidden = self.ignore_rules && self.exclude_hidden;
+        // The blobs to read, with the paths they are reported as and the paths below
+        // their root, which the root itself is for a file root, like in a walk.
+        let mut wanted = Vec::new();
+        for blob in &blobs {
+            let Some((root, below)) = roots
+                .iter()
+                .find_map(|(root, path)| Some((root, blob.path.strip_prefix(path).ok()?)))
+            else {
+                continue;
+            };
+            let (path, relative) = if below.as_os_str().is_empty() {
+                (root.to_path_buf(), root.to_path_buf())
+            } else {
+                (root.join(below), below.to_path_buf())
+            };
+            if exclusions.excludes(&relative, false)
+                || (hidden && has_hidden_component(below))
+                || self.overridden_out(&path)
+            {
+                continue;
+            }
+            wanted.push((blob, path, relative));
+        }
+
+        let mut acc = A::default();
+        let blobs: Vec<&git::Blob> = wanted.iter().map(|(blob, ..)| *blob).collect();
+        repository.read_blobs(&blobs, |index, content| {
+            let (blob, path, relative) = &wanted[index];
+            let Ok(sniffed) = Sniffed::new(content, self.max_bytes) else {
+                return;
+            };
+            #[cfg(feature = "archive")]
+            if let Some(format) = archive::Format::of(&sniffed.head).filter(|_| self.archives) {
+                let Sniffed { head, reader, .. } = sniffed;
+                self.scan_streamed_archive(path, format, head, reader, 0, &mut |file| {
+                    visit(&mut acc, file)
+                });
+                return;
+            }
+            if let Some(file) = self.scan_sniffed(path, relative, blob.size, sniffed) {
+                visit(&mut acc, file);
+            }
+        })?;
+        Ok(acc)
+    }
+
+    /// Walk the roots in parallel, folding every detected file into a per-thread `A`
+    /// with `visit` and combining the per-thread results with `merge`.
+    fn scan<A, V, M>(&self, visit: V, merge: M) -> A
+    where
+        A: Default + Send,
+        V: Fn(&mut A, ScannedFile) + Sync,
+        M: Fn(&mut A, A),
+    {
+        if self.roots.is_empty() {
+            return A::default();
+        }
+        let explicit = self.explicit();
+        let walker = self
+            .walker(&self.roots, None)
+            .threads(self.threads)
+            .build_parallel();
+
+        let header = cache::header(self.max_bytes, self.overrides.as_ref());
+        let cache = self.cache.as_ref().map(|path| Cache::load(path, &header));
+        let finished: Mutex<Vec<(A, Seen)>> = Mutex::new(Vec::new());
+        walker.run(|| {
+            let mut local = Accumulator {
+                acc: A::default(),
+                seen: Vec::new(),
+                finished: &finished,
+            };
+            let visit = &visit;
+            let cache = cache.as_ref();
+            let explicit = &explicit;
+            Box::new(move |entry| {
+                let Ok(entry) = entry else {
+                    return WalkState::Continue;
+                };
+                if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                    return WalkState::Continue;
+                }
+                let relative = relative_path(&self.roots, &entry);
+                if entry.depth() == 0 && self.overridden_out(entry.path()) {
+                    return WalkState::Continue;
+                }
+                let scanner = if entry.depth() == 0 { explicit } else { self };
+                let Ok(metadata) = entry.metadata() else {
+                    return WalkState::Continue;
+                };
+                let mut visit = |file| visit(&mut local.acc, file);
+                match cache {
+                    Some(cache) if !is_compressed(entry.path()) => scanner.scan_cached_file(
+                        entry.path(),
+                        relative,
+                        &metadata,
+                        cache,
+                        &mut local.seen,
+                        &mut visit,
+                    ),
+                    _ => scanner.scan_file(entry.path(), relative, metadata.len(), &mut visit),
+                }
+                WalkState::Continue
+            })
+        });
+
+        let mut acc = A::default();
+        let mut seen = Vec::new();
+        for (other, other_seen) in finished.into_inner().unwrap_or_else(|e| e.into_inner()) {
+            merge(&mut acc, other);
+            seen.extend(other_seen);
+        }
+        if let (Some(cache), Some(path)) = (cache, &self.cache) {
+            // Best effort, see `ScanBuilder::cache`.
+            let _ = cache.save(path, &header, &self.roots, seen);
+        }
+        acc
+    }
+
+    /// A walk of `starts`, which are roots or directories below them, with the ignore
+    /// rules, exclusions and overrides of a scan. Right below the starts, only `only` is
+    /// entered, if given.
+    fn walker(&self, starts: &[PathBuf], only: Option<PathBuf>) -> WalkBuilder {
+        let mut builder = WalkBuilder::new(&starts[0]);
+        for start in &starts[1..] {
+            builder.add(start);
+        }
+        let exclusions = self.exclusions();
+        let overrides = self.overrides.clone();
+        let roots = self.roots.clone();
+        builder
+            .standard_filters(self.ignore_rules)
+            .hidden(self.ignore_rules && self.exclude_hidden)
+            .filter_entry(move |entry| {
+                if entry.depth() == 0 {
+                    return true;
+                }
+                if entry.file_name() == ".git"
+                    || (entry.depth() == 1
+                        && only.as_ref().is_some_and(|only| entry.path() != only))
+                {
+                    return false;
+                }
+                let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+                !exclusions.excludes(relative_path(&roots, entry), is_dir)
+                    && !overrides
+                        .as_ref()
+                        .is_some_and(|overrides| overrides.excludes(entry.path(), is_dir))
+            });
+        builder
+    }
+
+    /// Scan the file at `path` and hand it to `visit`, or the members of the archive it
+    /// is, see [`Self::archives`].
+    fn scan_file(
+        &self,
+        path: &Path,
+        relative: &Path,
+        bytes: u64,
+        visit: &mut dyn FnMut(ScannedFile),
+    ) {
+        let Ok(sniffed) = Sniffed::open(path, self.max_bytes) else {
+            return;
+        };
+        let Some(sniffed) = self.scan_if_archive(path, sniffed, visit) else {
+            return;
+        };
+        if let Some(file) = self.scan_sniffed(path, relative, bytes, sniffed) {
+            visit(file);
+        }
+    }
+
+    fn scan_sniffed(
+        &self,
+        path: &Path,
+        relative: &Path,
+        bytes: u64,
+        sniffed: Sniffed<impl Read>,
+    ) -> Option<ScannedFile> {
+        #[cfg(feature = "compression")]
+        if let Some((compression, inner)) = sniffed.compression(path) {
+            let sniffed = sniffed.decompressed(compression, self.max_bytes).ok()?;
+            let relative = relative.with_extension("");
+            let mut file = self.scan_text(&inner, &relative, bytes, sniffed)?;
+            file.path = path.to_path_buf();
+            file.compression = Some(compression);
+            return Some(file);
+        }
+        self.scan_text(path, relative, bytes, sniffed)
+    }
+
+    fn scan_text(
+        &self,
+        path: &Path,
+        relative: &Path,
+        bytes: u64,
+        sniffed: Sniffed<impl Read>,
+    ) -> Option<ScannedFile> {
+        if !self.is_text(path, &sniffed) {
+            return None;
+        }
+        let content = sniffed.content();
+        let generated = is_generated(relative, &content);
+        if self.exclude_generated && generated {
+            return None;
+        }
+        let (file_type, stage) = self.detect(path, &content);
+        if !self.wants(file_type, stage) {
+            return None;
+        }
+        let mime = sniffed.mime();
+        let lines = if self.count_lines {
+            sniffed.count_lines(file_type).ok()
+        } else {
+            None
+        };
+        Some(ScannedFile {
+            path: path.to_path_buf(),
+            file_type,
+            stage,
+            mime,
+            bytes,
+            lines,
+            generated,
+            test: is_test_file(relative, file_type),
+            #[cfg(feature = "compression")]
+            compression: None,
+        })
+    }
+
+    /// Scan the members of the archive `sniffed` was read from if it is one and archives
+    /// are scanned, see [`Self::archives`]; `sniffed` is handed back otherwise.
+    #[cfg(feature = "archive")]
+    fn scan_if_archive(
+        &self,
+        path: &Path,
+        mut sniffed: Sniffed,
+        visit: &mut dyn FnMut(ScannedFile),
+    ) -> Option<Sniffed> {
+        let Some(format) = archive::Format::of(&sniffed.head).filter(|_| self.archives) else {
+            return Some(sniffed);
+        };
+        // Zip archives need to seek, which the file can, from its start.
+        if io::Seek::rewind(&mut sniffed.reader).is_ok() {
+            let exclusions = self.exclusions();
+            let _ =
+                archive::for_each_member(format, sniffed.reader, &mut |member, bytes, content| {
+                    self.scan_member(path, member, bytes, content, &exclusions, 0, visit);
+                });
+        }
+        None
+    }
+
+    #[cfg(not(feature = "archive"))]
+    fn scan_if_archive(
+        &self,
+        _path: &Path,
+        sniffed: Sniffed,
+        _visit: &mut dyn FnMut(ScannedFile),
+    ) -> Option<Sniffed> {
+        Some(sniffed)
+    }
+
+    /// Scan the members of an archive that can only be read front to back, which was
+    /// read up to `head`, reporting them below `path`. This is best effort: a damaged
+    /// archive reports the members before the damage.
+    #[cfg(feature = "archive")]
+    fn scan_streamed_archive(
+        &self,
+        path: &Path,
+        format: archive::Format,
+        head: Vec<u8>,
+        rest: &mut dyn Read,
+        depth: usize,
+        visit: &mut dyn FnMut(ScannedFile),
+    ) {
+        let exclusions = self.exclusions();
+        let members = &mut |member: &Path, bytes, content: &mut dyn Read| {
+            self.scan_member(path, member, bytes, content, &exclusions, depth, visit);
+        };
+        let _ = archive::for_each_streamed_member(format, head, rest, self.max_bytes, members);
+    }
+
+    /// Scan a member of the archive at `archive`, which is itself `depth` archives deep.
+    #[cfg(feature = "archive")]
+    #[allow(clippy::too_many_arguments)]
+    fn scan_member(
+        &self,
+        archive: &Path,
+        member: &Path,
+        bytes: u64,
+        content: &mut dyn Read,
+        exclusions: &Exclusions,
+        depth: usize,
+        visit: &mut dyn FnMut(ScannedFile),
+    ) {
+        let member_path = archive.join(member);
+        if exclusions.excludes(member, false)
+            || self.overridden_out(&member_path)
+            || (self.ignore_rules && self.exclude_hidden && has_hidden_component(member))
+        {
+            return;
+        }
+        let Ok(sniffed) = Sniffed::new(content, self.max_bytes) else {
+            return;
+        };
+        match archive::Format::of(&sniffed.head).filter(|_| depth < MAX_ARCHIVE_DEPTH) {
+            Some(format) => {
+                let Sniffed { head, reader, .. } = sniffed;
+                self.scan_streamed_archive(&member_path, format, head, reader, depth + 1, visit);
+            }
+            None => {
+                if let Some(file) = self.scan_sniffed(&member_path, member, bytes, sniffed) {
+                    visit(file);
+                }
+            }
+        }
+    }
+
+    /// Like [`Self::scan_file`], but looks the file up in `cache` first and records
+    /// what it found in `seen`. Cache entries hold everything about a file regardless
+    /// of the filters, so they can serve differently configured scans.
+    fn scan_cached_file(
+        &self,
+        path: &Path,
+        relative: &Path,
+        metadata: &Metadata,
+        cache: &Cache,
+        seen: &mut Seen,
+        visit: &mut dyn FnMut(ScannedFile),
+    ) {
+        let Some(stamp) = Stamp::of(metadata) else {
+            return;
+        };
+        let mapped = self.mapped(path);
+        // Entries are only valid for the same mapping, since it decides the file type.
+        let cached = cache.get(path, stamp).filter(|entry| {
+            entry.detected.as_ref().is_none_or(|detected| {
+                (!self.count_lines || detected.lines.is_some())
+                    && match mapped {
+                        Some(file_type) => {
+                            detected.stage == DetectionStage::Override
+                                && detected.file_type == file_type
+                        }
+                        None => detected.stage != DetectionStage::Override,
+                    }
+            })
+        });
+        // Archives are recorded as binary files, and their members are never cached.
+        #[cfg(feature = "archive")]
+        let cached = cached.filter(|entry| !self.archives || entry.detected.is_some());
+        let entry = match cached {
+            Some(entry) => entry.clone(),
+            None => match self.detect_file(path, relative, visit) {
+                Ok(detected) => CacheEntry { stamp, detected },
+                Err(_) => return,
+            },
+        };
+        seen.push((path.to_path_buf(), entry.clone()));
+
+        let Some(detected) = entry.detected else {
+            return;
+        };
+        // Entries cover everything that looks like text, see `Self::detect_file`.
+        let text = detected.recognized
+            || self.keep_undetected
+            || detected.stage == DetectionStage::Override;
+        if !text
+            || (self.exclude_generated && detected.generated)
+            || !self.wants(detected.file_type, detected.stage)
+        {
+            return;
+        }
+        visit(ScannedFile {
+            path: path.to_path_buf(),
+            file_type: detected.file_type,
+            stage: detected.stage,
+            mime: detected.mime,
+            bytes: stamp.size(),
+            lines: detected.lines.filter(|_| self.count_lines),
+            generated: detected.generated,
+            test: is_test_file(relative, detected.file_type),
+            #[cfg(feature = "compression")]
+            compression: None,
+        });
+    }
+
+    /// Everything the cache records about a file; `None` for binary files. Files that
+    /// only look like text are recorded too, so that the entry serves any scan. The
+    /// members of archives go to `visit`, see [`Self::archives`].
+    fn detect_file(
+        &self,
+        path: &Path,
+        relative: &Path,
+        visit: &mut dyn FnMut(ScannedFile),
+    ) -> io::Result<Option<Detected>> {
+        let sniffed = Sniffed::open(path, self.max_bytes)?;
+        let Some(sniffed) = self.scan_if_archive(path, sniffed, visit) else {
+            return Ok(None);
+        };
+        let recognized = sniffed.is_text(path);
+        if !recognized && !sniffed.looks_like_text() {
+            return Ok(None);
+        }
+        let content = sniffed.content();
+        let (file_type, stage) = self.detect(path, &content);
+        Ok(Some(Detected {
+            file_type,
+            stage,
+            mime: sniffed.mime(),
+            generated: is_generated(relative, &content),
+            recognized,
+            lines: if self.count_lines {
+                Some(sniffed.count_lines(file_type)?)
+            } else {
+                None
+            },
+        }))
+    }
+
+    /// Files the overrides map only need to look like text, and so does everything
+    /// when undetected files are kept.
+    fn is_text(&self, path: &Path, sniffed: &Sniffed<impl Read>) -> bool {
+        sniffed.is_text(path)
+            || ((self.keep_undetected || self.mapped(path).is_some()) && sniffed.looks_like_text())
+    }
+
+    /// [`try_detect_with_stage`](crate::try_detect_with_stage), unless the overrides map
+    /// `path`.
+    fn detect(&self, path: &Path, content: &str) -> (FileType, DetectionStage) {
+        match self.mapped(path) {
+            Some(file_type) => (file_type, DetectionStage::Override),
+            None => detect_with_stage(path, content)
+                .unwrap_or((FileType::Text, DetectionStage::Fallback)),
+        }
+    }
+
+    fn mapped(&self, path: &Path) -> Option<FileType> {
+        self.overrides.as_ref()?.file_type(path)
+    }
+
+    fn overridden_out(&self, path: &Path) -> bool {
+        self.overrides
+            .as_ref()
+            .is_some_and(|overrides| overrides.excludes(path, false))
+    }
+
+    /// This scan for the files given as roots. They were asked for by name, so only
+    /// the overrides exclude them: not the path exclusions, and neither being
+    /// generated nor undetected.
+    fn explicit(&self) -> Self {
+        let mut explicit = self.clone();
+        explicit.exclude_generated = false;
+        explicit.keep_undetected = true;
+        explicit
+    }
+
+    fn exclusions(&self) -> Exclusions {
+        Exclusions {
+            vendored: self.exclude_vendored,
+            documentation: self.exclude_documentation,
+        }
+    }
+
+    fn wants(&self, file_type: FileType, stage: DetectionStage) -> bool {
+        (self.keep_undetected || stage != DetectionStage::Fallback)
+            && self
+                .types
+                .as_ref()
+                .is_none_or(|types| types.contains(&file_type))
+    }
+}
+
+/// Whether a directory or file on `path` is hidden, for inputs the walker's own check
+/// doesn't see.
+fn has_hidden_component(path: &Path) -> bool {
+    path.components().any(|component| {
+        matches!(component, std::path::Component::Normal(name)
+            if name.as_encoded_bytes().starts_with(b"."))
+    })
+}
+
+/// Whether `path` has a compression suffix. Such files are never cached, since the
+/// cache doesn't record the compression.
+#[cfg(feature = "compression")]
+fn is_compressed(path: &Path) -> bool {
+    crate::Compression::from_path(path).is_some()
+}
+
+#[cfg(not(feature = "compression"))]
+fn is_compressed(_path: &Path) -> bool {
+    false
+}
+
+/// Which Linguist path classes are pruned from the walk.
+#[derive(Clone, Copy)]
+struct Exclusions {
+    vendored: bool,
+    documentation: bool,
+}
+
+impl Exclusions {
+    /// `relative` is the path below the scan root; directories are matched with a
+    /// trailing `/` so patterns like `(^|/)node_modules/` prune the whole tree.
+    fn excludes(&self, relative: &Path, is_dir: bool) -> bool {
+        if !self.vendored && !self.documentation {
+            return false;
+        }
+        let mut relative = relative.to_string_lossy().into_owned();
+        if is_dir {
+            relative.push('/');
+        }
+        (self.vendored && is_vendored(&relative))
+            || (self.documentation && is_documentation(&relative))
+    }
+}
+
+/// The path of `entry` below the root it was found under; roots themselves are kept
+/// as given.
+fn relative_path<'a>(roots: &[PathBuf], entry: &'a DirEntry) -> &'a Path {
+    let path = entry.path();
+    if entry.depth() == 0 {
+        return path;
+    }
+    roots
+        .iter()
+        .find_map(|root| path.strip_prefix(root).ok())
+        .unwrap_or(path)
+}
+
+/// Cache entries of the files a worker thread scanned.
+type Seen = Vec<(PathBuf, CacheEntry)>;
+
+/// A worker thread's accumulator and the cache entries it saw; hands them over when
+/// the walker drops it.
+struct Accumulator<'a, A: Default> {
+    acc: A,
+    seen: Seen,
+    finished: &'a Mutex<Vec<(A, Seen)>>,
+}
+
+impl<A: Default> Drop for Accumulator<'_, A> {
+    fn drop(&mut self) {
+        let acc = mem::take(&mut self.acc);
+        let seen = mem::take(&mut self.seen);
+        self.finished
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((acc, seen));
+    }
+}
+
+/// A file (or any other reader) with its first bytes already read for detection.
+///
+/// This is what a scan does with every file, exposed for inputs that don't live in a
+/// directory tree, like blobs read from stdin or a git object database.
+///
+/// # Example
+/// ```
+/// use palate::{FileType, detect, scan::Sniffed};
+///
+/// let sniffed = Sniffed::new("#!/bin/sh\necho hi\n".as_bytes(), 1024).unwrap();
+/// assert!(sniffed.looks_like_text());
+/// assert_eq!(FileType::Sh, detect("script", &sniffed.content()));
+/// assert_eq!(2, sniffed.count_lines(FileType::Sh).unwrap().lines);
+/// ```
+pub struct Sniffed<R = File> {
+    reader: R,
+    head: Vec<u8>,
+    /// `true` when `head` holds all of the input.
+    complete: bool,
+    kind: Option<infer::Type>,
+}
+
+impl Sniffed {
+    /// Open `path` and read up to `max_bytes` of it.
+    pub fn open(path: impl AsRef<Path>, max_bytes: usize) -> io::Result<Self> {
+        Self::new(File::open(path)?, max_bytes)
+    }
+}
+
+impl<R: Read> Sniffed<R> {
+    /// Read up to `max_bytes` of `reader`.
+    pub fn new(mut reader: R, max_bytes: usize) -> io::Result<Self> {
+        let mut head = Vec::new();
+        (&mut reader)
+            .take(max_bytes as u64)
+            .read_to_end(&mut head)?;
+        let complete = head.len() < max_bytes;
+        let kind = infer::get(&head);
+        Ok(Self {
+            reader,
+            head,
+            complete,
+            kind,
+        })
+    }
+
+    /// Same rules as [`is_text_file`](crate::is_text_file), applied to the bytes
+    /// already read.
+    pub fn is_text(&self, path: impl AsRef<Path>) -> bool {
+        match self.kind {
+            Some(kind) => kind.matcher_type() == MatcherType::Text,
+            None => try_detect(path, "").is_some(),
+        }
+    }
+
+    /// Whether the input is an archive [`ScanBuilder::archives`] looks into.
+    #[cfg(feature = "archive")]
+    pub fn is_archive(&self) -> bool {
+        archive::Format::of(&self.head).is_some()
+    }
+
+    /// The compression of a file named like `schema.sql.gz` whose content is
+    /// compressed that way, with the path without the compression suffix.
+    #[cfg(feature = "compression")]
+    pub fn compression(&self, path: impl AsRef<Path>) -> Option<(crate::Compression, PathBuf)> {
+        crate::Compression::from_path(path)
+            .filter(|(compression, _)| compression.matches(&self.head))
+    }
+
+    /// The decompressed input, with up to `max_bytes` of it read for detection.
+    #[cfg(feature = "compression")]
+    pub fn decompressed<'a>(
+        self,
+        compression: crate::Compression,
+        max_bytes: usize,
+    ) -> io::Result<Sniffed<Box<dyn Read + 'a>>>
+    where
+        R: 'a,
+    {
+        let compressed = io::Cursor::new(self.head).chain(self.reader);
+        Sniffed::new(compression.decoder(compressed)?, max_bytes)
+    }
+
+    /// Content-only check for input without a meaningful path: text formats `infer`
+    /// recognizes, and anything it doesn't recognize that has no NUL bytes.
+    pub fn looks_like_text(&self) -> bool {
+        match self.kind {
+            Some(kind) => kind.matcher_type() == MatcherType::Text,
+            None => !self.head.contains(&0),
+        }
+    }
+
+    /// The MIME type `infer` recognized, if any.
+    pub fn inferred_mime(&self) -> Option<&'static str> {
+        self.kind.map(|kind| kind.mime_type())
+    }
+
+    /// MIME type for text input: the sniffed one, `text/plain` otherwise.
+    pub fn mime(&self) -> &'static str {
+        self.inferred_mime().unwrap_or("text/plain")
+    }
+
+    /// The bytes read so far, decoded lossily as UTF-8.
+    pub fn content(&self) -> String {
+        String::from_utf8_lossy(&self.head).into_owned()
+    }
+
+    /// Count lines over all of the input, continuing after the bytes already read, with
+    /// the comment syntax of `file_type`.
+    pub fn count_lines(mut self, file_type: FileType) -> io::Result<LineCounts> {
+        let mut counter = LineCounter::new(file_type);
+        counter.feed(&self.head);
+        if !self.complete {
+            let mut buf = mem::take(&mut self.head);
+            buf.resize(64 * 1024, 0);
+            loop {
+                let n = self.reader.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                counter.feed(&buf[..n]);
+            }
+        }
+        Ok(counter.finish())
+    }
+}
+
+/// Counts lines, whitespace-only lines and comment lines over a byte stream fed in
+/// chunks.
+#[derive(Default)]
+struct LineCounter {
+    counts: LineCounts,
+    /// Bytes seen since the last newline.
+    pending: bool,
+    pending_blank: bool,
+    /// Present for languages with known comment syntax; the current line is kept in
+    /// `line` for it.
+    comments: Option<CommentScanner>,
+    line: Vec<u8>,
+}
+
+impl LineCounter {
+    fn new(file_type: FileType) -> Self {
+        Self {
+            comments: CommentScanner::new(file_type),
+            ..Self::default()
+        }
+    }
+
+    fn feed(&mut self, chunk: &[u8]) {
+        for &b in chunk {
+            if !self.pending {
+                self.pending = true;
+                self.pending_blank = true;
+            }
+            if !b.is_ascii_whitespace() {
+                self.pending_blank = false;
+            }
+            if b == b'\n' {
+                self.end_line();
+            } else if self.comments.is_some() {
+                self.line.push(b);
+            }
+        }
+    }
+
+    fn end_line(&mut self) {
+        self.counts.lines += 1;
+        if self.pending_blank {
+            self.counts.blanks += 1;
+        } else if let Some(comments) = &mut self.comments {
+            if comments.is_comment_line(&self.line) {
+                self.counts.comments += 1;
+            }
+        }
+        self.line.clear();
+        self.pending = false;
+    }
+
+    fn finish(mut self) -> LineCounts {
+        if self.pending {
+            self.end_line();
+        }
+        self.counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directory under the system temp dir, removed when it's dropped, even when the
+    /// test fails.
+    pub(super) fn temp_dir() -> tempfile::TempDir {
+        tempfile::Builder::new()
+            .prefix("palate-")
+            .tempdir()
+            .unwrap()
+    }
+
+    /// A [`temp_dir`] holding `files`.
+    pub(super) fn fixture(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let tmp = temp_dir();
+        for (path, content) in files {
+            let path = tmp.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        tmp
+    }
+
+    #[test]
+    fn test_count_lines() {
+        let counts = |content: &str| count_lines(content.as_bytes(), FileType::Text).unwrap();
+        assert_eq!(LineCounts::default(), counts(""));
+        assert_eq!(
+            LineCounts {
+                lines: 1,
+                blanks: 0,
+                comments: 0
+            },
+            counts("a")
+        );
+        assert_eq!(
+            LineCounts {
+                lines: 3,
+                blanks: 2,
+                comments: 0
+            },
+            counts("a\n\n  \t\n")
+        );
+        let shell = "#!/bin/sh\n# comment\n\necho '#' # trailing\n  # indented";
+        assert_eq!(
+            LineCounts {
+                lines: 5,
+                blanks: 1,
+                comments: 3
+            },
+            count_lines(shell.as_bytes(), FileType::Sh).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_sniffed_counts_past_the_head() {
+        let content = "line\n".repeat(100);
+        let sniffed = Sniffed::new(content.as_bytes(), 16).unwrap();
+        assert_eq!("line\nline\nline\nl", sniffed.content());
+        assert_eq!(100, sniffed.count_lines(FileType::Text).unwrap().lines);
+    }
+
+    #[test]
+    fn test_breakdown_exclusions() {
+        let tmp = fixture(&[
+            ("src/main.rs", "fn main() {}\n"),
+            (
+                "src/schema.rs",
+                "// @generated by diesel\npub mod schema {}\n",
+            ),
+            ("vendor/lib/lib.rs", "pub fn f() {}\n"),
+            ("docs/guide.md", "# Guide\n"),
+        ]);
+        let dir = tmp.path();
+        let breakdown = ScanBuilder::new(dir).breakdown();
+        let with_generated = ScanBuilder::new(dir).exclude_generated(false).breakdown();
+        let rust = &breakdown.languages[&FileType::Rust];
+        assert_eq!(1, rust.files);
+        assert!(rust.bytes > 0 && rust.paths.is_empty());
+        assert_eq!(2, with_generated.languages[&FileType::Rust].files);
+        assert_eq!(breakdown.total().files, rust.files);
+    }
+
+    #[test]
+    fn test_cached_breakdown_matches() {
+        let tmp = fixture(&[
+            ("src/main.rs", "fn main() {}\n"),
+            ("src/util.h", "#include <vector>\n"),
+            ("run", "#!/usr/bin/env python3\nprint(1)\n"),
+        ]);
+        let dir = tmp.path();
+        let cache_dir = temp_dir();
+        let cache = cache_dir.path().join("cache");
+        let uncached = ScanBuilder::new(dir).breakdown();
+        let cold = ScanBuilder::new(dir).cache(&cache).breakdown();
+        let warm = ScanBuilder::new(dir).cache(&cache).breakdown();
+        let kept = std::fs::read_to_string(&cache).unwrap();
+        assert_eq!(3, uncached.total().files);
+        assert_eq!(uncached, cold);
+        assert_eq!(uncached, warm);
+        assert!(kept.contains(&format!("\t{}\n", dir.join("src/main.rs").display())));
+    }
+
+    #[test]
+    fn test_files_filtered_and_sorted() {
+        let tmp = fixture(&[
+            ("Cargo.toml", "[package]\nname = \"app\"\n"),
+            ("src/main.rs", "mod util;\nfn main() {}\n"),
+            ("src/util.rs", "pub fn f() {}\n"),
+            ("src/config.toml", "debug = true\n"),
+            ("src/app.py", "print(1)\n"),
+        ]);
+        let dir = tmp.path();
+        let manifest = dir.join("Cargo.toml");
+        let files = ScanBuilder::new(dir.join("src"))
+            .add(&manifest)
+            .types([FileType::Toml, FileType::Rust])
+            .count_lines(false)
+            .files();
+        assert_eq!(4, files.len());
+        assert!(files.iter().any(|file| file.path == manifest));
+        assert!(files.iter().all(|file| file.lines.is_none()));
+        assert!(files.windows(2).all(|pair| pair[0].path <= pair[1].path));
+        let collected: LanguageBreakdown = files.into_iter().collect();
+        let toml = &collected.languages[&FileType::Toml];
+        assert!(toml.paths.contains(&manifest));
+        assert_eq!(2, toml.files);
+        assert_eq!(toml.paths.len(), toml.files);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let tmp = fixture(&[
+            ("scan/mod.rs", "mod cache;\n"),
+            ("scan/cache.rs", "pub struct Cache;\n"),
+            ("scan/comments.rs", "// comments\n"),
+        ]);
+        let dir = tmp.path();
+        let mut overrides = Overrides::new(dir);
+        overrides
+            .filename("mod.rs", FileType::Text)
+            .exclude("/scan/comments.rs")
+            .unwrap();
+        let files = ScanBuilder::new(dir.join("scan"))
+            .overrides(overrides)
+            .files();
+        let stage = |name: &str| {
+            let file = files.iter().find(|file| file.path.ends_with(name))?;
+            Some((file.file_type, file.stage))
+        };
+        assert_eq!(
+            Some((FileType::Text, DetectionStage::Override)),
+            stage("mod.rs")
+        );
+        assert!(stage("cache.rs").is_some_and(|(file_type, stage)| {
+            file_type == FileType::Rust && stage != DetectionStage::Override
+        }));
+        assert_eq!(None, stage("comments.rs"));
+    }
+
+    #[test]
+    fn test_keep_undetected() {
+        let tmp = temp_dir();
+        let dir = tmp.path();
+        std::fs::write(dir.join("NOTES"), "remember the milk\n").unwrap();
+        let cache_dir = temp_dir();
+        let cache = cache_dir.path().join("cache");
+        let strict = ScanBuilder::new(dir).cache(&cache).files();
+        let kept = ScanBuilder::new(dir)
+            .cache(&cache)
+            .keep_undetected(true)
+            .files();
+        let uncached = ScanBuilder::new(dir).keep_undetected(true).files();
+        assert!(strict.is_empty());
+        for files in [kept, uncached] {
+            assert_eq!(1, files.len());
+            assert_eq!(FileType::Text, files[0].file_type);
+            assert_eq!(DetectionStage::Fallback, files[0].stage);
+        }
+    }
+
+    #[test]
+    fn test_explicit_files() {
+        let tmp = temp_dir();
+        let dir = tmp.path();
+        let files = [
+            ".github/workflows/ci.yml",
+            "vendor/lib.js",
+            "api.pb.go",
+            "NOTES",
+            "fixtures/data.json",
+        ];
+        for file in files {
+            let path = dir.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let content = if file.ends_with(".go") {
+                "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api\n"
+            } else {
+                "remember the milk\n"
+            };
+            std::fs::write(path, content).unwrap();
+        }
+        let mut overrides = Overrides::new(dir);
+        overrides.exclude("fixtures/").unwrap();
+
+        let walked = ScanBuilder::new(dir).overrides(overrides.clone()).files();
+        assert!(walked.is_empty());
+        let mut scanner = ScanBuilder::default();
+        for file in files {
+            scanner.add(dir.join(file));
+        }
+        let mut scanned: Vec<_> = scanner
+            .overrides(overrides)
+            .files()
+            .into_iter()
+            .map(|file| file.path)
+            .collect();
+        scanned.sort();
+        let mut expected: Vec<_> = files[..4].iter().map(|file| dir.join(file)).collect();
+        expected.sort();
+        assert_eq!(expected, scanned);
+    }
+
+    #[test]
+    fn test_revisions() {
+        let tmp = temp_dir();
+        let dir = tmp.path();
+        std::fs::create_dir_all(dir.join("app")).unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args([
+                    "-c",
+                    "user.name=palate",
+                    "-c",
+                    "user.email=palate@example.com",
+                ])
+                .args(["-c", "commit.gpgsign=false"])
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {args:?}");
+        };
+        git(&["init", "-q"]);
+        std::fs::write(dir.join("app/main.py"), "print('hi')\n").unwrap();
+        std::fs::write(dir.join(".hidden.py"), "print('hi')\n").unwrap();
+        std::fs::create_dir(dir.join("legacy")).unwrap();
+        std::fs::write(dir.join("legacy/tool.py"), "x = 1\n".repeat(1000)).unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "Start in Python"]);
+        std::fs::remove_file(dir.join("app/main.py")).unwrap();
+        std::fs::remove_dir_all(dir.join("legacy")).unwrap();
+        std::fs::write(dir.join("app/main.rs"), "fn main() {}\n").unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "Rewrite it in Rust"]);
+        // Uncommitted changes aren't seen.
+        std::fs::write(dir.join("app/lib.go"), "package app\n").unwrap();
+
+        let commits = commits(dir, "HEAD", 10).unwrap();
+        let files = ScanBuilder::new(dir.join("app"))
+            .files_at("HEAD~1")
+            .unwrap();
+        // Roots only need to exist in the revision, and blobs are read past `max_bytes`
+        // only to count their lines.
+        let legacy = ScanBuilder::new(dir.join("app/../legacy"))
+            .max_bytes(64)
+            .files_at("HEAD~1")
+            .unwrap();
+        let breakdown = ScanBuilder::new(dir).breakdown_at(&commits[0].id).unwrap();
+        let unknown = ScanBuilder::new(dir).breakdown_at("v9").unwrap_err();
+
+        let summaries: Vec<&str> = commits.iter().map(|c| c.summary.as_str()).collect();
+        assert_eq!(vec!["Rewrite it in Rust", "Start in Python"], summaries);
+        assert_eq!(1, files.len());
+        assert_eq!(dir.join("app/main.py"), files[0].path);
+        assert_eq!(FileType::Python, files[0].file_type);
+        assert_eq!(12, files[0].bytes);
+        assert_eq!(1, legacy.len());
+        assert_eq!(dir.join("app/../legacy/tool.py"), legacy[0].path);
+        assert_eq!(6000, legacy[0].bytes);
+        assert_eq!(Some(1000), legacy[0].lines.map(|counts| counts.lines));
+        assert_eq!(
+            vec![&FileType::Rust],
+            breakdown.languages.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(io::ErrorKind::NotFound, unknown.kind());
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compressed() {
+        use std::io::Write;
+
+        let tmp = temp_dir();
+        let dir = tmp.path();
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gz.write_all(b"-- schema\nCREATE TABLE t (id int);\n\n")
+            .unwrap();
+        std::fs::write(dir.join("schema.sql.gz"), gz.finish().unwrap()).unwrap();
+        let cache_dir = temp_dir();
+        let cache = cache_dir.path().join("cache");
+        let uncached = ScanBuilder::new(dir).files();
+        let cached = ScanBuilder::new(dir).cache(&cache).files();
+        for files in [uncached, cached] {
+            assert_eq!(1, files.len());
+            assert_eq!(dir.join("schema.sql.gz"), files[0].path);
+            assert_eq!(FileType::Sql, files[0].file_type);
+            assert_eq!(Some(crate::Compression::Gzip), files[0].compression);
+            let counts = files[0].lines.unwrap();
+            assert_eq!((3, 1, 1), (counts.lines, counts.comments, counts.blanks));
+        }
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn test_archives() {
+        use std::io::Write;
+
+        let tmp = temp_dir();
+        let dir = tmp.path();
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, content) in [
+            ("pkg/src/lib.rs", "pub fn f() {}\n"),
+            ("pkg/node_modules/x/index.js", "module.exports = 1;\n"),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gz.write_all(&builder.into_inner().unwrap()).unwrap();
+        let inner = gz.finish().unwrap();
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(inner.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "inner.tar.gz", inner.as_slice())
+            .unwrap();
+        std::fs::write(dir.join("dist.tar"), builder.into_inner().unwrap()).unwrap();
+        let cache_dir = temp_dir();
+        let cache = cache_dir.path().join("cache");
+        let ignored = ScanBuilder::new(dir).files();
+        assert!(ignored.is_empty());
+        for _ in 0..2 {
+            for files in [
+                ScanBuilder::new(dir).archives(true).files(),
+                ScanBuilder::new(dir).archives(true).cache(&cache).files(),
+            ] {
+                assert_eq!(1, files.len());
+                assert_eq!(
+                    dir.join("dist.tar/inner.tar.gz/pkg/src/lib.rs"),
+                    files[0].path
+                );
+                assert_eq!(FileType::Rust, files[0].file_type);
+                assert_eq!(Some(1), files[0].lines.map(|counts| counts.lines));
+            }
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Project-specific file type mappings and exclusions.
+
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::FileType;
+
+/// File types and exclusions a project decides for itself, applied on top of
+/// detection by [`ScanBuilder::overrides`](super::ScanBuilder::overrides).
+///
+/// Globs use `.gitignore` syntax relative to the project root: `*.inc` matches at any
+/// depth, `/scripts/*` only below the root, and `fixtures/` everything in a
+/// `fixtures` directory. A glob mapping wins over a file name mapping, which wins over
+/// an extension mapping; the first matching glob wins.
+///
+/// # Example
+/// ```
+/// use palate::{FileType, scan::Overrides};
+///
+/// let mut overrides = Overrides::new("/project");
+/// overrides
+///     .extension("inc", FileType::Php)
+///     .glob("/templates/*.inc", FileType::Html)
+///     .unwrap()
+///     .exclude("fixtures/")
+///     .unwrap();
+/// assert_eq!(Some(FileType::Php), overrides.file_type("/project/lib/db.inc"));
+/// assert_eq!(Some(FileType::Html), overrides.file_type("/project/templates/a.inc"));
+/// assert!(overrides.excludes("/project/tests/fixtures/a.rs", false));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Overrides {
+    root: PathBuf,
+    extensions: HashMap<String, FileType>,
+    filenames: HashMap<String, FileType>,
+    /// Each glob with its matcher.
+    globs: Vec<(String, Gitignore, FileType)>,
+    excluded: Vec<String>,
+    exclude: Gitignore,
+}
+
+impl Overrides {
+    /// Overrides for the project in `root`; relative roots are taken from the current
+    /// directory.
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref();
+        Self {
+            root: std::path::absolute(root).unwrap_or_else(|_| root.to_path_buf()),
+            extensions: HashMap::new(),
+            filenames: HashMap::new(),
+            globs: Vec::new(),
+            excluded: Vec::new(),
+            exclude: Gitignore::empty(),
+        }
+    }
+
+    /// The project root.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Detect files with this extension (without the dot, any case) as `file_type`.
+    pub fn extension(&mut self, extension: &str, file_type: FileType) -> &mut Self {
+        let extension = extension.trim_start_matches('.').to_ascii_lowercase();
+        self.extensions.insert(extension, file_type);
+        self
+    }
+
+    /// Detect files with exactly this name as `file_type`.
+    pub fn filename(&mut self, filename: &str, file_type: FileType) -> &mut Self {
+        self.filenames.insert(filename.to_string(), file_type);
+        self
+    }
+
+    /// Detect files matching `glob` as `file_type`; fails if `glob` isn't valid.
+    pub fn glob(&mut self, glob: &str, file_type: FileType) -> io::Result<&mut Self> {
+        self.globs
+            .push((glob.to_string(), matcher([glob])?, file_type));
+        Ok(self)
+    }
+
+    /// Leave out paths matching `glob`, or keep them again for a `!glob`; fails if
+    /// `glob` isn't valid.
+    pub fn exclude(&mut self, glob: &str) -> io::Result<&mut Self> {
+        self.exclude = matcher(self.excluded.iter().map(String::as_str).chain([glob]))?;
+        self.excluded.push(glob.to_string());
+        Ok(self)
+    }
+
+    /// Whether nothing has been configured.
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+            && self.filenames.is_empty()
+            && self.globs.is_empty()
+            && self.excluded.is_empty()
+    }
+
+    /// The file type the project maps `path` to, if any.
+    pub fn file_type(&self, path: impl AsRef<Path>) -> Option<FileType> {
+        let path = path.as_ref();
+        if let Some(relative) = self.relative(path) {
+            let glob = self.globs.iter().find(|(_, glob, _)| {
+                glob.matched_path_or_any_parents(&relative, false)
+                    .is_ignore()
+            });
+            if let Some(&(_, _, file_type)) = glob {
+                return Some(file_type);
+            }
+        }
+        let filename = path.file_name().and_then(|name| name.to_str());
+        if let Some(&file_type) = filename.and_then(|name| self.filenames.get(name)) {
+            return Some(file_type);
+        }
+        let extension = path.extension().and_then(|ext| ext.to_str())?;
+        self.extensions
+            .get(&extension.to_ascii_lowercase())
+            .copied()
+    }
+
+    /// Whether the project excludes `path`, a directory if `is_dir`.
+    pub fn excludes(&self, path: impl AsRef<Path>, is_dir: bool) -> bool {
+        if self.excluded.is_empty() {
+            return false;
+        }
+        self.relative(path.as_ref()).is_some_and(|relative| {
+            self.exclude
+                .matched_path_or_any_parents(&relative, is_dir)
+                .is_ignore()
+        })
+    }
+
+    /// Everything configured, in a stable form, for telling apart the caches of scans
+    /// with different overrides.
+    pub(super) fn describe(&self) -> String {
+        let mut extensions: Vec<_> = self.extensions.iter().collect();
+        extensions.sort_unstable_by_key(|(extension, _)| *extension);
+        let mut filenames: Vec<_> = self.filenames.iter().collect();
+        filenames.sort_unstable_by_key(|(filename, _)| *filename);
+        let mut out = format!("root {}\n", self.root.display());
+        for (extension, file_type) in extensions {
+            out.push_str(&format!("extension {extension} {file_type}\n"));
+        }
+        for (filename, file_type) in filenames {
+            out.push_str(&format!("filename {filename} {file_type}\n"));
+        }
+        for (glob, _, file_type) in &self.globs {
+            out.push_str(&format!("glob {glob} {file_type}\n"));
+        }
+        for glob in &self.excluded {
+            out.push_str(&format!("exclude {glob}\n"));
+        }
+        out
+    }
+
+    /// `path` below the root; `None` for paths outside of the project.
+    fn relative(&self, path: &Path) -> Option<PathBuf> {
+        let path = std::path::absolute(path).ok()?;
+        let relative = path.strip_prefix(&self.root).ok()?;
+        (!relative.as_os_str().is_empty()).then(|| relative.to_path_buf())
+    }
+}
+
+/// A matcher for `.gitignore` lines, matched against relative paths.
+fn matcher<'a>(lines: impl IntoIterator<Item = &'a str>) -> io::Result<Gitignore> {
+    let invalid = |err| io::Error::new(io::ErrorKind::InvalidInput, err);
+    let mut builder = GitignoreBuilder::new("");
+    for line in lines {
+        builder.add_line(None, line).map_err(invalid)?;
+    }
+    builder.build().map_err(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapping_precedence() {
+        let mut overrides = Overrides::new("project");
+        overrides
+            .extension(".H", FileType::Cpp)
+            .filename("BUILD", FileType::Bzl)
+            .glob("legacy/", FileType::C)
+            .unwrap();
+        assert_eq!(Some(FileType::Cpp), overrides.file_type("project/src/a.h"));
+        assert_eq!(Some(FileType::Bzl), overrides.file_type("elsewhere/BUILD"));
+        assert_eq!(
+            Some(FileType::C),
+            overrides.file_type("project/legacy/x/a.h")
+        );
+        assert_eq!(None, overrides.file_type("project/src/a.c"));
+        assert!(overrides.glob("src/{a,b", FileType::C).is_err());
+    }
+
+    #[test]
+    fn test_excludes() {
+        let mut overrides = Overrides::new("project");
+        assert!(!overrides.excludes("project/gen", true));
+        overrides
+            .exclude("/gen/")
+            .unwrap()
+            .exclude("*.snap")
+            .unwrap()
+            .exclude("!keep.snap")
+            .unwrap();
+        assert!(overrides.excludes("project/gen", true));
+        assert!(overrides.excludes("./project/gen/a.rs", false));
+        assert!(!overrides.excludes("project/src/gen", true));
+        assert!(overrides.excludes("project/tests/a.snap", false));
+        assert!(!overrides.excludes("project/tests/keep.snap", false));
+        assert!(!overrides.excludes("other/a.snap", false));
+    }
+}
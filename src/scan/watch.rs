@@ -0,0 +1,433 @@
+//! Keeping a scan up to date with filesystem events.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io,
+    ops::Bound,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use super::{LanguageBreakdown, ScanBuilder, ScannedFile, relative_path};
+
+/// How long events have to stop coming in before they are applied, so that saving a
+/// file or checking out a branch is one update.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// A scan kept in memory and updated as files change, see [`ScanBuilder::watch`].
+pub struct Watch {
+    scanner: ScanBuilder,
+    /// The roots as given, and canonicalized to match the paths of events.
+    roots: Vec<(PathBuf, PathBuf)>,
+    files: BTreeMap<PathBuf, ScannedFile>,
+    /// The directories the scan entered, which are the ones watched.
+    dirs: BTreeSet<PathBuf>,
+    watcher: RecommendedWatcher,
+    events: mpsc::Receiver<notify::Result<notify::Event>>,
+}
+
+/// The files an update of a [`Watch`] touched.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Update {
+    /// Files that were scanned again, because they were added, changed or renamed.
+    pub changed: Vec<PathBuf>,
+    /// Files that are gone, or no longer wanted by the scan.
+    pub removed: Vec<PathBuf>,
+}
+
+impl Update {
+    /// Whether no file was touched.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// A directory or file the walk of a [`Watch`] came across.
+enum Walked {
+    Dir(PathBuf),
+    File(ScannedFile),
+}
+
+impl ScanBuilder {
+    /// Scan the roots, then watch them: [`Watch::wait`] applies filesystem events by
+    /// scanning only the files that changed.
+    ///
+    /// The scan honors the same ignore rules, exclusions and filters as
+    /// [`Self::breakdown`], except that it runs on one thread and doesn't use the
+    /// [cache](Self::cache). Only the directories it enters are watched, so ignored trees
+    /// like `node_modules` cost nothing; changing a `.gitignore` or `.ignore` file scans
+    /// its directory again.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use palate::scan::ScanBuilder;
+    ///
+    /// let mut watch = ScanBuilder::new(".").watch()?;
+    /// loop {
+    ///     watch.wait(None)?;
+    ///     println!("{} files", watch.breakdown().total().files);
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn watch(&self) -> io::Result<Watch> {
+        let (sender, events) = mpsc::channel();
+        let watcher = notify::recommended_watcher(sender).map_err(watch_error)?;
+        let roots = self
+            .roots
+            .iter()
+            .map(|root| Ok((root.clone(), root.canonicalize()?)))
+            .collect::<io::Result<Vec<_>>>()?;
+        let mut watch = Watch {
+            scanner: self.clone(),
+            roots,
+            files: BTreeMap::new(),
+            dirs: BTreeSet::new(),
+            watcher,
+            events,
+        };
+        for (root, canonical) in watch.roots.clone() {
+            // Editors replace files rather than write them, which ends a watch on the
+            // file itself.
+            if let Some(parent) = canonical.parent().filter(|_| !canonical.is_dir()) {
+                watch
+                    .watcher
+                    .watch(parent, RecursiveMode::NonRecursive)
+                    .map_err(watch_error)?;
+            }
+            watch.refresh(&root, &mut Update::default())?;
+        }
+        Ok(watch)
+    }
+
+    /// Walk `start` on this thread like [`Self::scan`] does, entering only `only` right
+    /// below it if given.
+    fn walk(&self, start: &Path, only: Option<&Path>, visit: &mut dyn FnMut(Walked)) {
+        let explicit = self.explicit();
+        let walker = self
+            .walker(&[start.to_path_buf()], only.map(Path::to_path_buf))
+            .build();
+        for entry in walker {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            match entry.file_type() {
+                Some(file_type) if file_type.is_dir() => {
+                    visit(Walked::Dir(entry.into_path()));
+                    continue;
+                }
+                Some(file_type) if file_type.is_file() => {}
+                _ => continue,
+            }
+            let relative = relative_path(&self.roots, &entry);
+            if entry.depth() == 0 && self.overridden_out(entry.path()) {
+                continue;
+            }
+            let scanner = if entry.depth() == 0 { &explicit } else { self };
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            scanner.scan_file(entry.path(), relative, metadata.len(), &mut |file| {
+                visit(Walked::File(file))
+            });
+        }
+    }
+}
+
+impl Watch {
+    /// The current breakdown.
+    pub fn breakdown(&self) -> LanguageBreakdown {
+        let mut breakdown = LanguageBreakdown::default();
+        for file in self.files.values() {
+            breakdown
+                .languages
+                .entry(file.file_type)
+                .or_default()
+                .add(file.clone(), self.scanner.collect_paths);
+        }
+        breakdown
+    }
+
+    /// The current files, sorted by path.
+    pub fn files(&self) -> impl Iterator<Item = &ScannedFile> {
+        self.files.values()
+    }
+
+    /// Wait until files change and apply the changes, or until `timeout` passes without
+    /// changes, which returns `None`. Events that only touch ignored or excluded files
+    /// don't end the wait.
+    pub fn wait(&mut self, timeout: Option<Duration>) -> io::Result<Option<Update>> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            let first = match deadline {
+                Some(deadline) => {
+                    let left = deadline.saturating_duration_since(Instant::now());
+                    match self.events.recv_timeout(left) {
+                        Ok(event) => event,
+                        Err(RecvTimeoutError::Timeout) => return Ok(None),
+                        Err(RecvTimeoutError::Disconnected) => return Err(stopped()),
+                    }
+                }
+                None => self.events.recv().map_err(|_| stopped())?,
+            };
+            let mut paths = BTreeSet::new();
+            let mut rescan = false;
+            self.collect(first, &mut paths, &mut rescan)?;
+            while let Ok(event) = self.events.recv_timeout(DEBOUNCE) {
+                self.collect(event, &mut paths, &mut rescan)?;
+            }
+
+            let mut update = Update::default();
+            if rescan {
+                for (root, _) in self.roots.clone() {
+                    self.refresh(&root, &mut update)?;
+                }
+            } else {
+                // A refresh covers everything below its path.
+                let mut refreshed: Vec<PathBuf> = Vec::new();
+                for path in paths {
+                    let Some(path) = self.refreshed(path) else {
+                        continue;
+                    };
+                    if refreshed.iter().any(|done| path.starts_with(done)) {
+                        continue;
+                    }
+                    self.refresh(&path, &mut update)?;
+                    refreshed.push(path);
+                }
+            }
+            if !update.is_empty() {
+                return Ok(Some(update));
+            }
+        }
+    }
+
+    /// Add the scan paths of `event` to `paths`, or set `rescan` if the events can't be
+    /// trusted anymore.
+    fn collect(
+        &self,
+        event: notify::Result<notify::Event>,
+        paths: &mut BTreeSet<PathBuf>,
+        rescan: &mut bool,
+    ) -> io::Result<()> {
+        let event = event.map_err(watch_error)?;
+        if event.need_rescan() {
+            *rescan = true;
+        }
+        // Reading files is an event, too.
+        if matches!(event.kind, EventKind::Access(_)) {
+            return Ok(());
+        }
+        paths.extend(event.paths.iter().filter_map(|path| self.scan_path(path)));
+        Ok(())
+    }
+
+    /// What to scan again for a change of `path`: the path itself, or the directory of a
+    /// changed ignore file. `None` if `path` is somewhere the scan doesn't go.
+    fn refreshed(&self, path: PathBuf) -> Option<PathBuf> {
+        if self.is_root(&path) {
+            return Some(path);
+        }
+        let parent = path.parent()?;
+        if !self.dirs.contains(parent) {
+            return None;
+        }
+        let ignore_file = path
+            .file_name()
+            .is_some_and(|name| name == ".gitignore" || name == ".ignore");
+        if ignore_file && self.scanner.ignore_rules {
+            return Some(parent.to_path_buf());
+        }
+        Some(path)
+    }
+
+    /// Forget everything at and below `path` and scan it again.
+    fn refresh(&mut self, path: &Path, update: &mut Update) -> io::Result<()> {
+        let old_files: Vec<PathBuf> = below(&self.files, path).cloned().collect();
+        for file in &old_files {
+            self.files.remove(file);
+        }
+        let old_dirs: Vec<PathBuf> = self
+            .dirs
+            .range::<Path, _>((Bound::Included(path), Bound::Unbounded))
+            .take_while(|dir| dir.starts_with(path))
+            .cloned()
+            .collect();
+        for dir in &old_dirs {
+            self.dirs.remove(dir);
+        }
+
+        let mut dirs = Vec::new();
+        let mut files = Vec::new();
+        if path.symlink_metadata().is_ok() {
+            let mut visit = |walked| match walked {
+                Walked::Dir(dir) => dirs.push(dir),
+                Walked::File(file) => files.push(file),
+            };
+            match path.parent().filter(|_| !self.is_root(path)) {
+                Some(parent) => self.scanner.walk(parent, Some(path), &mut visit),
+                None => self.scanner.walk(path, None, &mut visit),
+            }
+        }
+
+        for dir in dirs.into_iter().filter(|dir| dir.starts_with(path)) {
+            if !old_dirs.contains(&dir) {
+                let watched = self
+                    .watcher
+                    .watch(&self.absolute(&dir), RecursiveMode::NonRecursive);
+                // The directory may be gone again already.
+                if let Err(err) = watched.map_err(watch_error) {
+                    if err.kind() != io::ErrorKind::NotFound {
+                        return Err(err);
+                    }
+                }
+            }
+            self.dirs.insert(dir);
+        }
+        for dir in old_dirs {
+            if !self.dirs.contains(&dir) {
+                let _ = self.watcher.unwatch(&self.absolute(&dir));
+            }
+        }
+        for file in files {
+            update.changed.push(file.path.clone());
+            self.files.insert(file.path.clone(), file);
+        }
+        update.removed.extend(
+            old_files
+                .into_iter()
+                .filter(|file| !self.files.contains_key(file)),
+        );
+        Ok(())
+    }
+
+    fn is_root(&self, path: &Path) -> bool {
+        self.roots.iter().any(|(root, _)| root == path)
+    }
+
+    /// The path a scan reports for `absolute`, the path of an event.
+    fn scan_path(&self, absolute: &Path) -> Option<PathBuf> {
+        self.roots.iter().find_map(|(root, canonical)| {
+            let below = absolute.strip_prefix(canonical).ok()?;
+            Some(if below.as_os_str().is_empty() {
+                root.clone()
+            } else {
+                root.join(below)
+            })
+        })
+    }
+
+    /// The absolute path of `path`, a path the scan reports.
+    fn absolute(&self, path: &Path) -> PathBuf {
+        self.roots
+            .iter()
+            .find_map(|(root, canonical)| {
+                let below = path.strip_prefix(root).ok()?;
+                Some(if below.as_os_str().is_empty() {
+                    canonical.clone()
+                } else {
+                    canonical.join(below)
+                })
+            })
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+}
+
+/// The keys of `files` at and below `path`, which sort right after it.
+fn below<'a>(
+    files: &'a BTreeMap<PathBuf, ScannedFile>,
+    path: &'a Path,
+) -> impl Iterator<Item = &'a PathBuf> {
+    files
+        .range::<Path, _>((Bound::Included(path), Bound::Unbounded))
+        .map(|(file, _)| file)
+        .take_while(move |file| file.starts_with(path))
+}
+
+fn watch_error(err: notify::Error) -> io::Error {
+    match err.kind {
+        notify::ErrorKind::Io(err) => err,
+        notify::ErrorKind::PathNotFound | notify::ErrorKind::WatchNotFound => {
+            io::Error::new(io::ErrorKind::NotFound, "no such file or directory")
+        }
+        _ => io::Error::other(err),
+    }
+}
+
+fn stopped() -> io::Error {
+    io::Error::other("the filesystem watcher stopped")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileType, scan::tests::temp_dir};
+
+    /// Wait for updates until `done` holds for the watch.
+    fn wait_until(watch: &mut Watch, done: impl Fn(&Watch) -> bool) -> Update {
+        let mut seen = Update::default();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !done(watch) {
+            let left = deadline.saturating_duration_since(Instant::now());
+            let update = watch.wait(Some(left)).unwrap().expect("timed out");
+            seen.changed.extend(update.changed);
+            seen.removed.extend(update.removed);
+        }
+        seen
+    }
+
+    #[test]
+    fn test_watch() {
+        let tmp = temp_dir();
+        let dir = tmp.path();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.join("node_modules/left-pad")).unwrap();
+        std::fs::write(dir.join("src/main.py"), "print('hi')\n").unwrap();
+        std::fs::write(dir.join(".ignore"), "build/\n").unwrap();
+
+        let mut watch = ScanBuilder::new(dir).watch().unwrap();
+        let count = |watch: &Watch, file_type| {
+            watch
+                .breakdown()
+                .languages
+                .get(&file_type)
+                .map_or(0, |stats| stats.files)
+        };
+        assert_eq!(1, count(&watch, FileType::Python));
+
+        std::fs::write(dir.join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+        let update = wait_until(&mut watch, |watch| count(watch, FileType::Rust) == 1);
+        assert!(update.changed.contains(&dir.join("src/lib.rs")));
+
+        std::fs::create_dir_all(dir.join("src/app")).unwrap();
+        std::fs::write(dir.join("src/app/main.go"), "package main\n").unwrap();
+        wait_until(&mut watch, |watch| count(watch, FileType::Go) == 1);
+
+        std::fs::write(dir.join("src/main.py"), "#!/bin/sh\necho hi\n").unwrap();
+        std::fs::rename(dir.join("src/main.py"), dir.join("src/main")).unwrap();
+        let update = wait_until(&mut watch, |watch| count(watch, FileType::Sh) == 1);
+        assert!(update.removed.contains(&dir.join("src/main.py")));
+        assert_eq!(0, count(&watch, FileType::Python));
+
+        std::fs::create_dir_all(dir.join("build")).unwrap();
+        std::fs::write(dir.join("build/out.js"), "x()\n").unwrap();
+        std::fs::write(dir.join("node_modules/left-pad/index.js"), "x()\n").unwrap();
+        std::fs::write(dir.join(".ignore"), "").unwrap();
+        wait_until(&mut watch, |watch| count(watch, FileType::JavaScript) == 1);
+
+        std::fs::remove_dir_all(dir.join("src/app")).unwrap();
+        wait_until(&mut watch, |watch| count(watch, FileType::Go) == 0);
+        let paths: Vec<&Path> = watch.files().map(|file| file.path.as_path()).collect();
+        assert_eq!(
+            vec![
+                dir.join("build/out.js"),
+                dir.join("src/lib.rs"),
+                dir.join("src/main")
+            ],
+            paths
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    path::Path,
+    process::{Command, Output},
+};
+
+/// Run the `palate` binary in `dir`.
+fn palate(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_palate"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("run palate")
+}
+
+/// A temp dir holding `files`, removed when it's dropped.
+fn project(files: &[(&str, &str)]) -> tempfile::TempDir {
+    let dir = tempfile::Builder::new()
+        .prefix("palate-")
+        .tempdir()
+        .unwrap();
+    for (path, content) in files {
+        let path = dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn check_exit_codes() {
+    let policy =
+        |level: &str| format!("[[check.rules]]\nforbid = [\"perl\"]\nlevel = \"{level}\"\n");
+    let perl = ("build.pl", "#!/usr/bin/perl\nprint 1;\n");
+    let code = |files: &[(&str, &str)], args: &[&str]| {
+        let dir = project(files);
+        palate(dir.path(), &[&["check"], args].concat())
+            .status
+            .code()
+    };
+
+    assert_eq!(
+        Some(1),
+        code(&[(".palate.toml", &policy("deny")), perl], &[])
+    );
+    assert_eq!(
+        Some(0),
+        code(&[(".palate.toml", &policy("warn")), perl], &[])
+    );
+    assert_eq!(Some(0), code(&[(".palate.toml", &policy("deny"))], &[]));
+    // No policy, a config that doesn't parse, a path that doesn't exist.
+    assert_eq!(Some(2), code(&[(".palate.toml", ""), perl], &[]));
+    assert_eq!(
+        Some(2),
+        code(&[(".palate.toml", "[check]\nunknown = 1\n"), perl], &[])
+    );
+    assert_eq!(
+        Some(2),
+        code(&[(".palate.toml", &policy("deny")), perl], &["missing"])
+    );
+}
+
+#[test]
+fn files_from_keeps_excluded_paths() {
+    let dir = project(&[
+        (".github/workflows/ci.yml", "on: push\n"),
+        (
+            "api.pb.go",
+            "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api\n",
+        ),
+        ("fixtures/data.json", "{}\n"),
+        (".palate.toml", "exclude = [\"fixtures/\"]\n"),
+        (
+            "files",
+            ".github/workflows/ci.yml\napi.pb.go\nfixtures/data.json\n",
+        ),
+    ]);
+    let output = palate(dir.path(), &["--files-from", "files", "-o", "json"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\"YAML\""), "{stdout}");
+    assert!(stdout.contains("\"Go\""), "{stdout}");
+    assert!(!stdout.contains("\"JSON\""), "{stdout}");
+}
+
+#[test]
+fn paths_named_like_subcommands() {
+    let dir = project(&[
+        ("check/main.rs", "fn main() {}\n"),
+        ("src/app.py", "x = 1\n"),
+    ]);
+    let scan = |args: &[&str]| {
+        let output = palate(dir.path(), &[&["-o", "json"], args].concat());
+        assert!(output.status.success(), "{args:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // A bare subcommand name that is also a directory is ambiguous.
+    let bare = palate(dir.path(), &["check"]);
+    assert_eq!(Some(2), bare.status.code());
+    let stderr = String::from_utf8(bare.stderr).unwrap();
+    assert!(stderr.contains("./check"), "{stderr}");
+    // With an argument it's the subcommand, which finds no policy in .palate.toml.
+    let subcommand = palate(dir.path(), &["check", "."]);
+    assert_eq!(Some(2), subcommand.status.code());
+    let stderr = String::from_utf8(subcommand.stderr).unwrap();
+    assert!(stderr.contains("no [check] policy"), "{stderr}");
+    // An option in front makes it a path.
+    assert!(scan(&["check"]).contains("\"Rust\""));
+    assert!(scan(&["./check"]).contains("\"Rust\""));
+    let both = scan(&["src", "check"]);
+    assert!(
+        both.contains("\"Rust\"") && both.contains("\"Python\""),
+        "{both}"
+    );
+}
+
+#[test]
+fn report_with_output_defaults() {
+    let dir = project(&[
+        (
+            ".palate.toml",
+            "[output]\ncolor = \"never\"\nby = \"files\"\ntop = 3\n",
+        ),
+        ("main.rs", "fn main() {}\n"),
+    ]);
+    for format in ["svg", "html"] {
+        let output = palate(dir.path(), &["report", format]);
+        assert!(
+            output.status.success(),
+            "{format}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(String::from_utf8(output.stdout).unwrap().contains("Rust"));
+    }
+}
+
+#[test]
+fn history_with_output_defaults() {
+    let dir = project(&[
+        (
+            ".palate.toml",
+            "[output]\nsort = \"name\"\ncolor = \"never\"\ntop = 1\n",
+        ),
+        ("main.rs", "fn main() {}\n"),
+        ("app.py", "x = 1\n"),
+    ]);
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .current_dir(dir.path())
+            .args([
+                "-c",
+                "user.name=palate",
+                "-c",
+                "user.email=palate@example.com",
+            ])
+            .args(["-c", "commit.gpgsign=false"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?}");
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-qm", "first"]);
+
+    let output = palate(dir.path(), &["history", "--by", "files"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    // `top = 1` leaves one language and merges the other into "other".
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("other"), "{stdout}");
+    // The commits come from one repository, so history follows a single path.
+    assert_eq!(
+        Some(2),
+        palate(dir.path(), &["history", "main.rs", "app.py"])
+            .status
+            .code()
+    );
+}
@@ -346,7 +346,7 @@ fn samples_accuracy() {
                                 .as_ref()
                                 .and_then(|m| m.get(&got))
                                 .is_some_and(|got_parsers| {
-                                    got_parsers.iter().any(|gp| expected_parsers.contains(&gp))
+                                    got_parsers.iter().any(|gp| expected_parsers.contains(gp))
                                 })) =>
                 {
                     correct += 1